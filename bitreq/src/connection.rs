@@ -274,7 +274,8 @@ impl AsyncConnection {
                 #[cfg(not(feature = "tokio-rustls"))]
                 return Err(Error::HttpsFeatureNotEnabled);
                 #[cfg(feature = "tokio-rustls")]
-                rustls_stream::wrap_async_stream(socket, params.host).await
+                rustls_stream::wrap_async_stream(socket, params.host, params.root_certificates)
+                    .await
             } else {
                 Ok(AsyncHttpStream::Unsecured(socket))
             }
@@ -339,6 +340,10 @@ impl AsyncConnection {
             Some(proxy) => {
                 // do proxy things
                 let mut tcp = Self::tcp_connect(&proxy.server, proxy.port).await?;
+                if proxy.is_socks5() {
+                    proxy.socks5_handshake_async(&mut tcp, params.host, params.port).await?;
+                    return Ok(tcp);
+                }
 
                 let proxy_request = proxy.connect(params.host, params.port);
                 tcp.write_all(proxy_request.as_bytes()).await?;
@@ -373,7 +378,7 @@ impl AsyncConnection {
         }
 
         #[cfg(not(feature = "proxy"))]
-        Self::tcp_connect(&params.host, params.port).await
+        Self::tcp_connect(params.host, params.port).await
     }
 
     async fn timeout<O, F: Future<Output = O>>(timeout: Option<Instant>, f: F) -> Result<O, Error> {
@@ -657,7 +662,8 @@ impl Connection {
             return Err(Error::HttpsFeatureNotEnabled);
             #[cfg(feature = "rustls")]
             {
-                let tls = rustls_stream::wrap_stream(socket, params.host)?;
+                let tls =
+                    rustls_stream::wrap_stream(socket, params.host, params.root_certificates)?;
                 HttpStream::Secured(Box::new(tls), timeout_at)
             }
         } else {
@@ -712,6 +718,13 @@ impl Connection {
             Some(proxy) => {
                 // do proxy things
                 let mut tcp = Self::tcp_connect(&proxy.server, proxy.port, timeout_at)?;
+                if proxy.is_socks5() {
+                    let timeout = timeout_at_to_duration(timeout_at)?;
+                    tcp.set_read_timeout(timeout)?;
+                    tcp.set_write_timeout(timeout)?;
+                    proxy.socks5_handshake(&mut tcp, params.host, params.port)?;
+                    return Ok(tcp);
+                }
 
                 write!(tcp, "{}", proxy.connect(params.host, params.port))?;
                 tcp.flush()?;
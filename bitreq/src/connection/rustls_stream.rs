@@ -5,8 +5,12 @@
 use alloc::sync::Arc;
 #[cfg(feature = "rustls")]
 use core::convert::TryFrom;
+#[cfg(any(feature = "rustls", feature = "native-tls"))]
+use std::collections::HashMap;
 use std::io;
 use std::net::TcpStream;
+#[cfg(any(feature = "rustls", feature = "native-tls"))]
+use std::sync::Mutex;
 use std::sync::OnceLock;
 
 #[cfg(all(feature = "native-tls", not(feature = "rustls")))]
@@ -33,7 +37,50 @@ pub type SecuredStream = StreamOwned<ClientConnection, TcpStream>;
 static CONFIG: OnceLock<Arc<ClientConfig>> = OnceLock::new();
 
 #[cfg(feature = "rustls")]
-fn build_client_config() -> Arc<ClientConfig> {
+fn build_client_config() -> Arc<ClientConfig> { Arc::new(client_config(default_root_store())) }
+
+/// The configs trusting additional root certificates, by the DER encoded certificates.
+#[cfg(feature = "rustls")]
+type ConfigsWithRoots = Mutex<HashMap<Vec<Vec<u8>>, Arc<ClientConfig>>>;
+
+#[cfg(feature = "rustls")]
+static CONFIGS_WITH_ROOTS: OnceLock<ConfigsWithRoots> = OnceLock::new();
+
+/// Returns the config trusting `root_certificates` in addition to the default root certificates.
+///
+/// The config is built once for each set of root certificates, loading the default root
+/// certificates and building a config for every connection would be slow.
+#[cfg(feature = "rustls")]
+fn config_with_roots(root_certificates: &[Vec<u8>]) -> Result<Arc<ClientConfig>, Error> {
+    if root_certificates.is_empty() {
+        return Ok(CONFIG.get_or_init(build_client_config).clone());
+    }
+    let configs = CONFIGS_WITH_ROOTS.get_or_init(Default::default);
+    let mut configs = configs.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(config) = configs.get(root_certificates) {
+        return Ok(config.clone());
+    }
+
+    let mut roots = default_root_store();
+    for der in root_certificates {
+        roots.add(&rustls::Certificate(der.clone())).map_err(Error::RustlsCreateConnection)?;
+    }
+    let config = Arc::new(client_config(roots));
+    configs.insert(root_certificates.to_vec(), config.clone());
+    Ok(config)
+}
+
+#[cfg(feature = "rustls")]
+fn client_config(root_certificates: RootCertStore) -> ClientConfig {
+    ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(root_certificates)
+        .with_no_client_auth()
+}
+
+#[cfg(feature = "rustls")]
+fn default_root_store() -> RootCertStore {
+    #[allow(unused_mut)]
     let mut root_certificates = RootCertStore::empty();
 
     // Try to load native certs
@@ -56,22 +103,22 @@ fn build_client_config() -> Arc<ClientConfig> {
         )
     }));
 
-    let config = ClientConfig::builder()
-        .with_safe_defaults()
-        .with_root_certificates(root_certificates)
-        .with_no_client_auth();
-    Arc::new(config)
+    root_certificates
 }
 
 #[cfg(feature = "rustls")]
-pub(super) fn wrap_stream(tcp: TcpStream, host: &str) -> Result<SecuredStream, Error> {
+pub(super) fn wrap_stream(
+    tcp: TcpStream,
+    host: &str,
+    root_certificates: &[Vec<u8>],
+) -> Result<SecuredStream, Error> {
     #[cfg(feature = "log")]
     log::trace!("Setting up TLS parameters for {host}.");
     let dns_name = match ServerName::try_from(host) {
         Ok(result) => result,
         Err(err) => return Err(Error::IoError(io::Error::new(io::ErrorKind::Other, err))),
     };
-    let sess = ClientConnection::new(CONFIG.get_or_init(build_client_config).clone(), dns_name)
+    let sess = ClientConnection::new(config_with_roots(root_certificates)?, dns_name)
         .map_err(Error::RustlsCreateConnection)?;
 
     #[cfg(feature = "log")]
@@ -88,6 +135,7 @@ pub type AsyncSecuredStream = TlsStream<tokio::net::TcpStream>;
 pub(super) async fn wrap_async_stream(
     tcp: AsyncTcpStream,
     host: &str,
+    root_certificates: &[Vec<u8>],
 ) -> Result<AsyncHttpStream, Error> {
     #[cfg(feature = "log")]
     log::trace!("Setting up TLS parameters for {host}.");
@@ -96,7 +144,7 @@ pub(super) async fn wrap_async_stream(
         Err(err) => return Err(Error::IoError(io::Error::new(io::ErrorKind::Other, err))),
     };
 
-    let connector = TlsConnector::from(CONFIG.get_or_init(build_client_config).clone());
+    let connector = TlsConnector::from(config_with_roots(root_certificates)?);
 
     #[cfg(feature = "log")]
     log::trace!("Establishing TLS session to {host}.");
@@ -128,14 +176,54 @@ fn build_tls_connector() -> Result<TlsConnector, Error> {
     TlsConnector::builder().build().map_err(Error::NativeTlsError)
 }
 
+/// The connectors trusting additional root certificates, by the DER encoded certificates.
+#[cfg(all(feature = "native-tls", not(feature = "rustls")))]
+type ConnectorsWithRoots = Mutex<HashMap<Vec<Vec<u8>>, TlsConnector>>;
+
+#[cfg(all(feature = "native-tls", not(feature = "rustls")))]
+static CONNECTORS_WITH_ROOTS: OnceLock<ConnectorsWithRoots> = OnceLock::new();
+
+/// Returns the connector trusting `root_certificates` in addition to the default root certificates.
+///
+/// Like `config_with_roots`, the connector is built once for each set of root certificates.
+#[cfg(all(feature = "native-tls", not(feature = "rustls")))]
+fn connector_with_roots(root_certificates: &[Vec<u8>]) -> Result<TlsConnector, Error> {
+    if root_certificates.is_empty() {
+        // TODO: Once we can `get_or_try_init`, so that instead
+        // https://github.com/rust-lang/rust/issues/109737
+        return CONNECTOR
+            .get_or_init(build_tls_connector)
+            .as_ref()
+            .cloned()
+            .map_err(|_| Error::Other("failed to build the TLS connector"));
+    }
+    let connectors = CONNECTORS_WITH_ROOTS.get_or_init(Default::default);
+    let mut connectors = connectors.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(connector) = connectors.get(root_certificates) {
+        return Ok(connector.clone());
+    }
+
+    let mut builder = TlsConnector::builder();
+    for der in root_certificates {
+        let certificate =
+            native_tls::Certificate::from_der(der).map_err(Error::NativeTlsCreateConnection)?;
+        builder.add_root_certificate(certificate);
+    }
+    let connector = builder.build().map_err(Error::NativeTlsCreateConnection)?;
+    connectors.insert(root_certificates.to_vec(), connector.clone());
+    Ok(connector)
+}
+
 #[cfg(all(feature = "native-tls", not(feature = "rustls")))]
-pub(super) fn wrap_stream(tcp: TcpStream, host: &str) -> Result<SecuredStream, Error> {
+pub(super) fn wrap_stream(
+    tcp: TcpStream,
+    host: &str,
+    root_certificates: &[Vec<u8>],
+) -> Result<SecuredStream, Error> {
     #[cfg(feature = "log")]
     log::trace!("Setting up TLS parameters for {host}.");
 
-    // TODO: Once we can `get_or_try_init`, so that instead
-    // https://github.com/rust-lang/rust/issues/109737
-    let connector = CONNECTOR.get_or_init(build_tls_connector)?;
+    let connector = connector_with_roots(root_certificates)?;
 
     #[cfg(feature = "log")]
     log::trace!("Establishing TLS session to {host}.");
@@ -150,13 +238,12 @@ pub type AsyncSecuredStream = tokio_native_tls::TlsStream<tokio::net::TcpStream>
 pub(super) async fn wrap_async_stream(
     tcp: AsyncTcpStream,
     host: &str,
+    root_certificates: &[Vec<u8>],
 ) -> Result<AsyncHttpStream, Error> {
     #[cfg(feature = "log")]
     log::trace!("Setting up TLS parameters for {host}.");
 
-    // TODO: Once we can `get_or_try_init`, so that instead
-    // https://github.com/rust-lang/rust/issues/109737
-    let connector = AsyncTlsConnector::from(CONNECTOR.get_or_init(build_tls_connector)?.clone());
+    let connector = AsyncTlsConnector::from(connector_with_roots(root_certificates)?);
 
     #[cfg(feature = "log")]
     log::trace!("Establishing TLS session to {host}.");
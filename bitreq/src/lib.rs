@@ -83,7 +83,7 @@
 //!
 //! ## `proxy`
 //!
-//! This feature enables HTTP proxy support.
+//! This feature enables HTTP and SOCKS5 proxy support.
 //!
 //! # Examples
 //!
@@ -199,8 +199,9 @@
 //! `.with_proxy()` on your request.
 //!
 //! Supported proxy formats are `host:port` and
-//! `user:password@proxy:host`. Only HTTP CONNECT proxies are
-//! supported at this time.
+//! `user:password@proxy:host`. HTTP CONNECT proxies are created with
+//! `Proxy::new_http` and SOCKS5 proxies (e.g. Tor) with
+//! `Proxy::new_socks5`.
 //!
 //! ```no_run
 //! # #[cfg(feature = "std")]
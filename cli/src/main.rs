@@ -0,0 +1,278 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! A small debugging tool that calls Bitcoin Core using the typed client.
+//!
+//! For the methods in [`MODELLED`] the version specific type returned by the node is printed
+//! followed by the model, or the error converting into it, so running the tool against a live
+//! node doubles as a check that the types match. Any other method, or a modelled method called
+//! with arguments, is called with the arguments parsed as JSON (like `bitcoin-cli`) and the JSON
+//! result is printed.
+
+use std::convert::Infallible;
+use std::path::PathBuf;
+use std::{env, error, fmt, process};
+
+use client::client_sync::{self, Auth};
+use serde_json::Value;
+
+/// The error returned by the tool.
+type Result<T> = std::result::Result<T, Box<dyn error::Error>>;
+
+/// The default URL, of a mainnet node on the local host.
+const DEFAULT_URL: &str = "http://127.0.0.1:8332";
+
+/// The methods for which the version specific type and the model are printed.
+const MODELLED: &[&str] = &[
+    "getbalance",
+    "getbestblockhash",
+    "getblockchaininfo",
+    "getblockcount",
+    "getchaintips",
+    "getdifficulty",
+    "getmempoolinfo",
+    "getmininginfo",
+    "getnetworkinfo",
+    "getrawmempool",
+    "gettxoutsetinfo",
+    "getwalletinfo",
+];
+
+fn main() {
+    let opts = match Options::parse(env::args().skip(1)) {
+        Ok(Some(opts)) => opts,
+        Ok(None) => {
+            print!("{}", usage());
+            return;
+        }
+        Err(e) => {
+            eprint!("error: {}\n\n{}", e, usage());
+            process::exit(2);
+        }
+    };
+    if let Err(e) = run(opts) {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
+
+/// Returns the usage message.
+fn usage() -> String {
+    format!(
+        "\
+Usage: corepc-cli [OPTIONS] <METHOD> [ARGS]...
+
+Options:
+    --url <URL>             URL of the JSON-RPC server [default: {}]
+    --cookie <PATH>         Authenticate using the cookie file at PATH
+    --user <USER>           Authenticate as USER, requires --password
+    --password <PASSWORD>   Authenticate using PASSWORD
+    --core <VERSION>        Major version of Core e.g., 29 [default: detected]
+    -h, --help              Print this message
+
+Modelled methods:
+    {}
+",
+        DEFAULT_URL,
+        MODELLED.join("\n    ")
+    )
+}
+
+/// The command line options.
+#[derive(Debug, PartialEq)]
+struct Options {
+    url: String,
+    auth: Auth,
+    core: Option<u32>,
+    method: String,
+    args: Vec<Value>,
+}
+
+impl Options {
+    /// Parses the command line arguments, returns `None` if help was requested.
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Option<Self>> {
+        let mut url = DEFAULT_URL.to_owned();
+        let mut cookie = None;
+        let mut user = None;
+        let mut password = None;
+        let mut core = None;
+
+        let method = loop {
+            let arg = args.next().ok_or("missing method")?;
+            let mut value = || args.next().ok_or_else(|| format!("missing value for {}", arg));
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(None),
+                "--url" => url = value()?,
+                "--cookie" => cookie = Some(PathBuf::from(value()?)),
+                "--user" => user = Some(value()?),
+                "--password" => password = Some(value()?),
+                "--core" => core = Some(value()?.parse().map_err(|_| "invalid Core version")?),
+                _ if arg.starts_with('-') => return Err(format!("unknown option: {}", arg).into()),
+                _ => break arg,
+            }
+        };
+
+        let auth = match (cookie, user, password) {
+            (None, None, None) => Auth::None,
+            (Some(path), None, None) => Auth::CookieFile(path),
+            (None, Some(user), Some(password)) => Auth::UserPass(user, password.into()),
+            (Some(_), _, _) => return Err("--cookie can not be used with --user/--password".into()),
+            (None, _, _) => return Err("--user and --password must be used together".into()),
+        };
+        // Like `bitcoin-cli`, arguments that are not valid JSON are strings.
+        let args =
+            args.map(|arg| serde_json::from_str(&arg).unwrap_or(Value::String(arg))).collect();
+
+        Ok(Some(Self { url, auth, core, method, args }))
+    }
+}
+
+/// Creates the version specific client in `client_sync::$module`.
+macro_rules! new_client {
+    ($module:ident, $url:expr, $auth:expr) => {
+        match $auth {
+            Auth::None => client_sync::$module::Client::new($url),
+            auth => client_sync::$module::Client::new_with_auth($url, auth)?,
+        }
+    };
+}
+
+/// Runs the command described by `opts`.
+fn run(opts: Options) -> Result<()> {
+    let core = match opts.core {
+        Some(core) => core,
+        None => detect_version(&opts.url, opts.auth.clone())?,
+    };
+    call(core, &opts.url, opts.auth, &opts.method, &opts.args)
+}
+
+/// Returns the major version of the node, using `getnetworkinfo`.
+fn detect_version(url: &str, auth: Auth) -> Result<u32> {
+    let client = new_client!(v17, url, auth);
+    let info: Value = client.call("getnetworkinfo", &[])?;
+    let version = info["version"].as_u64().ok_or("getnetworkinfo returned no version")?;
+    // E.g. 170100 for v0.17.1 and 290000 for v29.0.
+    Ok((version / 10_000) as u32)
+}
+
+/// Implements `call` for the listed versions of Core.
+macro_rules! impl_call {
+    ($($version:literal => $module:ident),* $(,)?) => {
+        /// Calls `method` with `args` using the client for the major Core version `core`.
+        fn call(core: u32, url: &str, auth: Auth, method: &str, args: &[Value]) -> Result<()> {
+            match core {
+                $(
+                    $version => {
+                        let client = new_client!($module, url, auth);
+                        match method {
+                            _ if !args.is_empty() => show_json(client.call(method, args)?),
+                            "getbalance" => show(client.get_balance()?, |json| json.into_model()),
+                            "getbestblockhash" =>
+                                show(client.get_best_block_hash()?, |json| json.into_model()),
+                            "getblockchaininfo" =>
+                                show(client.get_blockchain_info()?, |json| json.into_model()),
+                            "getblockcount" => show(client.get_block_count()?, |json| {
+                                Ok::<_, Infallible>(json.into_model())
+                            }),
+                            "getchaintips" =>
+                                show(client.get_chain_tips()?, |json| json.into_model()),
+                            "getdifficulty" => show(client.get_difficulty()?, |json| {
+                                Ok::<_, Infallible>(json.into_model())
+                            }),
+                            "getmempoolinfo" =>
+                                show(client.get_mempool_info()?, |json| json.into_model()),
+                            "getmininginfo" =>
+                                show(client.get_mining_info()?, |json| json.into_model()),
+                            "getnetworkinfo" =>
+                                show(client.get_network_info()?, |json| json.into_model()),
+                            "getrawmempool" =>
+                                show(client.get_raw_mempool()?, |json| json.into_model()),
+                            "gettxoutsetinfo" =>
+                                show(client.get_tx_out_set_info()?, |json| json.into_model()),
+                            "getwalletinfo" =>
+                                show(client.get_wallet_info()?, |json| json.into_model()),
+                            _ => show_json(client.call(method, args)?),
+                        }
+                    }
+                )*
+                _ => Err(format!("unsupported Core version: {}", core).into()),
+            }
+        }
+    };
+}
+
+impl_call! {
+    17 => v17,
+    18 => v18,
+    19 => v19,
+    20 => v20,
+    21 => v21,
+    22 => v22,
+    23 => v23,
+    24 => v24,
+    25 => v25,
+    26 => v26,
+    27 => v27,
+    28 => v28,
+    29 => v29,
+    30 => v30,
+}
+
+/// Prints the version specific `json` type and the model it converts into.
+fn show<J, M, E>(json: J, into_model: impl FnOnce(J) -> std::result::Result<M, E>) -> Result<()>
+where
+    J: fmt::Debug,
+    M: fmt::Debug,
+    E: fmt::Display,
+{
+    println!("{:#?}", json);
+    let model = into_model(json).map_err(|e| format!("failed to convert into model: {}", e))?;
+    println!("{:#?}", model);
+    Ok(())
+}
+
+/// Prints the JSON result of a call that is not modelled.
+fn show_json(json: Value) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(&json)?);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Option<Options>> {
+        Options::parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn parse_options() {
+        let opts =
+            parse(&["--url", "http://127.0.0.1:18443", "--core", "29", "getblock", "abcd", "0"])
+                .unwrap()
+                .unwrap();
+        assert_eq!(opts.url, "http://127.0.0.1:18443");
+        assert_eq!(opts.auth, Auth::None);
+        assert_eq!(opts.core, Some(29));
+        assert_eq!(opts.method, "getblock");
+        assert_eq!(opts.args, vec![Value::from("abcd"), Value::from(0)]);
+
+        let opts = parse(&["--user", "alice", "--password", "secret", "getbalance", "*"])
+            .unwrap()
+            .unwrap();
+        assert_eq!(opts.url, DEFAULT_URL);
+        assert_eq!(opts.auth, Auth::UserPass("alice".to_owned(), "secret".into()));
+        assert_eq!(opts.args, vec![Value::from("*")]);
+
+        assert!(parse(&["--help"]).unwrap().is_none());
+    }
+
+    #[test]
+    fn parse_options_errors() {
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["--url"]).is_err());
+        assert!(parse(&["--core", "x", "getblockcount"]).is_err());
+        assert!(parse(&["--user", "alice", "getblockcount"]).is_err());
+        assert!(parse(&["--cookie", "path", "--user", "a", "--password", "b", "uptime"]).is_err());
+        assert!(parse(&["--verbose", "getblockcount"]).is_err());
+    }
+}
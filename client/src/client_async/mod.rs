@@ -0,0 +1,199 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Async JSON-RPC clients for specific versions of Bitcoin Core.
+//!
+//! Requests are sent using the async API of `bitreq` so all methods must be awaited from within a
+//! `tokio` runtime. The clients share the error type and authentication with the blocking clients
+//! in `client_sync`.
+//!
+//! Only a subset of the methods of the blocking clients have typed async methods, the blockchain,
+//! raw transaction, network and basic wallet calls most used by applications backed by a node.
+//! They are implemented by the same per-version macros as the blocking clients, invoked with
+//! `async`, so they match the blocking methods of the same version. Any other method can be called
+//! with `Client::call` using the version specific types.
+
+pub mod v17;
+pub mod v18;
+pub mod v19;
+pub mod v20;
+pub mod v21;
+pub mod v22;
+pub mod v23;
+pub mod v24;
+pub mod v25;
+pub mod v26;
+pub mod v27;
+pub mod v28;
+pub mod v29;
+pub mod v30;
+
+pub use crate::client_sync::{Auth, Error, Result, RpcErrorCode};
+
+/// Defines an async `Client` using the `bitreq` transport of `jsonrpc`.
+#[macro_export]
+macro_rules! define_jsonrpc_bitreq_async_client {
+    ($version:literal) => {
+        use std::fmt;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        use $crate::client_async::{Auth, Error, Result};
+        use $crate::client_sync::log_response;
+        use $crate::client_sync::observer::{Outcome, RpcCall};
+
+        /// Client implements an async JSON-RPC client for the Bitcoin Core daemon or compatible
+        /// APIs.
+        pub struct Client {
+            transport: jsonrpc::bitreq_http::BitreqHttpTransport,
+            /// The id of the next request.
+            nonce: AtomicUsize,
+            /// The wallet in the URL, if any, included in the logs of each call.
+            wallet: Option<String>,
+            /// Calls taking at least this long are logged at warn level.
+            slow_call: std::time::Duration,
+            /// Called once each call completes.
+            observer: $crate::client_sync::observer::Observer,
+        }
+
+        impl fmt::Debug for Client {
+            fn fmt(&self, f: &mut fmt::Formatter) -> core::fmt::Result {
+                write!(
+                    f,
+                    "corepc_client::client_async::{}::Client({:?})", $version, self.transport
+                )
+            }
+        }
+
+        impl Client {
+            /// Creates a client to a bitcoind JSON-RPC server without authentication.
+            pub fn new(url: &str) -> Self {
+                let transport = jsonrpc::http::bitreq_http::Builder::new()
+                    .url(url)
+                    .expect("jsonrpc v0.19, this function does not error")
+                    .timeout(std::time::Duration::from_secs(60))
+                    .build();
+                Self::with_transport(url, transport)
+            }
+
+            /// Creates a client to a bitcoind JSON-RPC server with authentication.
+            ///
+            /// A cookie file is read once, here, like the blocking client.
+            pub fn new_with_auth(url: &str, auth: Auth) -> Result<Self> {
+                if matches!(auth, Auth::None) {
+                    return Err(Error::MissingUserPassword);
+                }
+                let (user, pass) = auth.get_user_pass()?;
+
+                let transport = jsonrpc::http::bitreq_http::Builder::new()
+                    .url(url)
+                    .expect("jsonrpc v0.19, this function does not error")
+                    .timeout(std::time::Duration::from_secs(60))
+                    .basic_auth(user.unwrap(), pass)
+                    .build();
+                Ok(Self::with_transport(url, transport))
+            }
+
+            fn with_transport(
+                url: &str,
+                transport: jsonrpc::bitreq_http::BitreqHttpTransport,
+            ) -> Self {
+                Self {
+                    transport,
+                    nonce: AtomicUsize::new(1),
+                    wallet: $crate::client_sync::wallet_from_url(url),
+                    slow_call: $crate::client_sync::DEFAULT_SLOW_CALL,
+                    observer: Default::default(),
+                }
+            }
+
+            /// Logs calls taking at least `threshold` at warn level, instead of debug level.
+            ///
+            /// Defaults to 10 seconds.
+            pub fn slow_call_threshold(mut self, threshold: std::time::Duration) -> Self {
+                self.slow_call = threshold;
+                self
+            }
+
+            /// Calls `observer` with the method, duration, request size and outcome of each call.
+            ///
+            /// Replaces the observer set before, if any. See `RpcObserver`.
+            pub fn with_observer(
+                mut self,
+                observer: impl $crate::client_sync::observer::RpcObserver + 'static,
+            ) -> Self {
+                self.observer = $crate::client_sync::observer::Observer::new(observer);
+                self
+            }
+
+            /// Call an RPC `method` with given `args` list.
+            pub async fn call<T: for<'a> serde::de::Deserialize<'a>>(
+                &self,
+                method: &str,
+                args: &[serde_json::Value],
+            ) -> Result<T> {
+                let resp = self.send_request(method, args).await;
+                log_response(method, &resp);
+                let resp = resp?;
+                let result = $crate::client_sync::deserialize_result(&resp);
+                #[cfg(feature = "zeroize")]
+                $crate::client_sync::zeroize::zeroize_response(resp);
+                result
+            }
+
+            /// Call an RPC `method` that returns a hex encoded consensus object, e.g. a block.
+            pub async fn call_hex<T: bitcoin::consensus::Decodable>(
+                &self,
+                method: &str,
+                args: &[serde_json::Value],
+            ) -> Result<T> {
+                let resp = self.send_request(method, args).await;
+                log_response(method, &resp);
+                let resp = resp?;
+                let result = $crate::client_sync::decode_hex_result(&resp);
+                #[cfg(feature = "zeroize")]
+                $crate::client_sync::zeroize::zeroize_response(resp);
+                result
+            }
+
+            /// Sends a request for `method`, logging its duration and outcome.
+            async fn send_request(
+                &self,
+                method: &str,
+                args: &[serde_json::Value],
+            ) -> Result<jsonrpc::Response> {
+                let raw = serde_json::value::to_raw_value(args)?;
+                if log::log_enabled!(log::Level::Debug) {
+                    log::debug!(target: "corepc", "request: {} {}", method, serde_json::Value::from(args));
+                }
+                let nonce = self.nonce.fetch_add(1, Ordering::Relaxed);
+                let req = jsonrpc::Request {
+                    method,
+                    params: Some(&*raw),
+                    id: serde_json::Value::from(nonce),
+                    jsonrpc: Some("2.0"),
+                };
+
+                let start = std::time::Instant::now();
+                let resp = self.transport.send_request_async(req);
+                #[cfg(feature = "tracing")]
+                let span = $crate::client_sync::observer::call_span(method, self.wallet.as_deref());
+                #[cfg(feature = "tracing")]
+                let resp = tracing::Instrument::instrument(resp, span.clone());
+                let resp = resp.await;
+                let call = RpcCall {
+                    method,
+                    wallet: self.wallet.as_deref(),
+                    duration: start.elapsed(),
+                    request_size: raw.get().len(),
+                    outcome: Outcome::of(&resp),
+                };
+                #[cfg(feature = "zeroize")]
+                $crate::client_sync::zeroize::zeroize_raw(raw);
+                // Entered so that the duration and outcome are recorded in the span of the call.
+                #[cfg(feature = "tracing")]
+                let _span = span.entered();
+                self.observer.finish(&call, self.slow_call);
+                Ok(resp?)
+            }
+        }
+    };
+}
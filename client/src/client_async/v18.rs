@@ -0,0 +1,51 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! An async JSON-RPC client for Bitcoin Core `v0.18`.
+
+use bitcoin::address::{Address, NetworkChecked};
+use bitcoin::{Amount, Block, BlockHash, Txid};
+
+use crate::client_sync::into_json;
+pub use crate::client_sync::v17::AddressType;
+use crate::types::v18::*;
+
+crate::define_jsonrpc_bitreq_async_client!("v18");
+
+// == Blockchain ==
+crate::impl_client_v17__get_blockchain_info!(async);
+crate::impl_client_v17__get_best_block_hash!(async);
+crate::impl_client_v17__get_block!(async);
+crate::impl_client_v17__get_block_count!(async);
+crate::impl_client_v17__get_block_hash!(async);
+crate::impl_client_v17__get_block_header!(async);
+crate::impl_client_v17__get_chain_tips!(async);
+crate::impl_client_v17__get_difficulty!(async);
+crate::impl_client_v17__get_mempool_entry!(async);
+crate::impl_client_v17__get_mempool_info!(async);
+crate::impl_client_v17__get_raw_mempool!(async);
+crate::impl_client_v17__get_tx_out!(async);
+
+// == Control ==
+crate::impl_client_v17__stop!(async);
+crate::impl_client_v17__uptime!(async);
+
+// == Network ==
+crate::impl_client_v17__get_connection_count!(async);
+crate::impl_client_v17__get_network_info!(async);
+crate::impl_client_v17__get_peer_info!(async);
+
+// == Rawtransactions ==
+crate::impl_client_v17__get_raw_transaction!(async);
+crate::impl_client_v17__send_raw_transaction!(async);
+crate::impl_client_v17__test_mempool_accept!(async);
+
+// == Util ==
+crate::impl_client_v17__estimate_smart_fee!(async);
+
+// == Wallet ==
+crate::impl_client_v17__get_balance!(async);
+crate::impl_client_v17__get_new_address!(async);
+crate::impl_client_v17__get_transaction!(async);
+crate::impl_client_v17__get_wallet_info!(async);
+crate::impl_client_v17__list_unspent!(async);
+crate::impl_client_v17__send_to_address!(async);
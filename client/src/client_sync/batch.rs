@@ -0,0 +1,208 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Sending of different RPC calls in a single JSON-RPC batch request.
+//!
+//! See `Client::batch`, `Client::call_batch` is simpler when all the calls are to the same method.
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+
+use serde::de::DeserializeOwned;
+use serde_json::value::RawValue;
+
+use crate::client_sync::observer::{enter_span, Observer, Outcome, RpcCall};
+use crate::client_sync::{
+    decode_hex_result, deserialize_result, log_response, warn_deprecated, Error, Result,
+};
+
+/// Returns how to migrate away from an RPC method if it is deprecated.
+type Deprecation = fn(&str) -> Option<&'static str>;
+
+/// A batch of RPC calls, sent to the node in a single JSON-RPC request.
+///
+/// Each call returns a [`BatchCall`] that is used to get its typed result from the
+/// [`BatchResults`] once the batch is sent.
+///
+/// ```ignore
+/// let mut batch = client.batch();
+/// let count = batch.call::<GetBlockCount>("getblockcount", &[])?;
+/// let block = batch.call_hex::<Block>("getblock", &[hash.to_string().into(), 0.into()])?;
+/// let mut results = batch.send()?;
+/// let (count, block) = (results.take(count)?, results.take(block)?);
+/// ```
+pub struct Batch<'a> {
+    client: &'a jsonrpc::Client,
+    /// The Core version of the client, for deprecation warnings.
+    version: &'static str,
+    deprecation: Deprecation,
+    /// The wallet in the URL of the client, if any, for the logs.
+    wallet: Option<&'a str>,
+    slow_call: Duration,
+    /// Called once the batch completes.
+    observer: &'a Observer,
+    /// The method and serialized arguments of each call, in order.
+    calls: Vec<(String, Box<RawValue>)>,
+}
+
+impl<'a> Batch<'a> {
+    /// Creates an empty batch sent using `client`.
+    pub(crate) fn new(
+        client: &'a jsonrpc::Client,
+        version: &'static str,
+        deprecation: Deprecation,
+        wallet: Option<&'a str>,
+        slow_call: Duration,
+        observer: &'a Observer,
+    ) -> Self {
+        Self { client, version, deprecation, wallet, slow_call, observer, calls: vec![] }
+    }
+
+    /// Queues a call to RPC `method` with `args`, returning a handle to its result.
+    pub fn call<T: DeserializeOwned>(
+        &mut self,
+        method: &str,
+        args: &[serde_json::Value],
+    ) -> Result<BatchCall<T>> {
+        self.push(method, args, deserialize_result)
+    }
+
+    /// Queues a call to RPC `method` that returns a hex encoded consensus object, e.g. a block.
+    ///
+    /// See `Client::call_hex`.
+    pub fn call_hex<T: bitcoin::consensus::Decodable>(
+        &mut self,
+        method: &str,
+        args: &[serde_json::Value],
+    ) -> Result<BatchCall<T>> {
+        self.push(method, args, decode_hex_result)
+    }
+
+    /// Returns the number of queued calls.
+    pub fn len(&self) -> usize { self.calls.len() }
+
+    /// Returns true if no calls are queued.
+    pub fn is_empty(&self) -> bool { self.calls.is_empty() }
+
+    /// Sends all the queued calls in a single request.
+    ///
+    /// Only errors sending the request are returned here, each call can still fail on its own.
+    /// An empty batch is not sent.
+    pub fn send(self) -> Result<BatchResults> {
+        if self.calls.is_empty() {
+            return Ok(BatchResults { methods: vec![], responses: vec![] });
+        }
+
+        let reqs = self
+            .calls
+            .iter()
+            .map(|(method, raw)| self.client.build_request(method, Some(&**raw)))
+            .collect::<Vec<_>>();
+        if log::log_enabled!(log::Level::Debug) {
+            log::debug!(target: "corepc", "batch request: {} calls", reqs.len());
+        }
+
+        let label = format!("batch of {}", reqs.len());
+        let _span = enter_span(&label, self.wallet);
+        let start = Instant::now();
+        let resps = self.client.send_batch(&reqs);
+        let call = RpcCall {
+            method: &label,
+            wallet: self.wallet,
+            duration: start.elapsed(),
+            request_size: self.calls.iter().map(|(_, raw)| raw.get().len()).sum(),
+            outcome: Outcome::of_batch(&resps),
+        };
+        self.observer.finish(&call, self.slow_call);
+        let responses = resps?;
+
+        let (methods, _raws): (Vec<_>, Vec<_>) = self.calls.into_iter().unzip();
+        #[cfg(feature = "zeroize")]
+        _raws.into_iter().for_each(crate::client_sync::zeroize::zeroize_raw);
+        Ok(BatchResults { methods, responses })
+    }
+
+    fn push<T>(
+        &mut self,
+        method: &str,
+        args: &[serde_json::Value],
+        decode: fn(&jsonrpc::Response) -> Result<T>,
+    ) -> Result<BatchCall<T>> {
+        warn_deprecated(self.version, method, (self.deprecation)(method));
+        let raw = serde_json::value::to_raw_value(args)?;
+        self.calls.push((method.to_owned(), raw));
+        Ok(BatchCall { index: self.calls.len() - 1, decode, result: PhantomData })
+    }
+}
+
+impl fmt::Debug for Batch<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // The arguments are not shown, they may contain secrets.
+        f.debug_struct("Batch")
+            .field("client", self.client)
+            .field("calls", &self.calls.iter().map(|(method, _)| method).collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A handle to the result of a call in a [`Batch`].
+pub struct BatchCall<T> {
+    /// The position of the call in the batch.
+    index: usize,
+    decode: fn(&jsonrpc::Response) -> Result<T>,
+    result: PhantomData<fn() -> T>,
+}
+
+impl<T> BatchCall<T> {
+    /// Returns the position of the call in the batch.
+    pub fn index(&self) -> usize { self.index }
+}
+
+impl<T> Clone for BatchCall<T> {
+    fn clone(&self) -> Self { *self }
+}
+
+impl<T> Copy for BatchCall<T> {}
+
+impl<T> fmt::Debug for BatchCall<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BatchCall").field("index", &self.index).finish()
+    }
+}
+
+/// The responses to a sent [`Batch`].
+#[derive(Debug)]
+pub struct BatchResults {
+    methods: Vec<String>,
+    responses: Vec<Option<jsonrpc::Response>>,
+}
+
+impl BatchResults {
+    /// Takes the result of `call`, the response is moved out so large results are not copied.
+    ///
+    /// Returns `Error::UnexpectedStructure` if the node did not respond to the call, if the result
+    /// was already taken, or if `call` is from another batch with more calls.
+    pub fn take<T>(&mut self, call: BatchCall<T>) -> Result<T> {
+        let method = self.methods.get(call.index).ok_or(Error::UnexpectedStructure)?;
+        let resp = self.responses[call.index].take().ok_or(Error::UnexpectedStructure);
+        log_response(method, &resp);
+        let resp = resp?;
+        let result = (call.decode)(&resp);
+        #[cfg(feature = "zeroize")]
+        crate::client_sync::zeroize::zeroize_response(resp);
+        result
+    }
+
+    /// Returns the number of calls in the batch.
+    pub fn len(&self) -> usize { self.methods.len() }
+
+    /// Returns true if the batch had no calls.
+    pub fn is_empty(&self) -> bool { self.methods.is_empty() }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for BatchResults {
+    fn drop(&mut self) {
+        self.responses.drain(..).flatten().for_each(crate::client_sync::zeroize::zeroize_response);
+    }
+}
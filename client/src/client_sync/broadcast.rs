@@ -0,0 +1,164 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Broadcasting of transactions with a mempool acceptance precheck.
+//!
+//! See `Client::broadcast_checked`.
+
+use std::{error, fmt};
+
+use crate::client_sync::Error;
+
+/// The category of reason for a transaction to be rejected from the mempool.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RejectCategory {
+    /// The fee is below the relay or mempool minimum, or too low to replace a transaction.
+    FeeTooLow,
+    /// The fee is above the maximum fee rate accepted by the node.
+    FeeTooHigh,
+    /// The transaction is not final (time locks not yet satisfied).
+    NonFinal,
+    /// The transaction conflicts with a transaction in the mempool.
+    Conflict,
+    /// The transaction is already in the mempool or the chain.
+    AlreadyKnown,
+    /// One or more inputs are missing or already spent.
+    MissingInputs,
+    /// The transaction is not standard according to the node's policy.
+    Policy,
+    /// The transaction is invalid according to consensus rules.
+    Invalid,
+    /// The reason is not recognised.
+    Other,
+}
+
+impl RejectCategory {
+    /// Classifies a `reject-reason` as returned by `testmempoolaccept`.
+    pub fn from_reason(reason: &str) -> Self {
+        use RejectCategory::*;
+
+        // Older versions of Core prefix the reason with a reject code, e.g. "64: non-final".
+        let reason = match reason.split_once(": ") {
+            Some((code, rest)) if code.chars().all(|c| c.is_ascii_digit()) => rest,
+            _ => reason,
+        };
+        let starts = |prefixes: &[&str]| prefixes.iter().any(|p| reason.starts_with(p));
+
+        if starts(&["txn-already-in-mempool", "txn-already-known", "transaction already in block"])
+        {
+            AlreadyKnown
+        } else if starts(&["min relay fee not met", "mempool min fee not met", "insufficient fee"])
+        {
+            FeeTooLow
+        } else if starts(&["absurdly-high-fee", "max-fee-exceeded"]) {
+            FeeTooHigh
+        } else if starts(&["non-final", "non-BIP68-final"]) {
+            NonFinal
+        } else if starts(&[
+            "txn-mempool-conflict",
+            "bad-txns-spends-conflicting-tx",
+            "replacement-adds-unconfirmed",
+            "too many potential replacements",
+        ]) {
+            Conflict
+        } else if starts(&["missing-inputs", "bad-txns-inputs-missingorspent"]) {
+            MissingInputs
+        } else if starts(&[
+            "version",
+            "tx-size",
+            "scriptsig-size",
+            "scriptsig-not-pushonly",
+            "scriptpubkey",
+            "bare-multisig",
+            "dust",
+            "multi-op-return",
+            "bad-txns-nonstandard-inputs",
+            "bad-witness-nonstandard",
+            "non-mandatory-script-verify-flag",
+            "too-long-mempool-chain",
+            "mempool full",
+        ]) {
+            Policy
+        } else if starts(&["bad-txns", "bad-witness", "mandatory-script-verify-flag-failed"]) {
+            Invalid
+        } else {
+            Other
+        }
+    }
+}
+
+impl fmt::Display for RejectCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use RejectCategory::*;
+
+        let s = match *self {
+            FeeTooLow => "fee too low",
+            FeeTooHigh => "fee too high",
+            NonFinal => "non-final",
+            Conflict => "conflict",
+            AlreadyKnown => "already known",
+            MissingInputs => "missing inputs",
+            Policy => "policy",
+            Invalid => "invalid",
+            Other => "other",
+        };
+        fmt::Display::fmt(s, f)
+    }
+}
+
+/// A transaction was rejected by the mempool acceptance check.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rejection {
+    /// The category of the rejection.
+    pub category: RejectCategory,
+    /// The reject reason as returned by the node.
+    pub reason: String,
+}
+
+impl Rejection {
+    /// Creates a rejection from a `reject-reason` as returned by `testmempoolaccept`.
+    pub fn from_reason(reason: impl Into<String>) -> Self {
+        let reason = reason.into();
+        Self { category: RejectCategory::from_reason(&reason), reason }
+    }
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "transaction rejected ({}): {}", self.category, self.reason)
+    }
+}
+
+/// Error returned when a checked broadcast fails.
+#[derive(Debug)]
+pub enum BroadcastError {
+    /// The client returned an error.
+    Client(Error),
+    /// The transaction was rejected by the mempool acceptance check.
+    Rejected(Rejection),
+}
+
+impl From<Error> for BroadcastError {
+    fn from(e: Error) -> Self { Self::Client(e) }
+}
+
+impl fmt::Display for BroadcastError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use BroadcastError::*;
+
+        match *self {
+            Client(ref e) => write!(f, "client error: {}", e),
+            Rejected(ref r) => fmt::Display::fmt(r, f),
+        }
+    }
+}
+
+impl error::Error for BroadcastError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        use BroadcastError::*;
+
+        match *self {
+            Client(ref e) => Some(e),
+            Rejected(_) => None,
+        }
+    }
+}
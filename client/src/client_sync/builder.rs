@@ -0,0 +1,303 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Configuration of the HTTP transport of a client.
+//!
+//! See `Client::builder`.
+
+use std::fmt;
+use std::time::Duration;
+
+use crate::client_sync::{Auth, Error, Result, RpcErrorCode};
+
+/// The URL of a mainnet node on the local host.
+const DEFAULT_URL: &str = "http://127.0.0.1:8332";
+
+/// Default timeout of a request.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Default delay before the first retry.
+const DEFAULT_BACKOFF: Duration = Duration::from_millis(250);
+
+/// The delay between retries is doubled after each retry, up to this maximum.
+const MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+/// Default time a long poll waits for the node, see [`Builder::long_poll_timeout`].
+const DEFAULT_LONG_POLL_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Creates the client from its URL, the `jsonrpc` client, the retry policy and the long poll
+/// transport.
+type NewClient<C> = fn(&str, jsonrpc::Client, Retry, LongPoll) -> C;
+
+/// A builder of a client with a configurable HTTP transport.
+///
+/// ```ignore
+/// let client = Client::builder()
+///     .url("http://127.0.0.1:18443")
+///     .auth(Auth::CookieFile(cookie))
+///     .timeout(Duration::from_secs(10))
+///     .retries(5)
+///     .keep_alive(true)
+///     .build()?;
+/// ```
+///
+/// With the `proxy` feature the node can be reached over Tor, and with the `tls` feature over
+/// HTTPS e.g., behind a TLS proxy with a self-signed certificate:
+///
+/// ```ignore
+/// let client = Client::builder()
+///     .url("http://xxxxxxxxxxxxxxxx.onion:8332")
+///     .proxy("127.0.0.1:9050")
+///     .build()?;
+///
+/// let client = Client::builder()
+///     .url("https://node.example.com")
+///     .root_certificate(std::fs::read("ca.der")?)
+///     .build()?;
+/// ```
+pub struct Builder<C> {
+    url: String,
+    auth: Auth,
+    timeout: Duration,
+    retry: Retry,
+    keep_alive: bool,
+    long_poll_timeout: Duration,
+    #[cfg(feature = "proxy")]
+    proxy: Option<String>,
+    #[cfg(feature = "tls")]
+    root_certificates: Vec<Vec<u8>>,
+    new: NewClient<C>,
+}
+
+impl<C> Builder<C> {
+    /// Creates a builder with the default configuration, `new` creates the client once built.
+    pub(crate) fn new(new: NewClient<C>) -> Self {
+        Self {
+            url: DEFAULT_URL.to_owned(),
+            auth: Auth::None,
+            timeout: DEFAULT_TIMEOUT,
+            retry: Retry::NONE,
+            keep_alive: false,
+            long_poll_timeout: DEFAULT_LONG_POLL_TIMEOUT,
+            #[cfg(feature = "proxy")]
+            proxy: None,
+            #[cfg(feature = "tls")]
+            root_certificates: Vec::new(),
+            new,
+        }
+    }
+
+    /// Sets the URL of the JSON-RPC server, defaults to `http://127.0.0.1:8332`.
+    pub fn url(mut self, url: &str) -> Self {
+        url.clone_into(&mut self.url);
+        self
+    }
+
+    /// Sets the authentication, defaults to `Auth::None`.
+    ///
+    /// A cookie file is read when the client is built.
+    pub fn auth(mut self, auth: Auth) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    /// Sets the timeout of each request, defaults to 60 seconds.
+    ///
+    /// The timeout only has second granularity.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets how many times a call failing with a transient error is retried, defaults to 0.
+    ///
+    /// Transient errors are transport errors (e.g. the connection was refused or timed out),
+    /// the work queue of the server being full, and the node still warming up. A call that timed
+    /// out may have been executed by the node, so retrying a call that is not idempotent e.g.,
+    /// `sendtoaddress` can execute it twice.
+    ///
+    /// Not used for calls made with `call_batch` or `batch`.
+    pub fn retries(mut self, retries: usize) -> Self {
+        self.retry.max_retries = retries;
+        self
+    }
+
+    /// Sets the delay before the first retry, defaults to 250 milliseconds.
+    ///
+    /// The delay is doubled after each retry, up to 10 seconds.
+    pub fn retry_backoff(mut self, backoff: Duration) -> Self {
+        self.retry.backoff = backoff;
+        self
+    }
+
+    /// Reuses the connection to the server across calls instead of connecting for each call.
+    ///
+    /// Defaults to false. Calls made from different threads at the same time are sent one after
+    /// the other over the single connection.
+    ///
+    /// Ignored for an `https://` URL and when a proxy is set, calls then connect each time.
+    pub fn keep_alive(mut self, keep_alive: bool) -> Self {
+        self.keep_alive = keep_alive;
+        self
+    }
+
+    /// Sets how long the `waitfor*` calls without a timeout wait for the node, defaults to 10
+    /// minutes.
+    ///
+    /// Long polls are sent on their own connection whose HTTP timeout is the time waited for plus
+    /// the timeout of regular calls, see `Client::call_long_poll`.
+    pub fn long_poll_timeout(mut self, timeout: Duration) -> Self {
+        self.long_poll_timeout = timeout;
+        self
+    }
+
+    /// Sends the calls through the SOCKS5 proxy at `proxy` e.g., `127.0.0.1:9050` for Tor.
+    ///
+    /// The format is `[socks5://][user[:password]@]host[:port]`, the default port is 1080. The
+    /// host of the URL is resolved by the proxy so the node can be an onion service.
+    #[cfg(feature = "proxy")]
+    pub fn proxy(mut self, proxy: &str) -> Self {
+        self.proxy = Some(proxy.to_owned());
+        self
+    }
+
+    /// Trusts the DER encoded `certificate` when connecting to an `https://` URL.
+    ///
+    /// Certificates are trusted in addition to the default root certificates, add the
+    /// certificate of the private CA (or the self-signed certificate) of the server. Convert a
+    /// PEM file with `openssl x509 -in ca.pem -outform der -out ca.der`.
+    #[cfg(feature = "tls")]
+    pub fn root_certificate(mut self, certificate: Vec<u8>) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Returns true if the calls can only be sent with `bitreq`, which the proxy and HTTPS need.
+    fn needs_bitreq(&self) -> bool {
+        #[cfg(feature = "proxy")]
+        if self.proxy.is_some() {
+            return true;
+        }
+        self.url.starts_with("https://")
+    }
+
+    /// Builds the client.
+    pub fn build(self) -> Result<C> {
+        let keep_alive = self.keep_alive && !self.needs_bitreq();
+        let (user, pass) = self.auth.get_user_pass()?;
+        let mut transport = jsonrpc::bitreq_http::Builder::new()
+            .url(&self.url)
+            .expect("jsonrpc v0.19, this function does not error");
+        if let Some(ref user) = user {
+            transport = transport.basic_auth(user.clone(), pass.clone());
+        }
+        #[cfg(feature = "proxy")]
+        if let Some(ref proxy) = self.proxy {
+            let proxy = jsonrpc::bitreq::Proxy::new_socks5(proxy)
+                .map_err(|e| Error::JsonRpc(jsonrpc::bitreq_http::Error::from(e).into()))?;
+            transport = transport.proxy(proxy);
+        }
+        #[cfg(feature = "tls")]
+        for certificate in &self.root_certificates {
+            transport = transport.root_certificate(certificate.clone());
+        }
+        let long_poll =
+            LongPoll { transport, timeout: self.long_poll_timeout, margin: self.timeout };
+
+        let inner = if keep_alive {
+            let mut builder = jsonrpc::simple_http::Builder::new()
+                .url(&self.url)
+                .map_err(|e| Error::JsonRpc(e.into()))?
+                .timeout(self.timeout);
+            if let Some(user) = user {
+                builder = builder.auth(user, pass);
+            }
+            jsonrpc::Client::with_transport(builder.build())
+        } else {
+            jsonrpc::Client::with_transport(
+                long_poll.transport.clone().timeout(self.timeout).build(),
+            )
+        };
+        Ok((self.new)(&self.url, inner, self.retry, long_poll))
+    }
+}
+
+impl<C> fmt::Debug for Builder<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut s = f.debug_struct("Builder");
+        s.field("url", &self.url)
+            .field("auth", &self.auth)
+            .field("timeout", &self.timeout)
+            .field("retry", &self.retry)
+            .field("keep_alive", &self.keep_alive)
+            .field("long_poll_timeout", &self.long_poll_timeout);
+        #[cfg(feature = "proxy")]
+        s.field("proxy", &self.proxy.as_ref().map(|_| "[redacted]"));
+        #[cfg(feature = "tls")]
+        s.field("root_certificates", &self.root_certificates.len());
+        s.finish()
+    }
+}
+
+/// How a call failing with a transient error is retried, see [`Builder::retries`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Retry {
+    /// The maximum number of retries of a call.
+    pub max_retries: usize,
+    /// The delay before the first retry.
+    pub backoff: Duration,
+}
+
+impl Retry {
+    /// Calls are not retried.
+    pub const NONE: Self = Self { max_retries: 0, backoff: DEFAULT_BACKOFF };
+
+    /// Returns the delay before retry number `retry`, counting from zero.
+    pub fn delay(&self, retry: usize) -> Duration {
+        let factor = 1u32.checked_shl(retry as u32).unwrap_or(u32::MAX);
+        self.backoff.saturating_mul(factor).min(MAX_BACKOFF)
+    }
+}
+
+/// The transport of the calls that block on the node e.g., `waitfornewblock`.
+///
+/// A new connection is made for each call, with an HTTP timeout long enough for the call.
+#[derive(Clone, Debug)]
+pub struct LongPoll {
+    /// The transport configured with the URL and the authentication of the client.
+    transport: jsonrpc::bitreq_http::Builder,
+    /// How long the calls without a timeout wait for the node.
+    timeout: Duration,
+    /// The time allowed on top of the wait for the node to respond, the regular timeout.
+    margin: Duration,
+}
+
+impl LongPoll {
+    /// Returns how long the calls without a timeout wait for the node.
+    pub fn timeout(&self) -> Duration { self.timeout }
+
+    /// Returns a client whose HTTP timeout allows the node to wait for `wait` before responding.
+    pub(crate) fn client(&self, wait: Duration) -> jsonrpc::Client {
+        let transport = self.transport.clone().timeout(wait.saturating_add(self.margin)).build();
+        jsonrpc::Client::with_transport(transport)
+    }
+}
+
+/// Returns true if the call failed with an error that may go away if the call is retried.
+pub(crate) fn is_transient(resp: &Result<jsonrpc::Response>) -> bool {
+    match *resp {
+        Ok(ref resp) =>
+            resp.error.as_ref().is_some_and(|e| e.code == RpcErrorCode::InWarmup.code()),
+        Err(Error::JsonRpc(jsonrpc::Error::Transport(ref e))) => {
+            // Of the HTTP errors, only retry if the work queue of the server is full.
+            if let Some(jsonrpc::bitreq_http::Error::Http(ref e)) = e.downcast_ref() {
+                e.status_code == 503
+            } else if let Some(jsonrpc::simple_http::Error::HttpErrorCode(code)) = e.downcast_ref()
+            {
+                *code == 503
+            } else {
+                true
+            }
+        }
+        Err(_) => false,
+    }
+}
@@ -0,0 +1,240 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Discovery of the RPC URL and authentication from a `bitcoin.conf` file, like `bitcoin-cli`.
+//!
+//! Supports network sections (e.g. `[regtest]`), network prefixed options (e.g. `regtest.rpcport`),
+//! the `chain`, `testnet`, `testnet4`, `signet` and `regtest` options, and the `rpcconnect`,
+//! `rpcport`, `rpcuser`, `rpcpassword` and `rpccookiefile` options. Like Core, the first value of an
+//! option wins and `rpcport` is only read from the top level for mainnet. `includeconf` is not
+//! supported.
+//!
+//! See `Client::from_bitcoin_conf` and `Client::from_datadir`.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use bitcoin::Network;
+
+use crate::client_sync::{Auth, Error, Result};
+
+/// The file name of the config file in the data directory.
+const CONF_FILE: &str = "bitcoin.conf";
+
+/// The RPC settings used to connect to a node.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RpcConfig {
+    /// The URL of the JSON-RPC server.
+    pub url: String,
+    /// How to authenticate to the JSON-RPC server.
+    pub auth: Auth,
+    /// The chain the node is running on.
+    pub network: Network,
+}
+
+impl RpcConfig {
+    /// Reads the RPC settings from the config file at `path`.
+    ///
+    /// The data directory is the `datadir` option, or else the directory containing the file.
+    pub fn from_bitcoin_conf<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let conf = Conf::parse(&fs::read_to_string(path)?);
+        let datadir = match conf.get(None, "datadir") {
+            Some(dir) => PathBuf::from(dir),
+            None => path.parent().map(Path::to_path_buf).unwrap_or_default(),
+        };
+        let network = conf.network()?.unwrap_or(Network::Bitcoin);
+        conf.into_rpc_config(&datadir, network)
+    }
+
+    /// Reads the RPC settings for `network` from `bitcoin.conf` in `datadir`, if there is one.
+    pub fn from_datadir<P: AsRef<Path>>(datadir: P, network: Network) -> Result<Self> {
+        let datadir = datadir.as_ref();
+        let conf = match fs::read_to_string(datadir.join(CONF_FILE)) {
+            Ok(conf) => Conf::parse(&conf),
+            Err(e) if e.kind() == ErrorKind::NotFound => Conf::default(),
+            Err(e) => return Err(e.into()),
+        };
+        conf.into_rpc_config(datadir, network)
+    }
+
+    /// Parses the RPC settings from the contents of a config file.
+    ///
+    /// `network` overrides the chain set in `conf`, relative paths are relative to the network
+    /// specific directory in `datadir`.
+    pub fn parse(conf: &str, datadir: &Path, network: Option<Network>) -> Result<Self> {
+        let conf = Conf::parse(conf);
+        let network = match network {
+            Some(network) => network,
+            None => conf.network()?.unwrap_or(Network::Bitcoin),
+        };
+        conf.into_rpc_config(datadir, network)
+    }
+}
+
+/// The options in a config file, in order.
+#[derive(Debug, Default)]
+struct Conf {
+    /// The section, the option and its value.
+    options: Vec<(Option<String>, String, String)>,
+}
+
+impl Conf {
+    /// Parses a config file, lines that are not an option or a section are ignored like in Core.
+    fn parse(conf: &str) -> Self {
+        let mut options = vec![];
+        let mut section = None;
+        for line in conf.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                section = Some(name.trim().to_owned());
+            } else if let Some((key, value)) = line.split_once('=') {
+                let (key, value) = (key.trim(), value.trim().to_owned());
+                match key.split_once('.') {
+                    Some((prefix, key)) =>
+                        options.push((Some(prefix.to_owned()), key.to_owned(), value)),
+                    None => options.push((section.clone(), key.to_owned(), value)),
+                }
+            }
+        }
+        Self { options }
+    }
+
+    /// Returns the first value of `key` in `section`, `None` is the top level.
+    fn get(&self, section: Option<&str>, key: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .find(|(s, k, _)| s.as_deref() == section && k == key)
+            .map(|(_, _, value)| value.as_str())
+    }
+
+    /// Returns the value of `key` for `network`, from its section or else the top level.
+    fn get_for(&self, network: Network, key: &str) -> Option<&str> {
+        self.get(Some(network.to_core_arg()), key).or_else(|| self.get(None, key))
+    }
+
+    /// Returns the chain set at the top level, if any.
+    fn network(&self) -> Result<Option<Network>> {
+        if let Some(chain) = self.get(None, "chain") {
+            return parse_chain(chain).map(Some);
+        }
+        let enabled = |key| self.get(None, key).is_some_and(|value| value != "0");
+        let chain = ["regtest", "signet", "testnet4"].into_iter().find(|chain| enabled(chain));
+        match chain {
+            Some(chain) => parse_chain(chain).map(Some),
+            None if enabled("testnet") => Ok(Some(Network::Testnet)),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the RPC settings for `network`.
+    fn into_rpc_config(self, datadir: &Path, network: Network) -> Result<RpcConfig> {
+        // Like Core, only mainnet uses `rpcport` from the top level.
+        let port = match network {
+            Network::Bitcoin => self.get_for(network, "rpcport"),
+            _ => self.get(Some(network.to_core_arg()), "rpcport"),
+        };
+        let host = self.get_for(network, "rpcconnect").unwrap_or("127.0.0.1");
+        let (host, host_port) = split_port(host);
+        let port = match port.or(host_port) {
+            Some(port) => port
+                .parse::<u16>()
+                .map_err(|_| Error::InvalidConf(format!("invalid rpcport: {}", port)))?,
+            None => default_rpc_port(network),
+        };
+        let url = if host.contains(':') && !host.starts_with('[') {
+            format!("http://[{}]:{}", host, port)
+        } else {
+            format!("http://{}:{}", host, port)
+        };
+
+        let password = self.get_for(network, "rpcpassword").filter(|p| !p.is_empty());
+        let auth = match password {
+            Some(password) => {
+                let user = self.get_for(network, "rpcuser").unwrap_or_default();
+                Auth::UserPass(user.to_owned(), password.into())
+            }
+            None => {
+                let cookie = self.get_for(network, "rpccookiefile").unwrap_or(".cookie");
+                Auth::CookieFile(network_dir(datadir, network).join(cookie))
+            }
+        };
+
+        Ok(RpcConfig { url, auth, network })
+    }
+}
+
+/// Splits the port off `host`, e.g. `127.0.0.1:8332` or `[::1]:8332`.
+fn split_port(host: &str) -> (&str, Option<&str>) {
+    match host.rsplit_once(':') {
+        // A bare IPv6 address has more than one colon.
+        Some((addr, port)) if !addr.contains(':') || addr.ends_with(']') => (addr, Some(port)),
+        _ => (host, None),
+    }
+}
+
+/// Parses a chain name as used by the `chain` option, e.g. `regtest`.
+fn parse_chain(chain: &str) -> Result<Network> {
+    Network::from_core_arg(chain)
+        .map_err(|_| Error::InvalidConf(format!("unknown chain: {}", chain)))
+}
+
+/// Returns the default RPC port for `network`.
+fn default_rpc_port(network: Network) -> u16 {
+    // Matching on the Core name because `Network::Testnet4` is not in all `bitcoin` v0.32 releases.
+    match network.to_core_arg() {
+        "test" => 18332,
+        "testnet4" => 48332,
+        "signet" => 38332,
+        "regtest" => 18443,
+        _ => 8332,
+    }
+}
+
+/// Returns the directory in `datadir` used for `network`.
+fn network_dir(datadir: &Path, network: Network) -> PathBuf {
+    match network.to_core_arg() {
+        "main" => datadir.to_path_buf(),
+        "test" => datadir.join("testnet3"),
+        // The other networks use their Core name.
+        chain => datadir.join(chain),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rpc_config_parse() {
+        let datadir = Path::new("/data");
+        let parse = |conf, network| RpcConfig::parse(conf, datadir, network).expect("parse");
+
+        let config = parse("", None);
+        assert_eq!(config.url, "http://127.0.0.1:8332");
+        assert_eq!(config.auth, Auth::CookieFile(datadir.join(".cookie")));
+        assert_eq!(config.network, Network::Bitcoin);
+
+        // The top level `rpcport` is only used for mainnet.
+        let conf =
+            "rpcport=1234\nrpcuser=alice\nrpcpassword=secret # comment\n[test]\nrpcuser=bob\n";
+        let config = parse(conf, None);
+        assert_eq!(config.url, "http://127.0.0.1:1234");
+        assert_eq!(config.auth, Auth::UserPass("alice".to_owned(), "secret".into()));
+        let config = parse(conf, Some(Network::Testnet));
+        assert_eq!(config.url, "http://127.0.0.1:18332");
+        assert_eq!(config.auth, Auth::UserPass("bob".to_owned(), "secret".into()));
+
+        let config = parse("chain=regtest\nregtest.rpcport=1234\nrpcconnect=10.0.0.1", None);
+        assert_eq!(config.url, "http://10.0.0.1:1234");
+        assert_eq!(config.auth, Auth::CookieFile(datadir.join("regtest/.cookie")));
+        assert_eq!(config.network, Network::Regtest);
+
+        let config = parse("signet=1\nrpcconnect=[::1]:1234\nrpccookiefile=auth", None);
+        assert_eq!(config.url, "http://[::1]:1234");
+        assert_eq!(config.auth, Auth::CookieFile(datadir.join("signet/auth")));
+
+        assert!(RpcConfig::parse("chain=moon", datadir, None).is_err());
+        assert!(RpcConfig::parse("rpcport=x", datadir, None).is_err());
+    }
+}
@@ -0,0 +1,157 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Fee bumping of unconfirmed transactions by spending one of their outputs (CPFP).
+//!
+//! See `Client::cpfp`.
+
+use std::{error, fmt};
+
+use bitcoin::{
+    absolute, transaction, Amount, FeeRate, OutPoint, Psbt, ScriptBuf, Sequence, Transaction, TxIn,
+    TxOut, Txid, Weight, Witness,
+};
+
+use crate::client_sync::broadcast::Rejection;
+use crate::client_sync::Error;
+
+/// A parent and child package created by `Client::cpfp`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CpfpPackage {
+    /// The id of the parent transaction.
+    pub parent_txid: Txid,
+    /// The signed child transaction.
+    pub child: Transaction,
+    /// Fee the child transaction pays.
+    pub child_fee: Amount,
+    /// Fee rate of the parent and child together.
+    pub package_fee_rate: FeeRate,
+    /// How the package was submitted to the node.
+    pub submission: Submission,
+}
+
+/// How a CPFP package was submitted to the node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Submission {
+    /// As a package using `submitpackage`.
+    Package,
+    /// Parent then child using `sendrawtransaction`.
+    Sequential,
+}
+
+/// Error returned when a CPFP package could not be created or submitted.
+#[derive(Debug)]
+pub enum CpfpError {
+    /// The client returned an error.
+    Client(Error),
+    /// The parent transaction has no output with this index.
+    InvalidOutput(u32),
+    /// An output spent by the parent transaction could not be found, so its fee is unknown.
+    MissingPrevout(OutPoint),
+    /// The value of the spent output is too low to pay the child fee.
+    InsufficientValue {
+        /// The value of the spent output.
+        value: Amount,
+        /// The fee the child needs to pay.
+        fee: Amount,
+    },
+    /// The wallet could not sign the child transaction.
+    Incomplete,
+    /// A transaction of the package was rejected by the node.
+    Rejected {
+        /// The id of the rejected transaction.
+        txid: Txid,
+        /// Why the transaction was rejected.
+        rejection: Rejection,
+    },
+}
+
+impl From<Error> for CpfpError {
+    fn from(e: Error) -> Self { Self::Client(e) }
+}
+
+impl fmt::Display for CpfpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use CpfpError::*;
+
+        match *self {
+            Client(ref e) => write!(f, "client error: {}", e),
+            InvalidOutput(vout) => write!(f, "parent transaction has no output {}", vout),
+            MissingPrevout(ref outpoint) =>
+                write!(f, "output spent by the parent transaction not found: {}", outpoint),
+            InsufficientValue { value, fee } =>
+                write!(f, "output value {} is too low to pay a child fee of {}", value, fee),
+            Incomplete => write!(f, "wallet could not sign the child transaction"),
+            Rejected { ref txid, ref rejection } => write!(f, "{}: {}", txid, rejection),
+        }
+    }
+}
+
+impl error::Error for CpfpError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        use CpfpError::*;
+
+        match *self {
+            Client(ref e) => Some(e),
+            InvalidOutput(_)
+            | MissingPrevout(_)
+            | InsufficientValue { .. }
+            | Incomplete
+            | Rejected { .. } => None,
+        }
+    }
+}
+
+/// Returns the fee a child of `child_vsize` vbytes needs to pay for the package to have a fee
+/// rate of `package_fee_rate`.
+///
+/// The child always pays at least the minimum relay fee for its own size.
+pub fn child_fee(
+    parent_fee: Amount,
+    parent_vsize: u64,
+    child_vsize: u64,
+    package_fee_rate: FeeRate,
+) -> Amount {
+    let package_fee =
+        package_fee_rate.fee_vb(parent_vsize + child_vsize).unwrap_or(Amount::MAX_MONEY);
+    let min_fee = FeeRate::BROADCAST_MIN.fee_vb(child_vsize).unwrap_or(Amount::MAX_MONEY);
+    package_fee.checked_sub(parent_fee).unwrap_or(Amount::ZERO).max(min_fee)
+}
+
+/// Returns the fee rate of a package paying `fee` in total for `vsize` vbytes.
+pub fn package_fee_rate(fee: Amount, vsize: u64) -> FeeRate {
+    fee / Weight::from_vb_unchecked(vsize.max(1))
+}
+
+/// Creates an unsigned child spending output `vout` of `parent` to `script_pubkey`, paying `fee`.
+///
+/// The spent output is included in the PSBT so the wallet can sign it even if the parent has
+/// not been broadcast.
+pub fn unsigned_child(
+    parent: &Transaction,
+    vout: u32,
+    script_pubkey: ScriptBuf,
+    fee: Amount,
+) -> Result<Psbt, CpfpError> {
+    let spent = parent.output.get(vout as usize).ok_or(CpfpError::InvalidOutput(vout))?;
+    let value = spent
+        .value
+        .checked_sub(fee)
+        .filter(|value| *value >= script_pubkey.minimal_non_dust())
+        .ok_or(CpfpError::InsufficientValue { value: spent.value, fee })?;
+
+    let tx = Transaction {
+        version: transaction::Version::TWO,
+        lock_time: absolute::LockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: OutPoint { txid: parent.compute_txid(), vout },
+            script_sig: ScriptBuf::new(),
+            sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+            witness: Witness::new(),
+        }],
+        output: vec![TxOut { value, script_pubkey }],
+    };
+    let mut psbt = Psbt::from_unsigned_tx(tx).expect("script sigs are empty");
+    psbt.inputs[0].witness_utxo = Some(spent.clone());
+    psbt.inputs[0].non_witness_utxo = Some(parent.clone());
+    Ok(psbt)
+}
@@ -0,0 +1,90 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! An object safe client for the RPC methods common to all supported Core versions.
+//!
+//! Each Core version has its own `Client` type, code that should work with any of them (or with a
+//! mock) can take a `&dyn DynClient` or store a `Box<dyn DynClient>` instead.
+//!
+//! See `DynClient`.
+
+use bitcoin::consensus::encode;
+use bitcoin::{block, Block, BlockHash, Transaction, Txid};
+use serde::de::DeserializeOwned;
+
+use crate::client_sync::Result;
+
+/// A client that can be used as a trait object.
+///
+/// Only `call_json` is required, the RPC methods are implemented on top of it. This makes a mock
+/// a single function and means the methods behave the same for every Core version.
+///
+/// Implemented for all the version specific `Client`s and for `jsonrpc::Client`, the latter can be
+/// created with any `jsonrpc::Transport` (e.g. one that replays recorded responses).
+///
+/// ```ignore
+/// let client: Box<dyn DynClient> = Box::new(v29::Client::new_with_auth(url, auth)?);
+/// let tip = client.get_best_block_hash()?;
+/// ```
+pub trait DynClient {
+    /// Calls an RPC `method` with `args`, returning the JSON result.
+    fn call_json(&self, method: &str, args: &[serde_json::Value]) -> Result<serde_json::Value>;
+
+    /// Returns the height of the most-work fully-validated chain using `getblockcount`.
+    fn get_block_count(&self) -> Result<u64> { from_json(self.call_json("getblockcount", &[])?) }
+
+    /// Returns the hash of the best block using `getbestblockhash`.
+    fn get_best_block_hash(&self) -> Result<BlockHash> {
+        from_json(self.call_json("getbestblockhash", &[])?)
+    }
+
+    /// Returns the hash of the block at `height` in the best chain using `getblockhash`.
+    fn get_block_hash(&self, height: u64) -> Result<BlockHash> {
+        from_json(self.call_json("getblockhash", &[height.into()])?)
+    }
+
+    /// Returns the block with `hash` using `getblock`.
+    fn get_block(&self, hash: BlockHash) -> Result<Block> {
+        from_hex(self.call_json("getblock", &[hash.to_string().into(), 0.into()])?)
+    }
+
+    /// Returns the header of the block with `hash` using `getblockheader`.
+    fn get_block_header(&self, hash: BlockHash) -> Result<block::Header> {
+        from_hex(self.call_json("getblockheader", &[hash.to_string().into(), false.into()])?)
+    }
+
+    /// Returns the transaction with `txid` using `getrawtransaction`.
+    ///
+    /// Transactions not in the mempool or the wallet are only found with `-txindex`.
+    fn get_raw_transaction(&self, txid: Txid) -> Result<Transaction> {
+        from_hex(self.call_json("getrawtransaction", &[txid.to_string().into()])?)
+    }
+
+    /// Returns the ids of the transactions in the mempool using `getrawmempool`.
+    fn get_raw_mempool(&self) -> Result<Vec<Txid>> {
+        from_json(self.call_json("getrawmempool", &[])?)
+    }
+
+    /// Submits `tx` to the node using `sendrawtransaction`, returning its id.
+    fn send_raw_transaction(&self, tx: &Transaction) -> Result<Txid> {
+        let hex = encode::serialize_hex(tx);
+        from_json(self.call_json("sendrawtransaction", &[hex.into()])?)
+    }
+}
+
+impl DynClient for jsonrpc::Client {
+    fn call_json(&self, method: &str, args: &[serde_json::Value]) -> Result<serde_json::Value> {
+        let raw = serde_json::value::to_raw_value(args)?;
+        Ok(self.call(method, Some(&raw))?)
+    }
+}
+
+/// Deserializes the JSON result of a call.
+fn from_json<T: DeserializeOwned>(json: serde_json::Value) -> Result<T> {
+    Ok(serde_json::from_value(json)?)
+}
+
+/// Decodes the hex encoded consensus object returned by a call.
+fn from_hex<T: bitcoin::consensus::Decodable>(json: serde_json::Value) -> Result<T> {
+    let hex: String = from_json(json)?;
+    crate::client_sync::decode_hex(&hex)
+}
@@ -22,6 +22,253 @@ pub enum Error {
     ServerVersion(UnexpectedServerVersionError),
     /// Missing user/password.
     MissingUserPassword,
+    /// The block headers returned by the daemon do not form a valid chain.
+    HeaderChain(HeaderChainError),
+    /// Timed out waiting for the daemon to reach the expected state.
+    Timeout,
+    /// The `bitcoin.conf` file has an invalid value.
+    InvalidConf(String),
+}
+
+/// An error code returned by bitcoind, from `src/rpc/protocol.h`.
+///
+/// The codes are stable across versions of Core, unlike the error messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum RpcErrorCode {
+    // Standard JSON-RPC 2.0 errors.
+    /// The request is not a valid JSON-RPC request.
+    InvalidRequest,
+    /// The method does not exist, or is disabled e.g., wallet methods without a wallet.
+    MethodNotFound,
+    /// The parameters are invalid.
+    InvalidParams,
+    /// Internal JSON-RPC error, also used for errors in the arguments.
+    InternalError,
+    /// The request is not valid JSON.
+    ParseError,
+
+    // General application defined errors.
+    /// An exception was thrown while handling the request.
+    MiscError,
+    /// An unexpected type was passed as a parameter.
+    TypeError,
+    /// Invalid address or key.
+    InvalidAddressOrKey,
+    /// Ran out of memory during the operation.
+    OutOfMemory,
+    /// Invalid, missing or duplicate parameter.
+    InvalidParameter,
+    /// Database error.
+    DatabaseError,
+    /// Error parsing or validating a structure in the raw format.
+    DeserializationError,
+    /// General error during transaction or block submission.
+    VerifyError,
+    /// The transaction or block was rejected by the network rules.
+    VerifyRejected,
+    /// The transaction is already in the chain, `RPC_VERIFY_ALREADY_IN_UTXO_SET` since v28.
+    VerifyAlreadyInChain,
+    /// The node is still starting up.
+    InWarmup,
+    /// The method is deprecated, see `-deprecatedrpc`.
+    MethodDeprecated,
+
+    // P2P client errors.
+    /// Bitcoin is not connected.
+    ClientNotConnected,
+    /// Still downloading the initial blocks.
+    ClientInInitialDownload,
+    /// The node is already added.
+    ClientNodeAlreadyAdded,
+    /// The node has not been added before.
+    ClientNodeNotAdded,
+    /// The node to disconnect is not connected.
+    ClientNodeNotConnected,
+    /// Invalid IP or subnet.
+    ClientInvalidIpOrSubnet,
+    /// No valid connection manager instance found.
+    ClientP2pDisabled,
+    /// No mempool instance found.
+    ClientMempoolDisabled,
+    /// The maximum number of outbound or block-relay connections is already open.
+    ClientNodeCapacityReached,
+
+    // Wallet errors.
+    /// Unspecified problem with the wallet e.g., the key is not found.
+    WalletError,
+    /// Not enough funds in the wallet or account.
+    WalletInsufficientFunds,
+    /// Invalid label name.
+    WalletInvalidLabelName,
+    /// The keypool ran out, call `keypoolrefill` first.
+    WalletKeypoolRanOut,
+    /// Enter the wallet passphrase with `walletpassphrase` first.
+    WalletUnlockNeeded,
+    /// The wallet passphrase entered was incorrect.
+    WalletPassphraseIncorrect,
+    /// The command was given in the wrong wallet encryption state e.g., encrypting an encrypted
+    /// wallet.
+    WalletWrongEncState,
+    /// Failed to encrypt the wallet.
+    WalletEncryptionFailed,
+    /// The wallet is already unlocked.
+    WalletAlreadyUnlocked,
+    /// Invalid wallet specified, the wallet does not exist or is not loaded.
+    WalletNotFound,
+    /// No wallet specified, required when more than one wallet is loaded.
+    WalletNotSpecified,
+    /// The wallet is already loaded.
+    WalletAlreadyLoaded,
+    /// There is already a wallet with the same name.
+    WalletAlreadyExists,
+}
+
+impl RpcErrorCode {
+    /// Returns the error code for the numeric `code` returned by bitcoind, if it is known.
+    pub fn from_code(code: i32) -> Option<Self> {
+        use RpcErrorCode::*;
+
+        let code = match code {
+            -32600 => InvalidRequest,
+            -32601 => MethodNotFound,
+            -32602 => InvalidParams,
+            -32603 => InternalError,
+            -32700 => ParseError,
+            -1 => MiscError,
+            -3 => TypeError,
+            -5 => InvalidAddressOrKey,
+            -7 => OutOfMemory,
+            -8 => InvalidParameter,
+            -20 => DatabaseError,
+            -22 => DeserializationError,
+            -25 => VerifyError,
+            -26 => VerifyRejected,
+            -27 => VerifyAlreadyInChain,
+            -28 => InWarmup,
+            -32 => MethodDeprecated,
+            -9 => ClientNotConnected,
+            -10 => ClientInInitialDownload,
+            -23 => ClientNodeAlreadyAdded,
+            -24 => ClientNodeNotAdded,
+            -29 => ClientNodeNotConnected,
+            -30 => ClientInvalidIpOrSubnet,
+            -31 => ClientP2pDisabled,
+            -33 => ClientMempoolDisabled,
+            -34 => ClientNodeCapacityReached,
+            -4 => WalletError,
+            -6 => WalletInsufficientFunds,
+            -11 => WalletInvalidLabelName,
+            -12 => WalletKeypoolRanOut,
+            -13 => WalletUnlockNeeded,
+            -14 => WalletPassphraseIncorrect,
+            -15 => WalletWrongEncState,
+            -16 => WalletEncryptionFailed,
+            -17 => WalletAlreadyUnlocked,
+            -18 => WalletNotFound,
+            -19 => WalletNotSpecified,
+            -35 => WalletAlreadyLoaded,
+            -36 => WalletAlreadyExists,
+            _ => return None,
+        };
+        Some(code)
+    }
+
+    /// Returns the numeric code returned by bitcoind.
+    pub fn code(self) -> i32 {
+        use RpcErrorCode::*;
+
+        match self {
+            InvalidRequest => -32600,
+            MethodNotFound => -32601,
+            InvalidParams => -32602,
+            InternalError => -32603,
+            ParseError => -32700,
+            MiscError => -1,
+            TypeError => -3,
+            InvalidAddressOrKey => -5,
+            OutOfMemory => -7,
+            InvalidParameter => -8,
+            DatabaseError => -20,
+            DeserializationError => -22,
+            VerifyError => -25,
+            VerifyRejected => -26,
+            VerifyAlreadyInChain => -27,
+            InWarmup => -28,
+            MethodDeprecated => -32,
+            ClientNotConnected => -9,
+            ClientInInitialDownload => -10,
+            ClientNodeAlreadyAdded => -23,
+            ClientNodeNotAdded => -24,
+            ClientNodeNotConnected => -29,
+            ClientInvalidIpOrSubnet => -30,
+            ClientP2pDisabled => -31,
+            ClientMempoolDisabled => -33,
+            ClientNodeCapacityReached => -34,
+            WalletError => -4,
+            WalletInsufficientFunds => -6,
+            WalletInvalidLabelName => -11,
+            WalletKeypoolRanOut => -12,
+            WalletUnlockNeeded => -13,
+            WalletPassphraseIncorrect => -14,
+            WalletWrongEncState => -15,
+            WalletEncryptionFailed => -16,
+            WalletAlreadyUnlocked => -17,
+            WalletNotFound => -18,
+            WalletNotSpecified => -19,
+            WalletAlreadyLoaded => -35,
+            WalletAlreadyExists => -36,
+        }
+    }
+}
+
+impl Error {
+    /// Returns the error returned by the daemon for the RPC call, if any.
+    pub fn rpc_error(&self) -> Option<&jsonrpc::error::RpcError> {
+        match *self {
+            Error::JsonRpc(jsonrpc::error::Error::Rpc(ref e)) => Some(e),
+            _ => None,
+        }
+    }
+
+    /// Returns the code of the error returned by the daemon for the RPC call, if it is known.
+    ///
+    /// Use [`Error::rpc_error`] to get the numeric code if it is not known.
+    pub fn rpc_error_code(&self) -> Option<RpcErrorCode> {
+        self.rpc_error().and_then(|e| RpcErrorCode::from_code(e.code))
+    }
+
+    /// Returns true if the method does not exist, or is disabled e.g., wallet methods without a wallet.
+    pub fn is_method_not_found(&self) -> bool { self.is_rpc_code(RpcErrorCode::MethodNotFound) }
+
+    /// Returns true if the address or key is invalid, or e.g., the transaction is not found.
+    pub fn is_invalid_address_or_key(&self) -> bool {
+        self.is_rpc_code(RpcErrorCode::InvalidAddressOrKey)
+    }
+
+    /// Returns true if the requested wallet does not exist or is not loaded.
+    pub fn is_wallet_not_loaded(&self) -> bool { self.is_rpc_code(RpcErrorCode::WalletNotFound) }
+
+    /// Returns true if the wallet does not have enough funds for the transaction.
+    pub fn is_insufficient_funds(&self) -> bool {
+        match (self.rpc_error_code(), self.rpc_error()) {
+            (Some(RpcErrorCode::WalletInsufficientFunds), _) => true,
+            // Funding a transaction returns a generic wallet error.
+            (Some(RpcErrorCode::WalletError), Some(e)) => e.message.contains("Insufficient funds"),
+            _ => false,
+        }
+    }
+
+    /// Returns true if the transaction is already in the chain.
+    pub fn is_tx_already_in_chain(&self) -> bool {
+        self.is_rpc_code(RpcErrorCode::VerifyAlreadyInChain)
+    }
+
+    /// Returns true if the daemon is still starting up, the call can be retried later.
+    pub fn is_warmup(&self) -> bool { self.is_rpc_code(RpcErrorCode::InWarmup) }
+
+    /// Returns true if the daemon returned an error with `code`.
+    pub fn is_rpc_code(&self, code: RpcErrorCode) -> bool { self.rpc_error_code() == Some(code) }
 }
 
 impl From<jsonrpc::error::Error> for Error {
@@ -64,6 +311,9 @@ impl fmt::Display for Error {
             Returned(ref s) => write!(f, "the daemon returned an error string: {}", s),
             ServerVersion(ref e) => write!(f, "server version: {}", e),
             MissingUserPassword => write!(f, "missing user and/or password"),
+            HeaderChain(ref e) => write!(f, "invalid header chain: {}", e),
+            Timeout => write!(f, "timed out waiting for the daemon"),
+            InvalidConf(ref s) => write!(f, "invalid bitcoin.conf: {}", s),
         }
     }
 }
@@ -80,7 +330,9 @@ impl error::Error for Error {
             BitcoinSerialization(ref e) => Some(e),
             Io(ref e) => Some(e),
             ServerVersion(ref e) => Some(e),
-            InvalidCookieFile | UnexpectedStructure | Returned(_) | MissingUserPassword => None,
+            HeaderChain(ref e) => Some(e),
+            InvalidCookieFile | UnexpectedStructure | Returned(_) | MissingUserPassword
+            | Timeout | InvalidConf(_) => None,
         }
     }
 }
@@ -110,3 +362,44 @@ impl error::Error for UnexpectedServerVersionError {}
 impl From<UnexpectedServerVersionError> for Error {
     fn from(e: UnexpectedServerVersionError) -> Self { Self::ServerVersion(e) }
 }
+
+/// Error returned when the block headers returned by bitcoind do not form a valid chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HeaderChainError {
+    /// The header hash does not match the block hash reported for its height.
+    HashMismatch {
+        /// Height of the offending header.
+        height: u32,
+    },
+    /// The header does not commit to the header before it.
+    Disconnected {
+        /// Height of the offending header.
+        height: u32,
+    },
+    /// The header hash does not meet the header's own proof of work target.
+    InvalidProofOfWork {
+        /// Height of the offending header.
+        height: u32,
+    },
+}
+
+impl fmt::Display for HeaderChainError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use HeaderChainError::*;
+
+        match *self {
+            HashMismatch { height } =>
+                write!(f, "header hash does not match block hash at height {}", height),
+            Disconnected { height } =>
+                write!(f, "header does not connect to previous header at height {}", height),
+            InvalidProofOfWork { height } =>
+                write!(f, "header has invalid proof of work at height {}", height),
+        }
+    }
+}
+
+impl error::Error for HeaderChainError {}
+
+impl From<HeaderChainError> for Error {
+    fn from(e: HeaderChainError) -> Self { Self::HeaderChain(e) }
+}
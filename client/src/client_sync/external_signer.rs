@@ -0,0 +1,300 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Signing PSBTs with an external signer command, such as [HWI].
+//!
+//! Uses the same command line protocol as Core's `-signer` option, so any command that works with
+//! Core works here. This allows the PSBT workflow to use a hardware signer
+//! without the node having to be configured with `-signer`.
+//!
+//! See `ExternalSigner::enumerate` and `PsbtPipeline::sign_external`.
+//!
+//! [HWI]: <https://github.com/bitcoin-core/HWI>
+
+use std::io::Write as _;
+use std::process::{Command, ExitStatus, Stdio};
+use std::str::FromStr as _;
+use std::{error, fmt, io};
+
+use bitcoin::address::{self, NetworkUnchecked};
+use bitcoin::bip32::Fingerprint;
+use bitcoin::hex::HexToArrayError;
+use bitcoin::psbt::{Input, PsbtParseError};
+use bitcoin::{Address, Network, Psbt};
+use serde::Deserialize;
+
+use crate::client_sync::psbt::{Funded, Processed, PsbtPipeline};
+
+/// An external signer (e.g. a hardware wallet) reached through a signer command.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExternalSigner {
+    /// The signer command and its arguments.
+    command: Vec<String>,
+    /// The master key fingerprint of the signer.
+    fingerprint: Fingerprint,
+    /// The name of the signer, as reported by the command.
+    name: String,
+    /// The chain the signer is used on.
+    network: Network,
+}
+
+impl ExternalSigner {
+    /// Lists the signers available through `command` (e.g. `hwi`) using `enumerate`.
+    ///
+    /// `command` is split on whitespace, the first part is the program to run.
+    pub fn enumerate(command: &str, network: Network) -> Result<Vec<Self>, ExternalSignerError> {
+        let command = command.split_whitespace().map(str::to_owned).collect::<Vec<_>>();
+        let chain = format!("--chain={}", network.to_core_arg());
+        let output = run(&command, &[&chain, "enumerate"], None)?;
+
+        let devices: Vec<Device> = serde_json::from_slice(&output)?;
+        devices
+            .into_iter()
+            .map(|device| {
+                if let Some(error) = device.error {
+                    return Err(ExternalSignerError::Signer(error));
+                }
+                let fingerprint = device.fingerprint.ok_or(ExternalSignerError::MissingField)?;
+                Ok(Self {
+                    command: command.clone(),
+                    fingerprint: Fingerprint::from_str(&fingerprint)?,
+                    name: device.model.unwrap_or_default(),
+                    network,
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the master key fingerprint of the signer.
+    pub fn fingerprint(&self) -> Fingerprint { self.fingerprint }
+
+    /// Returns the name of the signer.
+    pub fn name(&self) -> &str { &self.name }
+
+    /// Shows the address for `descriptor` on the signer so it can be verified by the user.
+    ///
+    /// Returns the address as displayed by the signer.
+    pub fn display_address(
+        &self,
+        descriptor: &str,
+    ) -> Result<Address<NetworkUnchecked>, ExternalSignerError> {
+        let output = self.run(&["displayaddress", "--desc", descriptor], None)?;
+        let json: Response = serde_json::from_slice(&output)?;
+        let address = json.into_result()?.address.ok_or(ExternalSignerError::MissingField)?;
+        Ok(address.parse()?)
+    }
+
+    /// Signs `psbt` with the signer using `signtx`.
+    ///
+    /// Errors if none of the inputs are derived from the signer's master key.
+    pub fn sign_psbt(&self, psbt: &Psbt) -> Result<Psbt, ExternalSignerError> {
+        let involved = psbt.inputs.iter().any(|input| {
+            input.bip32_derivation.values().any(|(fp, _)| *fp == self.fingerprint)
+                || input.tap_key_origins.values().any(|(_, (fp, _))| *fp == self.fingerprint)
+        });
+        if !involved {
+            return Err(ExternalSignerError::NotInvolved);
+        }
+
+        // Like Core, pass the PSBT on stdin because it may be too long for the command line.
+        let stdin = format!("signtx {}", psbt);
+        let output = self.run(&["--stdin"], Some(&stdin))?;
+        let json: Response = serde_json::from_slice(&output)?;
+        let psbt = json.into_result()?.psbt.ok_or(ExternalSignerError::MissingField)?;
+        Ok(psbt.parse()?)
+    }
+
+    /// Runs the signer command for this signer with `args`.
+    fn run(&self, args: &[&str], stdin: Option<&str>) -> Result<Vec<u8>, ExternalSignerError> {
+        let fingerprint = format!("--fingerprint={}", self.fingerprint);
+        let chain = format!("--chain={}", self.network.to_core_arg());
+        let mut all = vec![fingerprint.as_str(), chain.as_str()];
+        all.extend_from_slice(args);
+        run(&self.command, &all, stdin)
+    }
+}
+
+impl<'a, C> PsbtPipeline<'a, C, Funded> {
+    /// Signs the PSBT with an external signer.
+    ///
+    /// See `PsbtPipeline::<Processed>::sign_external`.
+    pub fn sign_external(
+        self,
+        signer: &ExternalSigner,
+    ) -> Result<PsbtPipeline<'a, C, Processed>, ExternalSignerError> {
+        self.sign_external_inner(signer, &self.state().psbt)
+    }
+}
+
+impl<'a, C> PsbtPipeline<'a, C, Processed> {
+    /// Signs the PSBT with an external signer, after it has been updated by the wallet.
+    ///
+    /// `complete` is set if every input has a signature, `finalize` is still needed to know if
+    /// the signatures are enough (e.g. for multisig inputs).
+    pub fn sign_external(
+        self,
+        signer: &ExternalSigner,
+    ) -> Result<PsbtPipeline<'a, C, Processed>, ExternalSignerError> {
+        self.sign_external_inner(signer, &self.state().psbt)
+    }
+}
+
+impl<'a, C, S> PsbtPipeline<'a, C, S> {
+    /// Signs `psbt`, the PSBT of the current stage, with `signer`.
+    fn sign_external_inner(
+        &self,
+        signer: &ExternalSigner,
+        psbt: &Psbt,
+    ) -> Result<PsbtPipeline<'a, C, Processed>, ExternalSignerError> {
+        let psbt = signer.sign_psbt(psbt)?;
+        let complete = is_signed(&psbt);
+        Ok(PsbtPipeline::new(self.client(), Processed { psbt, complete }))
+    }
+}
+
+/// Returns true if every input of `psbt` is signed or finalized.
+fn is_signed(psbt: &Psbt) -> bool {
+    let signed = |input: &Input| {
+        !input.partial_sigs.is_empty()
+            || input.tap_key_sig.is_some()
+            || !input.tap_script_sigs.is_empty()
+            || input.final_script_sig.is_some()
+            || input.final_script_witness.is_some()
+    };
+    psbt.inputs.iter().all(signed)
+}
+
+/// Runs `command` with `args`, optionally writing `stdin`, returning its stdout.
+fn run(
+    command: &[String],
+    args: &[&str],
+    stdin: Option<&str>,
+) -> Result<Vec<u8>, ExternalSignerError> {
+    let (program, rest) = command.split_first().ok_or(ExternalSignerError::EmptyCommand)?;
+    let mut child = Command::new(program)
+        .args(rest)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(input) = stdin {
+        child.stdin.take().expect("stdin is piped").write_all(input.as_bytes())?;
+    }
+    // Closes stdin, if still open, before waiting.
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+        return Err(ExternalSignerError::Status { status: output.status, stderr });
+    }
+    Ok(output.stdout)
+}
+
+/// A device returned by `enumerate`.
+#[derive(Deserialize)]
+struct Device {
+    fingerprint: Option<String>,
+    model: Option<String>,
+    error: Option<String>,
+}
+
+/// The result of `displayaddress` and `signtx`.
+#[derive(Deserialize)]
+struct Response {
+    address: Option<String>,
+    psbt: Option<String>,
+    error: Option<String>,
+}
+
+impl Response {
+    fn into_result(self) -> Result<Self, ExternalSignerError> {
+        match self.error {
+            Some(error) => Err(ExternalSignerError::Signer(error)),
+            None => Ok(self),
+        }
+    }
+}
+
+/// Error returned when using an external signer.
+#[derive(Debug)]
+pub enum ExternalSignerError {
+    /// The signer command is empty.
+    EmptyCommand,
+    /// Failed to run the signer command.
+    Io(io::Error),
+    /// The signer command exited unsuccessfully.
+    Status {
+        /// The exit status of the command.
+        status: ExitStatus,
+        /// What the command wrote to stderr.
+        stderr: String,
+    },
+    /// The signer command returned invalid JSON.
+    Json(serde_json::Error),
+    /// The signer returned an error.
+    Signer(String),
+    /// The signer command result is missing a field.
+    MissingField,
+    /// The signer returned an invalid fingerprint.
+    InvalidFingerprint(HexToArrayError),
+    /// The signer returned an invalid address.
+    InvalidAddress(address::ParseError),
+    /// The signer returned an invalid PSBT.
+    InvalidPsbt(PsbtParseError),
+    /// None of the PSBT inputs are derived from the signer's master key.
+    NotInvolved,
+}
+
+impl From<io::Error> for ExternalSignerError {
+    fn from(e: io::Error) -> Self { Self::Io(e) }
+}
+
+impl From<serde_json::Error> for ExternalSignerError {
+    fn from(e: serde_json::Error) -> Self { Self::Json(e) }
+}
+
+impl From<HexToArrayError> for ExternalSignerError {
+    fn from(e: HexToArrayError) -> Self { Self::InvalidFingerprint(e) }
+}
+
+impl From<address::ParseError> for ExternalSignerError {
+    fn from(e: address::ParseError) -> Self { Self::InvalidAddress(e) }
+}
+
+impl From<PsbtParseError> for ExternalSignerError {
+    fn from(e: PsbtParseError) -> Self { Self::InvalidPsbt(e) }
+}
+
+impl fmt::Display for ExternalSignerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ExternalSignerError::*;
+
+        match *self {
+            EmptyCommand => write!(f, "empty signer command"),
+            Io(ref e) => write!(f, "failed to run signer command: {}", e),
+            Status { ref status, ref stderr } =>
+                write!(f, "signer command failed ({}): {}", status, stderr.trim()),
+            Json(ref e) => write!(f, "signer command returned invalid JSON: {}", e),
+            Signer(ref e) => write!(f, "signer error: {}", e),
+            MissingField => write!(f, "signer command result is missing a field"),
+            InvalidFingerprint(ref e) => write!(f, "signer returned an invalid fingerprint: {}", e),
+            InvalidAddress(ref e) => write!(f, "signer returned an invalid address: {}", e),
+            InvalidPsbt(ref e) => write!(f, "signer returned an invalid PSBT: {}", e),
+            NotInvolved => write!(f, "no PSBT input is derived from the signer's master key"),
+        }
+    }
+}
+
+impl error::Error for ExternalSignerError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        use ExternalSignerError::*;
+
+        match *self {
+            Io(ref e) => Some(e),
+            Json(ref e) => Some(e),
+            InvalidFingerprint(ref e) => Some(e),
+            InvalidAddress(ref e) => Some(e),
+            InvalidPsbt(ref e) => Some(e),
+            EmptyCommand | Status { .. } | Signer(_) | MissingField | NotInvolved => None,
+        }
+    }
+}
@@ -0,0 +1,82 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Long polling of `getblocktemplate`.
+//!
+//! See `Client::block_templates`.
+
+use std::thread;
+use std::time::Duration;
+
+use crate::client_sync::{Error, Result};
+
+/// Number of consecutive transport errors retried before an error is yielded.
+const MAX_TRANSPORT_RETRIES: usize = 3;
+
+/// Time to wait before re-issuing a request that failed with a transport error.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Fetches a template given the long poll ID of the previous one, see [`BlockTemplates::new`].
+type Fetch<'a, T> = Box<dyn FnMut(Option<&str>) -> Result<(T, Option<String>)> + 'a>;
+
+/// An iterator over block templates, yielding a new template each time the node has one.
+///
+/// The first call to `next` returns the current template immediately, subsequent calls block
+/// until the node returns a template that differs from the previous one (new tip or significant
+/// mempool changes).
+///
+/// A long poll may run longer than the client timeout, transport errors (timeouts, dropped
+/// connections) are therefore retried by re-issuing the same long poll. If a request fails with
+/// a transport error more than a few times in a row the error is yielded instead. The iterator
+/// is never exhausted and can continue to be used after yielding an error.
+pub struct BlockTemplates<'a, T> {
+    /// Fetches a template, long polling with the given ID if there is one.
+    fetch: Fetch<'a, T>,
+    /// The long poll ID of the last template returned.
+    long_poll_id: Option<String>,
+}
+
+impl<'a, T> BlockTemplates<'a, T> {
+    /// Creates a new iterator.
+    ///
+    /// `fetch` is given the long poll ID from the previous template and returns the next
+    /// template along with its long poll ID.
+    pub fn new<F>(fetch: F) -> Self
+    where
+        F: FnMut(Option<&str>) -> Result<(T, Option<String>)> + 'a,
+    {
+        Self { fetch: Box::new(fetch), long_poll_id: None }
+    }
+
+    /// Returns the long poll ID that will be used for the next request, if any.
+    pub fn long_poll_id(&self) -> Option<&str> { self.long_poll_id.as_deref() }
+}
+
+impl<T> Iterator for BlockTemplates<'_, T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut retries = 0;
+        loop {
+            match (self.fetch)(self.long_poll_id.as_deref()) {
+                Ok((template, id)) => {
+                    self.long_poll_id = id;
+                    return Some(Ok(template));
+                }
+                Err(Error::JsonRpc(jsonrpc::Error::Transport(ref e)))
+                    if retries < MAX_TRANSPORT_RETRIES =>
+                {
+                    log::debug!(target: "corepc", "getblocktemplate long poll failed, retrying: {}", e);
+                    retries += 1;
+                    thread::sleep(RETRY_DELAY);
+                }
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+impl<T> core::fmt::Debug for BlockTemplates<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("BlockTemplates").field("long_poll_id", &self.long_poll_id).finish()
+    }
+}
@@ -2,7 +2,22 @@
 
 //! JSON-RPC clients for testing against specific versions of Bitcoin Core.
 
+pub mod batch;
+pub mod broadcast;
+pub mod builder;
+pub mod conf;
+pub mod cpfp;
+pub mod dyn_client;
 mod error;
+#[cfg(feature = "external-signer")]
+pub mod external_signer;
+pub mod long_poll;
+pub mod notifier;
+pub mod observer;
+pub mod pages;
+pub mod preview;
+pub mod psbt;
+pub mod rbf;
 pub mod v17;
 pub mod v18;
 pub mod v19;
@@ -17,12 +32,19 @@ pub mod v27;
 pub mod v28;
 pub mod v29;
 pub mod v30;
+pub mod wallet_manager;
+pub mod wallet_sync;
+#[cfg(feature = "zeroize")]
+pub mod zeroize;
 
+use std::cell::RefCell;
+use std::fmt;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
 use std::path::PathBuf;
+use std::time::Duration;
 
-pub use crate::client_sync::error::Error;
+pub use crate::client_sync::error::{Error, HeaderChainError, RpcErrorCode};
 
 /// Crate-specific Result type.
 ///
@@ -30,43 +52,109 @@ pub use crate::client_sync::error::Error;
 pub type Result<T> = std::result::Result<T, Error>;
 
 /// The different authentication methods for the client.
-#[derive(Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+///
+/// The `Debug` implementation does not show the password.
+#[derive(Clone, Hash, Eq, PartialEq, Ord, PartialOrd)]
 pub enum Auth {
     None,
-    UserPass(String, String),
+    UserPass(String, Password),
     CookieFile(PathBuf),
 }
 
+impl fmt::Debug for Auth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Auth::None => f.write_str("None"),
+            Auth::UserPass(ref user, _) =>
+                f.debug_tuple("UserPass").field(user).field(&"[redacted]").finish(),
+            Auth::CookieFile(ref path) => f.debug_tuple("CookieFile").field(path).finish(),
+        }
+    }
+}
+
 impl Auth {
     /// Convert into the arguments that jsonrpc::Client needs.
     pub fn get_user_pass(self) -> Result<(Option<String>, Option<String>)> {
         match self {
             Auth::None => Ok((None, None)),
-            Auth::UserPass(u, p) => Ok((Some(u), Some(p))),
-            Auth::CookieFile(path) => {
+            Auth::UserPass(u, mut p) => Ok((Some(u), Some(std::mem::take(&mut p.0)))),
+            Auth::CookieFile(ref path) => {
                 let line = BufReader::new(File::open(path)?)
                     .lines()
                     .next()
                     .ok_or(Error::InvalidCookieFile)??;
-                let colon = line.find(':').ok_or(Error::InvalidCookieFile)?;
-                Ok((Some(line[..colon].into()), Some(line[colon + 1..].into())))
+                let user_pass = match line.find(':') {
+                    Some(colon) => Ok((Some(line[..colon].into()), Some(line[colon + 1..].into()))),
+                    None => Err(Error::InvalidCookieFile),
+                };
+                #[cfg(feature = "zeroize")]
+                {
+                    let mut line = line;
+                    zeroize::Zeroize::zeroize(&mut line);
+                }
+                user_pass
             }
         }
     }
 }
 
+/// The password of [`Auth::UserPass`].
+///
+/// With the `zeroize` feature it is overwritten with zeros when dropped. The `Debug`
+/// implementation does not show it.
+#[derive(Clone, Default, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Password(String);
+
+impl Password {
+    /// Constructs a password.
+    pub fn new(password: impl Into<String>) -> Self { Self(password.into()) }
+
+    /// Returns the password.
+    pub fn as_str(&self) -> &str { &self.0 }
+}
+
+impl From<String> for Password {
+    fn from(password: String) -> Self { Self(password) }
+}
+
+impl From<&str> for Password {
+    fn from(password: &str) -> Self { Self(password.to_owned()) }
+}
+
+impl fmt::Debug for Password {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str("Password([redacted])") }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Password {
+    fn drop(&mut self) { zeroize::Zeroize::zeroize(&mut self.0) }
+}
+
 /// Defines a `jsonrpc::Client` using `bitreq`.
 #[macro_export]
 macro_rules! define_jsonrpc_bitreq_client {
     ($version:literal) => {
         use std::fmt;
 
-        use $crate::client_sync::{log_response, Auth, Result};
+        use $crate::client_sync::observer::{enter_span, Outcome, RpcCall};
+        use $crate::client_sync::{log_response, warn_deprecated, Auth, Result};
         use $crate::client_sync::error::Error;
 
         /// Client implements a JSON-RPC client for the Bitcoin Core daemon or compatible APIs.
         pub struct Client {
             inner: jsonrpc::client::Client,
+            /// The wallet to load if a call fails because it is not loaded.
+            auto_load_wallet: Option<String>,
+            /// The wallet in the URL, if any, included in the logs of each call.
+            wallet: Option<String>,
+            /// Calls taking at least this long are logged at warn level.
+            slow_call: std::time::Duration,
+            /// How calls failing with a transient error are retried.
+            retry: $crate::client_sync::builder::Retry,
+            /// The transport of the calls that block on the node.
+            long_poll: $crate::client_sync::builder::LongPoll,
+            /// Called once each call completes.
+            observer: $crate::client_sync::observer::Observer,
         }
 
         impl fmt::Debug for Client {
@@ -78,17 +166,23 @@ macro_rules! define_jsonrpc_bitreq_client {
             }
         }
 
+        impl $crate::client_sync::dyn_client::DynClient for Client {
+            fn call_json(
+                &self,
+                method: &str,
+                args: &[serde_json::Value],
+            ) -> Result<serde_json::Value> {
+                self.call(method, args)
+            }
+        }
+
         impl Client {
             /// Creates a client to a bitcoind JSON-RPC server without authentication.
             pub fn new(url: &str) -> Self {
-                let transport = jsonrpc::http::bitreq_http::Builder::new()
+                Self::builder()
                     .url(url)
-                    .expect("jsonrpc v0.19, this function does not error")
-                    .timeout(std::time::Duration::from_secs(60))
-                    .build();
-                let inner = jsonrpc::client::Client::with_transport(transport);
-
-                Self { inner }
+                    .build()
+                    .expect("without auth or keep alive, building does not error")
             }
 
             /// Creates a client to a bitcoind JSON-RPC server with authentication.
@@ -96,17 +190,106 @@ macro_rules! define_jsonrpc_bitreq_client {
                 if matches!(auth, Auth::None) {
                     return Err(Error::MissingUserPassword);
                 }
-                let (user, pass) = auth.get_user_pass()?;
+                Self::builder().url(url).auth(auth).build()
+            }
 
-                let transport = jsonrpc::http::bitreq_http::Builder::new()
-                    .url(url)
-                    .expect("jsonrpc v0.19, this function does not error")
-                    .timeout(std::time::Duration::from_secs(60))
-                    .basic_auth(user.unwrap(), pass)
-                    .build();
-                let inner = jsonrpc::client::Client::with_transport(transport);
+            /// Returns a builder to configure the timeout, retries and connection reuse of the
+            /// client.
+            pub fn builder() -> $crate::client_sync::builder::Builder<Self> {
+                $crate::client_sync::builder::Builder::new(|url, inner, retry, long_poll| Self {
+                    inner,
+                    auto_load_wallet: None,
+                    wallet: $crate::client_sync::wallet_from_url(url),
+                    slow_call: $crate::client_sync::DEFAULT_SLOW_CALL,
+                    retry,
+                    long_poll,
+                    observer: Default::default(),
+                })
+            }
+
+            /// Creates a client using the RPC settings in the config file at `path`, like
+            /// `bitcoin-cli -conf=<path>`.
+            ///
+            /// See `RpcConfig::from_bitcoin_conf`.
+            pub fn from_bitcoin_conf<P: AsRef<std::path::Path>>(path: P) -> Result<Self> {
+                let conf = $crate::client_sync::conf::RpcConfig::from_bitcoin_conf(path)?;
+                Self::new_with_auth(&conf.url, conf.auth)
+            }
 
-                Ok(Self { inner })
+            /// Creates a client for `network` using the RPC settings in `datadir`, like
+            /// `bitcoin-cli -datadir=<datadir>`.
+            ///
+            /// See `RpcConfig::from_datadir`.
+            pub fn from_datadir<P: AsRef<std::path::Path>>(
+                datadir: P,
+                network: bitcoin::Network,
+            ) -> Result<Self> {
+                let conf = $crate::client_sync::conf::RpcConfig::from_datadir(datadir, network)?;
+                Self::new_with_auth(&conf.url, conf.auth)
+            }
+
+            /// Creates a `WalletManager` for the node at `url`, wallet clients use the same `auth`.
+            pub fn wallet_manager(
+                url: &str,
+                auth: Auth,
+            ) -> Result<$crate::client_sync::wallet_manager::WalletManager<Self>> {
+                $crate::client_sync::wallet_manager::WalletManager::new(url, move |url| {
+                    match auth {
+                        Auth::None => Ok(Self::new(url)),
+                        ref auth => Self::new_with_auth(url, auth.clone()),
+                    }
+                })
+            }
+
+            /// Loads `wallet` and retries the call, once, if a call fails because the wallet is
+            /// not loaded e.g., after the node was restarted.
+            ///
+            /// Not used for calls made with `call_batch`.
+            pub fn auto_load_wallet(mut self, wallet: &str) -> Self {
+                self.auto_load_wallet = Some(wallet.to_owned());
+                self
+            }
+
+            /// Logs calls taking at least `threshold` at warn level, instead of debug level.
+            ///
+            /// Defaults to 10 seconds.
+            pub fn slow_call_threshold(mut self, threshold: std::time::Duration) -> Self {
+                self.slow_call = threshold;
+                self
+            }
+
+            /// Calls `observer` with the method, duration, request size and outcome of each call.
+            ///
+            /// Replaces the observer set before, if any. See `RpcObserver`.
+            pub fn with_observer(
+                mut self,
+                observer: impl $crate::client_sync::observer::RpcObserver + 'static,
+            ) -> Self {
+                self.observer = $crate::client_sync::observer::Observer::new(observer);
+                self
+            }
+
+            /// Blocks until the RPC server is up or `timeout` elapses, like `bitcoin-cli -rpcwait`.
+            ///
+            /// Retries while the server can not be reached or is still warming up (e.g. loading
+            /// the block index), returns the last error if `timeout` elapses first.
+            pub fn wait_for_rpc(&self, timeout: std::time::Duration) -> Result<()> {
+                let start = std::time::Instant::now();
+                loop {
+                    let err = match self.call::<serde_json::Value>("uptime", &[]) {
+                        Ok(_) => return Ok(()),
+                        Err(e) if e.is_warmup() => e,
+                        Err(e @ Error::JsonRpc(jsonrpc::Error::Transport(_))) => e,
+                        Err(e) => return Err(e),
+                    };
+                    let elapsed = start.elapsed();
+                    if elapsed >= timeout {
+                        return Err(err);
+                    }
+                    log::debug!(target: "corepc", "waiting for RPC server: {}", err);
+                    let retry = std::time::Duration::from_millis(200);
+                    std::thread::sleep((timeout - elapsed).min(retry));
+                }
             }
 
             /// Call an RPC `method` with given `args` list.
@@ -115,15 +298,294 @@ macro_rules! define_jsonrpc_bitreq_client {
                 method: &str,
                 args: &[serde_json::Value],
             ) -> Result<T> {
+                warn_deprecated($version, method, Self::deprecation(method));
+                let raw = serde_json::value::to_raw_value(args)?;
+                if log::log_enabled!(log::Level::Debug) {
+                    log::debug!(target: "corepc", "request: {} {}", method, serde_json::Value::from(args));
+                }
+
+                let resp = self.send_request(method, &raw);
+                #[cfg(feature = "zeroize")]
+                $crate::client_sync::zeroize::zeroize_raw(raw);
+                log_response(method, &resp);
+                let resp = resp?;
+                let result = $crate::client_sync::deserialize_result(&resp);
+                #[cfg(feature = "zeroize")]
+                $crate::client_sync::zeroize::zeroize_response(resp);
+                result
+            }
+
+            /// Call an RPC `method` with `args` holding secrets e.g., a wallet passphrase.
+            ///
+            /// With the `zeroize` feature the string arguments are overwritten with zeros once
+            /// the call returns, as well as the serialized request.
+            pub fn call_with_secrets<T: for<'a> serde::de::Deserialize<'a>>(
+                &self,
+                method: &str,
+                args: &mut [serde_json::Value],
+            ) -> Result<T> {
+                let res = self.call(method, args);
+                #[cfg(feature = "zeroize")]
+                $crate::client_sync::zeroize::zeroize_args(args);
+                res
+            }
+
+            /// Call an RPC `method` that blocks on the node for up to `wait` e.g., `waitfornewblock`.
+            ///
+            /// The call is sent on a new connection whose HTTP timeout is `wait` plus the timeout
+            /// of regular calls, so that it does not time out while the node waits. The call is
+            /// not retried, and only logged as slow if it takes longer than `wait` plus the slow
+            /// call threshold.
+            pub fn call_long_poll<T: for<'a> serde::de::Deserialize<'a>>(
+                &self,
+                method: &str,
+                args: &[serde_json::Value],
+                wait: std::time::Duration,
+            ) -> Result<T> {
+                warn_deprecated($version, method, Self::deprecation(method));
+                let raw = serde_json::value::to_raw_value(args)?;
+                if log::log_enabled!(log::Level::Debug) {
+                    log::debug!(target: "corepc", "request: {} {} wait={:?}", method, serde_json::Value::from(args), wait);
+                }
+
+                let client = self.long_poll.client(wait);
+                let _span = enter_span(method, self.wallet.as_deref());
+                let start = std::time::Instant::now();
+                let resp = client.send_request(client.build_request(method, Some(&*raw)));
+                let resp = resp.map_err(Error::from);
+                let call = RpcCall {
+                    method,
+                    wallet: self.wallet.as_deref(),
+                    duration: start.elapsed(),
+                    request_size: raw.get().len(),
+                    outcome: Outcome::of(&resp),
+                };
+                self.observer.finish(&call, wait.saturating_add(self.slow_call));
+                #[cfg(feature = "zeroize")]
+                $crate::client_sync::zeroize::zeroize_raw(raw);
+                log_response(method, &resp);
+                let resp = resp?;
+                let result = $crate::client_sync::deserialize_result(&resp);
+                #[cfg(feature = "zeroize")]
+                $crate::client_sync::zeroize::zeroize_response(resp);
+                result
+            }
+
+            /// Call an RPC `method` that returns a hex encoded consensus object, e.g. a block.
+            ///
+            /// Decodes the hex straight from the response instead of deserializing it into a
+            /// `String` first, the bytes are decoded into a buffer reused across calls.
+            pub fn call_hex<T: bitcoin::consensus::Decodable>(
+                &self,
+                method: &str,
+                args: &[serde_json::Value],
+            ) -> Result<T> {
+                warn_deprecated($version, method, Self::deprecation(method));
                 let raw = serde_json::value::to_raw_value(args)?;
-                let req = self.inner.build_request(&method, Some(&*raw));
                 if log::log_enabled!(log::Level::Debug) {
                     log::debug!(target: "corepc", "request: {} {}", method, serde_json::Value::from(args));
                 }
 
-                let resp = self.inner.send_request(req).map_err(Error::from);
+                let resp = self.send_request(method, &raw);
+                #[cfg(feature = "zeroize")]
+                $crate::client_sync::zeroize::zeroize_raw(raw);
                 log_response(method, &resp);
-                Ok(resp?.result()?)
+                let resp = resp?;
+                let result = $crate::client_sync::decode_hex_result(&resp);
+                #[cfg(feature = "zeroize")]
+                $crate::client_sync::zeroize::zeroize_response(resp);
+                result
+            }
+
+            /// Sends a request for `method`, logging its duration and outcome.
+            ///
+            /// Retries the request if it fails with a transient error, see `Builder::retries`.
+            fn send_request(
+                &self,
+                method: &str,
+                args: &serde_json::value::RawValue,
+            ) -> Result<jsonrpc::Response> {
+                let _span = enter_span(method, self.wallet.as_deref());
+                let mut retry = 0;
+                loop {
+                    let start = std::time::Instant::now();
+                    let resp = self.send_request_inner(method, args);
+                    let call = RpcCall {
+                        method,
+                        wallet: self.wallet.as_deref(),
+                        duration: start.elapsed(),
+                        request_size: args.get().len(),
+                        outcome: Outcome::of(&resp),
+                    };
+                    self.observer.finish(&call, self.slow_call);
+
+                    if retry >= self.retry.max_retries
+                        || !$crate::client_sync::builder::is_transient(&resp)
+                    {
+                        return resp;
+                    }
+                    let delay = self.retry.delay(retry);
+                    log::debug!(target: "corepc", "retrying {} in {:?}", method, delay);
+                    std::thread::sleep(delay);
+                    retry += 1;
+                }
+            }
+
+            /// Sends a request for `method`, see `auto_load_wallet` for when it is sent twice.
+            fn send_request_inner(
+                &self,
+                method: &str,
+                args: &serde_json::value::RawValue,
+            ) -> Result<jsonrpc::Response> {
+                use $crate::client_sync::RpcErrorCode;
+
+                let req = self.inner.build_request(method, Some(args));
+                let resp = self.inner.send_request(req)?;
+
+                let not_loaded = match resp.error {
+                    Some(ref e) =>
+                        e.code == RpcErrorCode::WalletNotFound.code() && method != "loadwallet",
+                    None => false,
+                };
+                let wallet = match self.auto_load_wallet {
+                    Some(ref wallet) if not_loaded => wallet,
+                    _ => return Ok(resp),
+                };
+                log::debug!(target: "corepc", "wallet {} not loaded, retrying {}", wallet, method);
+
+                let raw = serde_json::value::to_raw_value(&[wallet])?;
+                let req = self.inner.build_request("loadwallet", Some(&*raw));
+                let load = self.inner.send_request(req)?;
+                match load.error {
+                    // Someone else loaded the wallet in the meantime.
+                    Some(ref e) if e.code == RpcErrorCode::WalletAlreadyLoaded.code() => {}
+                    // Return the original error, the wallet may not exist.
+                    Some(_) => return Ok(resp),
+                    None => {}
+                }
+
+                let req = self.inner.build_request(method, Some(args));
+                Ok(self.inner.send_request(req)?)
+            }
+
+            /// Starts a batch of calls, to different methods, sent in a single JSON-RPC request.
+            ///
+            /// See `Batch`, `call_batch` is simpler if all the calls are to the same method.
+            pub fn batch(&self) -> $crate::client_sync::batch::Batch<'_> {
+                $crate::client_sync::batch::Batch::new(
+                    &self.inner,
+                    $version,
+                    Self::deprecation,
+                    self.wallet.as_deref(),
+                    self.slow_call,
+                    &self.observer,
+                )
+            }
+
+            /// Call an RPC `method` once for each entry in `args`, using a single JSON-RPC batch.
+            ///
+            /// Results are returned in the same order as `args`.
+            pub fn call_batch<T: for<'a> serde::de::Deserialize<'a>>(
+                &self,
+                method: &str,
+                args: &[Vec<serde_json::Value>],
+            ) -> Result<Vec<T>> {
+                if args.is_empty() {
+                    return Ok(vec![]);
+                }
+                warn_deprecated($version, method, Self::deprecation(method));
+
+                let raws = args
+                    .iter()
+                    .map(|a| serde_json::value::to_raw_value(a))
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                let reqs = raws
+                    .iter()
+                    .map(|raw| self.inner.build_request(&method, Some(&**raw)))
+                    .collect::<Vec<_>>();
+                if log::log_enabled!(log::Level::Debug) {
+                    log::debug!(target: "corepc", "batch request: {} x{}", method, reqs.len());
+                }
+
+                let _span = enter_span(method, self.wallet.as_deref());
+                let start = std::time::Instant::now();
+                let resps = self.inner.send_batch(&reqs);
+                let call = RpcCall {
+                    method,
+                    wallet: self.wallet.as_deref(),
+                    duration: start.elapsed(),
+                    request_size: raws.iter().map(|raw| raw.get().len()).sum(),
+                    outcome: Outcome::of_batch(&resps),
+                };
+                self.observer.finish(&call, self.slow_call);
+                let resps = resps?;
+                #[cfg(feature = "zeroize")]
+                raws.into_iter().for_each($crate::client_sync::zeroize::zeroize_raw);
+                resps
+                    .into_iter()
+                    .map(|resp| {
+                        let resp = resp.ok_or(Error::UnexpectedStructure);
+                        log_response(method, &resp);
+                        let resp = resp?;
+                        let result = $crate::client_sync::deserialize_result(&resp);
+                        #[cfg(feature = "zeroize")]
+                        $crate::client_sync::zeroize::zeroize_response(resp);
+                        result
+                    })
+                    .collect()
+            }
+
+            /// Like `call_batch` but for a `method` that returns a hex encoded consensus object.
+            ///
+            /// See `call_hex`.
+            pub fn call_batch_hex<T: bitcoin::consensus::Decodable>(
+                &self,
+                method: &str,
+                args: &[Vec<serde_json::Value>],
+            ) -> Result<Vec<T>> {
+                if args.is_empty() {
+                    return Ok(vec![]);
+                }
+                warn_deprecated($version, method, Self::deprecation(method));
+
+                let raws = args
+                    .iter()
+                    .map(|a| serde_json::value::to_raw_value(a))
+                    .collect::<std::result::Result<Vec<_>, _>>()?;
+                let reqs = raws
+                    .iter()
+                    .map(|raw| self.inner.build_request(&method, Some(&**raw)))
+                    .collect::<Vec<_>>();
+                if log::log_enabled!(log::Level::Debug) {
+                    log::debug!(target: "corepc", "batch request: {} x{}", method, reqs.len());
+                }
+
+                let _span = enter_span(method, self.wallet.as_deref());
+                let start = std::time::Instant::now();
+                let resps = self.inner.send_batch(&reqs);
+                let call = RpcCall {
+                    method,
+                    wallet: self.wallet.as_deref(),
+                    duration: start.elapsed(),
+                    request_size: raws.iter().map(|raw| raw.get().len()).sum(),
+                    outcome: Outcome::of_batch(&resps),
+                };
+                self.observer.finish(&call, self.slow_call);
+                let resps = resps?;
+                #[cfg(feature = "zeroize")]
+                raws.into_iter().for_each($crate::client_sync::zeroize::zeroize_raw);
+                resps
+                    .into_iter()
+                    .map(|resp| {
+                        let resp = resp.ok_or(Error::UnexpectedStructure);
+                        log_response(method, &resp);
+                        let resp = resp?;
+                        let result = $crate::client_sync::decode_hex_result(&resp);
+                        #[cfg(feature = "zeroize")]
+                        $crate::client_sync::zeroize::zeroize_response(resp);
+                        result
+                    })
+                    .collect()
             }
         }
     }
@@ -156,16 +618,320 @@ macro_rules! impl_client_check_expected_server_version {
     };
 }
 
+/// Implements `Client::deprecation` for the methods deprecated in a Core version.
+///
+/// Calling a deprecated method logs a warning, once per method, so users can migrate before the
+/// RPC is removed.
+///
+/// # Parameters
+///
+/// - `$method => $migration`: The RPC method name and how to migrate away from it.
+#[macro_export]
+macro_rules! impl_client_deprecated_methods {
+    ($($method:literal => $migration:expr),* $(,)?) => {
+        impl Client {
+            /// Returns how to migrate away from RPC `method` if it is deprecated in this version.
+            pub fn deprecation(method: &str) -> Option<&'static str> {
+                match method {
+                    $($method => Some($migration),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+/// Awaits `$e` if the method is async, used by the method macros shared with `client_async`.
+///
+/// The method macros take an optional `async` argument, e.g. `impl_client_v17__get_block!(async)`
+/// implements `Client::get_block` as an `async fn` for the clients in `client_async`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! maybe_await {
+    (async $e:expr) => {
+        $e.await
+    };
+    ($e:expr) => {
+        $e
+    };
+}
+
+/// How to migrate away from the RPC methods that only work with legacy wallets.
+pub(crate) const LEGACY_WALLET: &str =
+    "legacy wallets are removed in Core v30, use a descriptor wallet (see `migratewallet`)";
+
+/// Logs a warning the first time a deprecated `method` is called for a `version`.
+fn warn_deprecated(version: &str, method: &str, migration: Option<&str>) {
+    use std::collections::BTreeSet;
+    use std::sync::Mutex;
+
+    static WARNED: Mutex<BTreeSet<(String, String)>> = Mutex::new(BTreeSet::new());
+
+    let migration = match migration {
+        Some(migration) => migration,
+        None => return,
+    };
+    let mut warned = WARNED.lock().unwrap_or_else(|e| e.into_inner());
+    if warned.insert((version.to_owned(), method.to_owned())) {
+        log::warn!(target: "corepc", "{} is deprecated in Core {}: {}", method, version, migration);
+    }
+}
+
 /// Shorthand for converting a variable into a `serde_json::Value`.
-fn into_json<T>(val: T) -> Result<serde_json::Value>
+pub(crate) fn into_json<T>(val: T) -> Result<serde_json::Value>
 where
     T: serde::ser::Serialize,
 {
     Ok(serde_json::to_value(val)?)
 }
 
+/// Converts `amount` into the JSON value in BTC used for amount arguments.
+///
+/// Core accepts amounts as a string or a number, a decimal string is used because an `f64` can
+/// not represent most BTC amounts exactly.
+pub(crate) fn amount_json(amount: bitcoin::Amount) -> serde_json::Value {
+    amount.to_string_in(bitcoin::Denomination::Bitcoin).into()
+}
+
+/// Converts `fee_rate` to BTC/kvB, as used by the `feeRate` option of Core v0.17 to v20.
+///
+/// Returns a decimal string, which Core parses exactly.
+pub(crate) fn fee_rate_btc_per_kvb(fee_rate: bitcoin::FeeRate) -> String {
+    bitcoin::Amount::from_sat(fee_rate.to_sat_per_kwu() * 4)
+        .to_string_in(bitcoin::Denomination::Bitcoin)
+}
+
+/// Converts `fee_rate` to sat/vB, as used by the `fee_rate` option from Core v21 onwards.
+///
+/// Returns a decimal string, which Core parses exactly.
+pub(crate) fn fee_rate_sat_per_vb(fee_rate: bitcoin::FeeRate) -> String {
+    let sat_per_kvb = fee_rate.to_sat_per_kwu() * 4;
+    format!("{}.{:03}", sat_per_kvb / 1000, sat_per_kvb % 1000)
+}
+
+/// Serializes the fee rate in BTC/kvB, as expected by the `feeRate` option of Core v0.17 to v20.
+pub(crate) fn serialize_fee_rate_btc_per_kvb<S>(
+    fee_rate: &Option<bitcoin::FeeRate>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    match fee_rate {
+        Some(fee_rate) => serializer.serialize_str(&fee_rate_btc_per_kvb(*fee_rate)),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Serializes the fee rate in sat/vB, as expected by the `fee_rate` option from Core v21 onwards.
+pub(crate) fn serialize_fee_rate_sat_per_vb<S>(
+    fee_rate: &Option<bitcoin::FeeRate>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    match fee_rate {
+        Some(fee_rate) => serializer.serialize_str(&fee_rate_sat_per_vb(*fee_rate)),
+        None => serializer.serialize_none(),
+    }
+}
+
+/// Decodes the hex encoded consensus object returned in `resp`.
+///
+/// The hex string is borrowed from the raw JSON response, so decoding does not allocate an owned
+/// `String` per response, see `decode_hex`.
+pub(crate) fn decode_hex_result<T: bitcoin::consensus::Decodable>(
+    resp: &jsonrpc::Response,
+) -> Result<T> {
+    if let Some(ref e) = resp.error {
+        return Err(jsonrpc::Error::Rpc(e.clone()).into());
+    }
+    let raw = resp.result.as_ref().ok_or(Error::UnexpectedStructure)?;
+    // Hex strings never contain escapes so can be borrowed.
+    let hex: &str = serde_json::from_str(raw.get())?;
+    decode_hex(hex)
+}
+
+/// The capacity the scratch buffers are shrunk to after parsing a larger object.
+const MAX_SCRATCH_CAPACITY: usize = 1 << 20;
+
+/// Decodes the hex encoded consensus object `hex`.
+///
+/// The bytes are decoded into a thread local scratch buffer that is reused across calls, so
+/// decoding does not allocate a `Vec` per object. The buffer is shrunk back after decoding an
+/// object larger than `MAX_SCRATCH_CAPACITY` so a single large block does not stay allocated.
+pub(crate) fn decode_hex<T: bitcoin::consensus::Decodable>(hex: &str) -> Result<T> {
+    use bitcoin::consensus::encode::FromHexError;
+    use bitcoin::consensus::DecodeError;
+
+    thread_local! {
+        static SCRATCH: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+    }
+
+    SCRATCH.with(|scratch| {
+        let mut buf = scratch.borrow_mut();
+        buf.clear();
+
+        #[cfg(feature = "fast-hex")]
+        let decoded = decode_hex_fast(hex, &mut buf);
+        #[cfg(not(feature = "fast-hex"))]
+        let decoded = false;
+
+        // Also used to get a descriptive error if the fast path fails.
+        if !decoded {
+            buf.clear();
+            let iter =
+                bitcoin::hex::HexToBytesIter::new(hex).map_err(FromHexError::OddLengthString)?;
+            buf.reserve(hex.len() / 2);
+            for byte in iter {
+                buf.push(byte.map_err(|e| FromHexError::Decode(DecodeError::Other(e)))?);
+            }
+        }
+        let obj = bitcoin::consensus::deserialize(&buf)
+            .map_err(|e| FromHexError::Decode(DecodeError::Consensus(e)));
+        if buf.capacity() > MAX_SCRATCH_CAPACITY {
+            buf.clear();
+            buf.shrink_to(MAX_SCRATCH_CAPACITY);
+        }
+        Ok(obj?)
+    })
+}
+
+/// Deserializes the result returned in `resp`.
+///
+/// With the `simd-json` feature the result is parsed with `simd-json` instead of `serde_json`,
+/// see `parse_json_simd`.
+pub(crate) fn deserialize_result<T: for<'a> serde::de::Deserialize<'a>>(
+    resp: &jsonrpc::Response,
+) -> Result<T> {
+    #[cfg(feature = "simd-json")]
+    {
+        if let Some(ref e) = resp.error {
+            return Err(jsonrpc::Error::Rpc(e.clone()).into());
+        }
+        if let Some(ref raw) = resp.result {
+            return parse_json_simd(raw.get());
+        }
+    }
+    Ok(resp.result()?)
+}
+
+/// Parses `json` using `simd-json`.
+///
+/// `simd-json` parses in place, so `json` is copied into a thread local scratch buffer. The
+/// buffer and the internal buffers of `simd-json` are reused across calls, and dropped after
+/// parsing an object larger than `MAX_SCRATCH_CAPACITY` like the buffer of `decode_hex`. With
+/// the `zeroize` feature the scratch buffer is overwritten with zeros after parsing.
+#[cfg(feature = "simd-json")]
+fn parse_json_simd<T: for<'a> serde::de::Deserialize<'a>>(json: &str) -> Result<T> {
+    use serde::de::Error as _;
+    use simd_json::Buffers;
+
+    thread_local! {
+        static SCRATCH: RefCell<(Vec<u8>, Buffers)> = RefCell::new(Default::default());
+    }
+
+    SCRATCH.with(|scratch| {
+        let mut scratch = scratch.borrow_mut();
+        let (ref mut buf, ref mut buffers) = *scratch;
+        buf.clear();
+        buf.extend_from_slice(json.as_bytes());
+        let obj = simd_json::serde::from_slice_with_buffers(buf, buffers)
+            .map_err(serde_json::Error::custom);
+        #[cfg(feature = "zeroize")]
+        zeroize::Zeroize::zeroize(buf);
+        if json.len() > MAX_SCRATCH_CAPACITY {
+            *scratch = Default::default();
+        }
+        Ok(obj?)
+    })
+}
+
+/// Decodes `hex` into `buf` using a lookup table, returns false if `hex` is not valid hex.
+///
+/// Validity is checked once at the end instead of per character so the loop body has no branches
+/// and can be vectorized, about four times faster than `HexToBytesIter` for a 2MB block.
+#[cfg(feature = "fast-hex")]
+fn decode_hex_fast(hex: &str, buf: &mut Vec<u8>) -> bool {
+    // Invalid characters map to a value with the high bits set.
+    const INVALID: u8 = 0xff;
+    const TABLE: [u8; 256] = {
+        let mut table = [INVALID; 256];
+        let mut i = 0;
+        while i < 10 {
+            table[b'0' as usize + i] = i as u8;
+            i += 1;
+        }
+        let mut i = 0;
+        while i < 6 {
+            table[b'a' as usize + i] = 10 + i as u8;
+            table[b'A' as usize + i] = 10 + i as u8;
+            i += 1;
+        }
+        table
+    };
+
+    let bytes = hex.as_bytes();
+    if bytes.len() % 2 != 0 {
+        return false;
+    }
+    let start = buf.len();
+    buf.resize(start + bytes.len() / 2, 0);
+
+    let mut invalid = 0;
+    for (out, pair) in buf[start..].iter_mut().zip(bytes.chunks_exact(2)) {
+        let (high, low) = (TABLE[pair[0] as usize], TABLE[pair[1] as usize]);
+        invalid |= high | low;
+        *out = high << 4 | low;
+    }
+    invalid & 0xf0 == 0
+}
+
+/// Calls taking at least this long are logged at warn level by default.
+pub(crate) const DEFAULT_SLOW_CALL: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Returns the wallet name in `url`, e.g. `http://127.0.0.1:18443/wallet/name`.
+pub(crate) fn wallet_from_url(url: &str) -> Option<String> {
+    url.split_once("/wallet/").map(|(_, wallet)| wallet.trim_end_matches('/').to_owned())
+}
+
+/// Converts `timeout` to the milliseconds argument of the `waitfor*` methods.
+///
+/// Zero means no timeout to the node, so `timeout` is rounded up to at least a millisecond. The
+/// node parses the argument as a 32 bit integer.
+pub(crate) fn timeout_millis(timeout: Duration) -> serde_json::Value {
+    let millis = timeout.as_millis().clamp(1, i32::MAX as u128);
+    (millis as i64).into()
+}
+
+/// Logs the method, wallet, duration and outcome of a call.
+///
+/// Logged at warn level if the call took at least `slow`, otherwise at debug level.
+pub(crate) fn log_call(
+    method: &str,
+    wallet: Option<&str>,
+    elapsed: Duration,
+    slow: Duration,
+    ok: bool,
+) {
+    let wallet = wallet.unwrap_or("-");
+    let outcome = if ok { "ok" } else { "error" };
+    if elapsed >= slow {
+        log::warn!(
+            target: "corepc",
+            "slow call: {} wallet={} duration={:?} outcome={}", method, wallet, elapsed, outcome
+        );
+    } else {
+        log::debug!(
+            target: "corepc",
+            "call: {} wallet={} duration={:?} outcome={}", method, wallet, elapsed, outcome
+        );
+    }
+}
+
 /// Helper to log an RPC response.
-fn log_response(method: &str, resp: &Result<jsonrpc::Response>) {
+pub(crate) fn log_response(method: &str, resp: &Result<jsonrpc::Response>) {
     use log::Level::{Debug, Trace, Warn};
 
     if log::log_enabled!(Warn) || log::log_enabled!(Debug) || log::log_enabled!(Trace) {
@@ -188,3 +954,155 @@ fn log_response(method: &str, resp: &Result<jsonrpc::Response>) {
         }
     }
 }
+
+#[cfg(all(test, feature = "fast-hex"))]
+mod tests {
+    use bitcoin::hex::{DisplayHex as _, FromHex as _};
+
+    use super::*;
+
+    fn decode(hex: &str) -> Option<Vec<u8>> {
+        let mut buf = Vec::new();
+        decode_hex_fast(hex, &mut buf).then_some(buf)
+    }
+
+    #[test]
+    fn decode_hex_fast_matches_from_hex() {
+        let bytes = (0..=u8::MAX).collect::<Vec<u8>>();
+        for hex in [bytes.to_lower_hex_string(), bytes.to_upper_hex_string()] {
+            assert_eq!(decode(&hex), Some(Vec::<u8>::from_hex(&hex).unwrap()));
+        }
+        assert_eq!(decode("aBcD09"), Some(Vec::<u8>::from_hex("aBcD09").unwrap()));
+        assert_eq!(decode(""), Some(vec![]));
+    }
+
+    #[test]
+    fn decode_hex_fast_rejects_odd_length() {
+        assert!(Vec::<u8>::from_hex("abc").is_err());
+        assert_eq!(decode("abc"), None);
+    }
+
+    #[test]
+    fn decode_hex_fast_rejects_non_hex() {
+        for hex in ["0g", "g0", "  ", "0x", "ab\u{e9}", "abcd-f"] {
+            assert!(Vec::<u8>::from_hex(hex).is_err(), "{}", hex);
+            assert_eq!(decode(hex), None, "{}", hex);
+        }
+    }
+
+    #[test]
+    fn decode_hex_fast_appends_to_buffer() {
+        let mut buf = vec![0x01];
+        assert!(decode_hex_fast("ff", &mut buf));
+        assert_eq!(buf, [0x01, 0xff]);
+    }
+}
+
+#[cfg(bench)]
+#[cfg(feature = "fast-hex")]
+mod benches {
+    use bitcoin::hex::{DisplayHex as _, HexToBytesIter};
+    use test::{black_box, Bencher};
+
+    use super::*;
+
+    /// The hex of a 2MB block, about the largest `getblock` response.
+    fn block_hex() -> String {
+        (0..2_000_000).map(|i| i as u8).collect::<Vec<u8>>().to_lower_hex_string()
+    }
+
+    #[bench]
+    fn bench_decode_hex_fast(bh: &mut Bencher) {
+        let hex = block_hex();
+        let mut buf = Vec::new();
+        bh.iter(|| {
+            buf.clear();
+            black_box(decode_hex_fast(&hex, &mut buf));
+        });
+    }
+
+    #[bench]
+    fn bench_decode_hex_to_bytes_iter(bh: &mut Bencher) {
+        let hex = block_hex();
+        let mut buf = Vec::new();
+        bh.iter(|| {
+            buf.clear();
+            buf.extend(HexToBytesIter::new(&hex).unwrap().map(|byte| byte.unwrap()));
+            black_box(&buf);
+        });
+    }
+}
+
+#[cfg(all(test, feature = "simd-json"))]
+mod simd_json_tests {
+    use types::v17::{ChainTips, GetChainTips};
+
+    use super::*;
+
+    fn response(json: &str) -> jsonrpc::Response {
+        serde_json::from_str(&format!(r#"{{"result":{},"error":null,"id":1}}"#, json)).unwrap()
+    }
+
+    #[test]
+    fn parse_json_simd_matches_serde_json() {
+        let json = r#"[
+            {"height":110,"hash":"0f9188f1","branchlen":0,"status":"active"},
+            {"height":109,"hash":"3cb7b2c7","branchlen":2,"status":"valid-fork"}
+        ]"#;
+        let tips: GetChainTips = deserialize_result(&response(json)).unwrap();
+        assert_eq!(tips, serde_json::from_str::<GetChainTips>(json).unwrap());
+
+        let json = r#"{"a":[1.5,-2,18446744073709551615,null,true],"b":"esc\"aped é\n"}"#;
+        let value: serde_json::Value = deserialize_result(&response(json)).unwrap();
+        assert_eq!(value, serde_json::from_str::<serde_json::Value>(json).unwrap());
+    }
+
+    #[test]
+    fn parse_json_simd_errors() {
+        let tip = r#"{"height":"110","hash":"","branchlen":0,"status":"active"}"#;
+        assert!(matches!(deserialize_result::<ChainTips>(&response(tip)), Err(Error::Json(_))));
+
+        let resp: jsonrpc::Response = serde_json::from_str(
+            r#"{"result":null,"error":{"code":-5,"message":"not found"},"id":1}"#,
+        )
+        .unwrap();
+        let err = deserialize_result::<ChainTips>(&resp).unwrap_err();
+        assert!(err.is_invalid_address_or_key());
+    }
+}
+
+#[cfg(bench)]
+#[cfg(feature = "simd-json")]
+mod simd_json_benches {
+    use test::{black_box, Bencher};
+    use types::v17::GetChainTips;
+
+    use super::*;
+
+    /// A `getchaintips` result with many tips, about 1MB of JSON.
+    fn chain_tips_json() -> String {
+        let tips = (0..10_000)
+            .map(|i| {
+                format!(
+                    r#"{{"height":{},"hash":"{:064x}","branchlen":{},"status":"valid-fork"}}"#,
+                    i,
+                    i,
+                    i % 10
+                )
+            })
+            .collect::<Vec<_>>();
+        format!("[{}]", tips.join(","))
+    }
+
+    #[bench]
+    fn bench_parse_json_simd(bh: &mut Bencher) {
+        let json = chain_tips_json();
+        bh.iter(|| black_box(parse_json_simd::<GetChainTips>(&json).unwrap()));
+    }
+
+    #[bench]
+    fn bench_parse_json_serde_json(bh: &mut Bencher) {
+        let json = chain_tips_json();
+        bh.iter(|| black_box(serde_json::from_str::<GetChainTips>(&json).unwrap()));
+    }
+}
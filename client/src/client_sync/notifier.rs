@@ -0,0 +1,422 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Polling based notifications of changes to the state of a `bitcoind` instance.
+//!
+//! Useful when the node does not have ZMQ enabled. See `Client::into_chain_notifier` and
+//! `Client::into_mempool_watcher`.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use bitcoin::{BlockHash, OutPoint, Transaction, Txid};
+
+use crate::client_sync::Result;
+
+/// Maximum reorg depth that can be precisely reported by a [`ChainTracker`].
+const MAX_TRACKED_BLOCKS: usize = 1000;
+
+/// The maximum number of added transactions fetched with a single batch request.
+const GET_TXS_BATCH_SIZE: usize = 100;
+
+/// A change to the best chain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChainEvent {
+    /// A block was connected to the tip of the best chain.
+    BlockConnected {
+        /// The hash of the connected block.
+        hash: BlockHash,
+        /// The height of the connected block.
+        height: u32,
+    },
+    /// A block was disconnected from the tip of the best chain (i.e. a reorg happened).
+    BlockDisconnected {
+        /// The hash of the disconnected block.
+        hash: BlockHash,
+        /// The height the disconnected block was at.
+        height: u32,
+    },
+}
+
+/// Tracks the recent blocks of the best chain and computes the changes when the tip moves.
+///
+/// For the most recent blocks a reorg is reported with the exact blocks disconnected, if a reorg
+/// is deeper than the tracked window all tracked blocks are reported as disconnected.
+#[derive(Clone, Debug)]
+pub struct ChainTracker {
+    /// Best chain as seen by the tracker, keyed by height.
+    chain: BTreeMap<u32, BlockHash>,
+}
+
+impl ChainTracker {
+    /// Creates a tracker starting at the given chain tip.
+    pub fn new(tip: BlockHash, height: u32) -> Self {
+        let mut chain = BTreeMap::new();
+        chain.insert(height, tip);
+        Self { chain }
+    }
+
+    /// Returns the current tip as seen by the tracker.
+    pub fn tip(&self) -> (BlockHash, u32) {
+        let (height, hash) = self.chain.iter().next_back().expect("chain is never empty");
+        (*hash, *height)
+    }
+
+    /// Updates the tracker with a new chain tip.
+    ///
+    /// `prev_block_hash` is used to walk back from `tip` until a block already known to the
+    /// tracker is found. Returns the disconnected blocks (tip first) followed by the connected
+    /// blocks (lowest first).
+    pub fn update<F>(
+        &mut self,
+        tip: BlockHash,
+        height: u32,
+        mut prev_block_hash: F,
+    ) -> Result<Vec<ChainEvent>>
+    where
+        F: FnMut(&BlockHash) -> Result<BlockHash>,
+    {
+        let lowest = *self.chain.keys().next().expect("chain is never empty");
+
+        let mut connected = vec![];
+        let (mut hash, mut height) = (tip, height);
+        loop {
+            if self.chain.get(&height) == Some(&hash) || height < lowest {
+                break;
+            }
+            connected.push((hash, height));
+            if height == 0 {
+                break;
+            }
+            hash = prev_block_hash(&hash)?;
+            height -= 1;
+        }
+        connected.reverse();
+        Ok(self.reorg(hash, height, connected))
+    }
+
+    /// Updates the tracker with a new chain tip that forks off the tracked chain at `fork_height`
+    /// e.g., as found with `getchaintips`.
+    ///
+    /// `block_hash` returns the hash of the best chain block at a height, it is used to check the
+    /// fork point and to get the connected blocks. Returns `None`, leaving the tracker unchanged,
+    /// if the tracked block at `fork_height` is not in the best chain, otherwise the events as
+    /// returned by `update`.
+    pub fn update_at_fork<F>(
+        &mut self,
+        tip: BlockHash,
+        height: u32,
+        fork_height: u32,
+        mut block_hash: F,
+    ) -> Result<Option<Vec<ChainEvent>>>
+    where
+        F: FnMut(u32) -> Result<BlockHash>,
+    {
+        if height < fork_height {
+            return Ok(None);
+        }
+        let fork = if height == fork_height { tip } else { block_hash(fork_height)? };
+        if self.chain.get(&fork_height).is_some_and(|&hash| hash != fork) {
+            return Ok(None);
+        }
+
+        let mut connected = vec![];
+        for height in fork_height.saturating_add(1)..height {
+            connected.push((block_hash(height)?, height));
+        }
+        if height > fork_height {
+            connected.push((tip, height));
+        }
+        Ok(Some(self.reorg(fork, fork_height, connected)))
+    }
+
+    /// Disconnects the tracked blocks above the fork point and connects `connected` (lowest
+    /// first), returning the events.
+    fn reorg(
+        &mut self,
+        fork: BlockHash,
+        fork_height: u32,
+        connected: Vec<(BlockHash, u32)>,
+    ) -> Vec<ChainEvent> {
+        // Everything we have seen above the fork point is stale.
+        let stale = self.chain.split_off(&(fork_height + 1));
+        let mut events: Vec<ChainEvent> = stale
+            .into_iter()
+            .rev()
+            .map(|(height, hash)| ChainEvent::BlockDisconnected { hash, height })
+            .collect();
+
+        self.chain.insert(fork_height, fork);
+        for (hash, height) in connected {
+            self.chain.insert(height, hash);
+            events.push(ChainEvent::BlockConnected { hash, height });
+        }
+
+        while self.chain.len() > MAX_TRACKED_BLOCKS {
+            self.chain.pop_first();
+        }
+        events
+    }
+}
+
+/// Delivers [`ChainEvent`]s produced by polling a node from a background thread.
+///
+/// The background thread is stopped when this struct is dropped.
+#[derive(Debug)]
+pub struct ChainNotifier(Poller<ChainEvent>);
+
+impl ChainNotifier {
+    /// Spawns a background thread that calls `poll` in a loop.
+    ///
+    /// `poll` is expected to block for up to `poll_interval` waiting for a new tip e.g., with
+    /// `waitfornewblock`, so it is called again as soon as it returns. All events returned by
+    /// `poll` are delivered in order. Polling errors are logged and the poll retried after
+    /// `poll_interval`. Dropping the notifier waits for the current poll to return.
+    pub fn spawn<F>(poll_interval: Duration, poll: F) -> Self
+    where
+        F: FnMut() -> Result<Vec<ChainEvent>> + Send + 'static,
+    {
+        Self(Poller::spawn("chain notifier", poll_interval, true, poll))
+    }
+
+    /// Returns the receiving end of the event channel.
+    pub fn events(&self) -> &Receiver<ChainEvent> { &self.0.events }
+}
+
+/// A change to the mempool.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MempoolEvent {
+    /// A transaction was added to the mempool.
+    Added(Transaction),
+    /// A transaction left the mempool e.g., it was mined, evicted or expired.
+    Removed(Txid),
+    /// A transaction left the mempool because a transaction spending the same outputs was added.
+    ///
+    /// The replacement is also reported as [`MempoolEvent::Added`].
+    Replaced {
+        /// The id of the replaced transaction.
+        txid: Txid,
+        /// The id of the transaction that replaced it.
+        replacement: Txid,
+    },
+}
+
+/// Tracks the transactions in the mempool and computes the changes between two polls.
+///
+/// Only the outputs spent by each transaction are kept, to tell replacements from removals.
+#[derive(Clone, Debug, Default)]
+pub struct MempoolTracker {
+    /// The mempool sequence number of the last update.
+    sequence: Option<u64>,
+    /// The transactions in the mempool and the outputs they spend.
+    txs: BTreeMap<Txid, Vec<OutPoint>>,
+}
+
+impl MempoolTracker {
+    /// Creates a tracker for an empty mempool.
+    pub fn new() -> Self { Self::default() }
+
+    /// Updates the tracker with the mempool as returned by `getrawmempool` with `mempool_sequence`.
+    ///
+    /// If `sequence` did not change since the last update the mempool did not change either and
+    /// `txids` is not looked at. `get_txs` is called with chunks of the added transactions, and
+    /// returns the result of fetching each of them in order, a transaction that is no longer in the
+    /// mempool by then is skipped. Returns the removed and replaced transactions followed by the
+    /// added transactions.
+    pub fn update<F>(
+        &mut self,
+        txids: Vec<Txid>,
+        sequence: u64,
+        mut get_txs: F,
+    ) -> Result<Vec<MempoolEvent>>
+    where
+        F: FnMut(&[Txid]) -> Result<Vec<Result<Transaction>>>,
+    {
+        if self.sequence == Some(sequence) {
+            return Ok(vec![]);
+        }
+
+        let current: BTreeSet<Txid> = txids.into_iter().collect();
+        let removed: Vec<Txid> =
+            self.txs.keys().filter(|&txid| !current.contains(txid)).copied().collect();
+
+        let new: Vec<Txid> =
+            current.iter().filter(|&txid| !self.txs.contains_key(txid)).copied().collect();
+        let mut added = vec![];
+        for chunk in new.chunks(GET_TXS_BATCH_SIZE) {
+            for result in get_txs(chunk)? {
+                match result {
+                    Ok(tx) => added.push(tx),
+                    // Left the mempool since `getrawmempool` was called.
+                    Err(e) if e.is_invalid_address_or_key() => continue,
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+
+        // The added transaction spending an output, to find what replaced a removed transaction.
+        let mut spent_by = BTreeMap::new();
+        for tx in &added {
+            let txid = tx.compute_txid();
+            for input in &tx.input {
+                spent_by.insert(input.previous_output, txid);
+            }
+        }
+
+        let mut events = vec![];
+        for txid in removed {
+            let spent = self.txs.remove(&txid).expect("removed txid is tracked");
+            match spent.iter().find_map(|outpoint| spent_by.get(outpoint)) {
+                Some(&replacement) => events.push(MempoolEvent::Replaced { txid, replacement }),
+                None => events.push(MempoolEvent::Removed(txid)),
+            }
+        }
+        for tx in added {
+            let spent = tx.input.iter().map(|input| input.previous_output).collect();
+            self.txs.insert(tx.compute_txid(), spent);
+            events.push(MempoolEvent::Added(tx));
+        }
+
+        self.sequence = Some(sequence);
+        Ok(events)
+    }
+}
+
+/// Delivers [`MempoolEvent`]s produced by polling a node from a background thread.
+///
+/// The background thread is stopped when this struct is dropped.
+#[derive(Debug)]
+pub struct MempoolWatcher(Poller<MempoolEvent>);
+
+impl MempoolWatcher {
+    /// Spawns a background thread that calls `poll` every `poll_interval`.
+    ///
+    /// All events returned by `poll` are delivered in order. Polling errors are logged and the
+    /// poll retried on the next interval.
+    pub fn spawn<F>(poll_interval: Duration, poll: F) -> Self
+    where
+        F: FnMut() -> Result<Vec<MempoolEvent>> + Send + 'static,
+    {
+        Self(Poller::spawn("mempool watcher", poll_interval, false, poll))
+    }
+
+    /// Returns the receiving end of the event channel.
+    pub fn events(&self) -> &Receiver<MempoolEvent> { &self.0.events }
+}
+
+/// Calls a poll function from a background thread and sends the events it returns.
+#[derive(Debug)]
+struct Poller<E> {
+    events: Receiver<E>,
+    shutdown: Option<Sender<()>>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl<E: Send + 'static> Poller<E> {
+    /// Spawns a background thread that calls `poll` every `poll_interval`, `name` is for logging.
+    ///
+    /// If `blocking` then `poll` waits for changes itself and is only delayed after an error.
+    fn spawn<F>(name: &'static str, poll_interval: Duration, blocking: bool, mut poll: F) -> Self
+    where
+        F: FnMut() -> Result<Vec<E>> + Send + 'static,
+    {
+        let (events_tx, events) = mpsc::channel();
+        let (shutdown, shutdown_rx) = mpsc::channel::<()>();
+
+        let handle = thread::spawn(move || loop {
+            let wait = match poll() {
+                Ok(evs) => {
+                    for e in evs {
+                        if events_tx.send(e).is_err() {
+                            return; // Receiver is gone.
+                        }
+                    }
+                    if blocking {
+                        Duration::ZERO
+                    } else {
+                        poll_interval
+                    }
+                }
+                Err(e) => {
+                    log::warn!(target: "corepc", "{} poll failed: {}", name, e);
+                    poll_interval
+                }
+            };
+            match shutdown_rx.recv_timeout(wait) {
+                Err(RecvTimeoutError::Timeout) => continue,
+                Ok(()) | Err(RecvTimeoutError::Disconnected) => return,
+            }
+        });
+
+        Self { events, shutdown: Some(shutdown), handle: Some(handle) }
+    }
+}
+
+impl<E> Drop for Poller<E> {
+    fn drop(&mut self) {
+        // Dropping the sender wakes up the background thread.
+        drop(self.shutdown.take());
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::hashes::Hash as _;
+
+    use super::*;
+
+    fn hash(n: u8) -> BlockHash { BlockHash::from_byte_array([n; 32]) }
+
+    /// Returns the best chain block hash at a height, for a chain of `hash(height + offset)`.
+    fn chain(offset: u8) -> impl FnMut(u32) -> Result<BlockHash> {
+        move |height| Ok(hash(height as u8 + offset))
+    }
+
+    #[test]
+    fn update_at_fork_connects_blocks() {
+        let mut tracker = ChainTracker::new(hash(0), 0);
+        let events = tracker.update_at_fork(hash(2), 2, 0, chain(0)).unwrap().unwrap();
+        assert_eq!(
+            events,
+            [
+                ChainEvent::BlockConnected { hash: hash(1), height: 1 },
+                ChainEvent::BlockConnected { hash: hash(2), height: 2 },
+            ]
+        );
+        assert_eq!(tracker.tip(), (hash(2), 2));
+    }
+
+    #[test]
+    fn update_at_fork_disconnects_stale_blocks() {
+        let mut tracker = ChainTracker::new(hash(0), 0);
+        tracker.update_at_fork(hash(2), 2, 0, chain(0)).unwrap().unwrap();
+
+        // Blocks 1 and 2 are replaced by blocks 11, 12 and 13.
+        let mut best = |height| Ok(if height == 0 { hash(0) } else { hash(height as u8 + 10) });
+        let events = tracker.update_at_fork(hash(13), 3, 0, &mut best).unwrap().unwrap();
+        assert_eq!(
+            events,
+            [
+                ChainEvent::BlockDisconnected { hash: hash(2), height: 2 },
+                ChainEvent::BlockDisconnected { hash: hash(1), height: 1 },
+                ChainEvent::BlockConnected { hash: hash(11), height: 1 },
+                ChainEvent::BlockConnected { hash: hash(12), height: 2 },
+                ChainEvent::BlockConnected { hash: hash(13), height: 3 },
+            ]
+        );
+    }
+
+    #[test]
+    fn update_at_fork_rejects_wrong_fork() {
+        let mut tracker = ChainTracker::new(hash(0), 0);
+        tracker.update_at_fork(hash(2), 2, 0, chain(0)).unwrap().unwrap();
+
+        // Block 2 is not in the best chain, so it is not the fork point.
+        assert!(tracker.update_at_fork(hash(13), 3, 2, chain(10)).unwrap().is_none());
+        assert_eq!(tracker.tip(), (hash(2), 2));
+    }
+}
@@ -0,0 +1,174 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Hooks called for each call made by a client, e.g. to collect per method latency metrics.
+//!
+//! See `Client::with_observer`. The arguments of a call are not passed to the observer, so that
+//! secrets (e.g. a wallet passphrase) do not end up in logs or metrics.
+//!
+//! With the `tracing` feature each call is also made inside a `corepc.rpc` span, at debug level,
+//! recording the `method`, the `wallet`, and once the call completes its `duration` and `outcome`
+//! (those of the last attempt if the call is retried).
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::client_sync::log_call;
+
+/// Called by a client once each call completes.
+///
+/// Implemented for closures, `client.with_observer(|call: &RpcCall| ...)`.
+pub trait RpcObserver: Send + Sync {
+    /// Called with the outcome of a call, once for each attempt if the call is retried.
+    ///
+    /// Called on the thread making the call, so it should not block.
+    fn on_call(&self, call: &RpcCall<'_>);
+}
+
+impl<F> RpcObserver for F
+where
+    F: Fn(&RpcCall<'_>) + Send + Sync,
+{
+    fn on_call(&self, call: &RpcCall<'_>) { self(call) }
+}
+
+/// A completed call, passed to [`RpcObserver::on_call`].
+///
+/// Each attempt of a retried call is observed separately. A batch is observed once, with the
+/// method of its calls or `batch of <n>` for a batch of different methods.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RpcCall<'a> {
+    /// The RPC method called.
+    pub method: &'a str,
+    /// The wallet in the URL of the client, if any.
+    pub wallet: Option<&'a str>,
+    /// How long the node took to respond, including the HTTP round trip.
+    pub duration: Duration,
+    /// The size in bytes of the serialized arguments, summed over the calls of a batch.
+    pub request_size: usize,
+    /// Whether the call succeeded.
+    pub outcome: Outcome,
+}
+
+/// The outcome of a call, see [`RpcCall`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    /// The node returned a result.
+    Success,
+    /// The node returned an error with this code, see `RpcErrorCode`.
+    ///
+    /// For a batch, the code of the first call that failed.
+    RpcError(i32),
+    /// No response was received e.g., the connection was refused or timed out.
+    TransportError,
+}
+
+impl Outcome {
+    /// Returns the outcome of the response `resp`.
+    pub(crate) fn of<E>(resp: &Result<jsonrpc::Response, E>) -> Self {
+        match resp {
+            Ok(resp) => Outcome::of_response(resp),
+            Err(_) => Outcome::TransportError,
+        }
+    }
+
+    /// Returns the outcome of the responses `resps` of a batch.
+    pub(crate) fn of_batch<E>(resps: &Result<Vec<Option<jsonrpc::Response>>, E>) -> Self {
+        match resps {
+            Ok(resps) => resps
+                .iter()
+                .map(|resp| resp.as_ref().map_or(Outcome::TransportError, Outcome::of_response))
+                .find(|outcome| !outcome.is_success())
+                .unwrap_or(Outcome::Success),
+            Err(_) => Outcome::TransportError,
+        }
+    }
+
+    fn of_response(resp: &jsonrpc::Response) -> Self {
+        match resp.error {
+            Some(ref e) => Outcome::RpcError(e.code),
+            None => Outcome::Success,
+        }
+    }
+
+    /// Returns true if the call succeeded.
+    pub fn is_success(&self) -> bool { matches!(self, Outcome::Success) }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Outcome::Success => f.write_str("ok"),
+            Outcome::RpcError(code) => write!(f, "error {}", code),
+            Outcome::TransportError => f.write_str("transport error"),
+        }
+    }
+}
+
+/// The observer of a client, if any.
+#[derive(Clone, Default)]
+pub(crate) struct Observer(Option<Arc<dyn RpcObserver>>);
+
+impl Observer {
+    /// Wraps `observer`.
+    pub(crate) fn new(observer: impl RpcObserver + 'static) -> Self {
+        Self(Some(Arc::new(observer)))
+    }
+
+    /// Logs `call` and passes it to the observer, see `log_call` for the use of `slow`.
+    pub(crate) fn finish(&self, call: &RpcCall<'_>, slow: Duration) {
+        log_call(call.method, call.wallet, call.duration, slow, call.outcome.is_success());
+        #[cfg(feature = "tracing")]
+        record_span(call);
+        if let Some(ref observer) = self.0 {
+            observer.on_call(call);
+        }
+    }
+}
+
+impl fmt::Debug for Observer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Some(_) => f.write_str("Observer(Some(..))"),
+            None => f.write_str("Observer(None)"),
+        }
+    }
+}
+
+/// Enters the span of a call to `method`.
+#[cfg(feature = "tracing")]
+pub(crate) fn enter_span(method: &str, wallet: Option<&str>) -> tracing::span::EnteredSpan {
+    call_span(method, wallet).entered()
+}
+
+/// Without the `tracing` feature calls are made outside of any span.
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn enter_span(_method: &str, _wallet: Option<&str>) -> NoSpan { NoSpan }
+
+/// Stands in for the span guard without the `tracing` feature.
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct NoSpan;
+
+/// Returns the span of a call to `method`.
+#[cfg(feature = "tracing")]
+pub(crate) fn call_span(method: &str, wallet: Option<&str>) -> tracing::Span {
+    tracing::debug_span!(
+        target: "corepc",
+        "corepc.rpc",
+        method,
+        wallet = wallet.unwrap_or("-"),
+        duration = tracing::field::Empty,
+        outcome = tracing::field::Empty,
+    )
+}
+
+/// Records the duration and outcome of `call` in its span, the current one.
+#[cfg(feature = "tracing")]
+fn record_span(call: &RpcCall<'_>) {
+    let span = tracing::Span::current();
+    if span.metadata().is_some_and(|meta| meta.name() == "corepc.rpc") {
+        span.record("duration", tracing::field::debug(call.duration));
+        span.record("outcome", tracing::field::display(call.outcome));
+    }
+}
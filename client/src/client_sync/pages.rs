@@ -0,0 +1,84 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Lazy pagination of list style RPCs that take a `count` and `skip` argument.
+//!
+//! See `Client::list_transactions_paged`.
+
+use std::fmt;
+
+use crate::client_sync::Result;
+
+/// Fetches the page of `count` entries after skipping the first `skip` entries.
+type Fetch<'a, T> = Box<dyn FnMut(usize, usize) -> Result<Vec<T>> + 'a>;
+
+/// An iterator over the entries of a list style RPC, fetching a page of entries at a time.
+///
+/// A page shorter than the page size is the last one. The iterator is fused after returning an
+/// error. Entries added or removed on the node between fetching two pages shift the offsets, so
+/// an entry may be skipped or returned twice.
+pub struct Pages<'a, T> {
+    /// Fetches a page of entries.
+    fetch: Fetch<'a, T>,
+    /// The number of entries to fetch at a time.
+    page_size: usize,
+    /// The number of entries fetched so far.
+    skip: usize,
+    /// The entries of the current page not yet returned.
+    page: std::vec::IntoIter<T>,
+    /// True if there are no more pages to fetch.
+    done: bool,
+}
+
+impl<'a, T> Pages<'a, T> {
+    /// Creates an iterator fetching `page_size` entries at a time using `fetch(count, skip)`.
+    ///
+    /// A `page_size` of zero is treated as one.
+    pub fn new<F>(page_size: usize, fetch: F) -> Self
+    where
+        F: FnMut(usize, usize) -> Result<Vec<T>> + 'a,
+    {
+        Self {
+            fetch: Box::new(fetch),
+            page_size: page_size.max(1),
+            skip: 0,
+            page: Vec::new().into_iter(),
+            done: false,
+        }
+    }
+}
+
+impl<T> Iterator for Pages<'_, T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(entry) = self.page.next() {
+            return Some(Ok(entry));
+        }
+        if self.done {
+            return None;
+        }
+
+        match (self.fetch)(self.page_size, self.skip) {
+            Ok(page) => {
+                self.done = page.len() < self.page_size;
+                self.skip += page.len();
+                self.page = page.into_iter();
+                self.page.next().map(Ok)
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+impl<T> fmt::Debug for Pages<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Pages")
+            .field("page_size", &self.page_size)
+            .field("skip", &self.skip)
+            .field("done", &self.done)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,84 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Previews of what a wallet send would do, without broadcasting anything.
+//!
+//! See `Client::preview_send`.
+
+use bitcoin::{Amount, FeeRate, OutPoint, Psbt, TxOut};
+
+use crate::client_sync::{Error, Result};
+
+/// The result of running coin selection for a send, without signing or broadcasting.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SendPreview {
+    /// The funded (unsigned) PSBT.
+    pub psbt: Psbt,
+    /// The inputs selected by the wallet.
+    pub inputs: Vec<PreviewInput>,
+    /// The change output added by the wallet, if any.
+    pub change: Option<PreviewChange>,
+    /// Fee the transaction pays.
+    pub fee: Amount,
+    /// Fee rate of the signed transaction, if the node was able to estimate it.
+    ///
+    /// Always `None` for Core v0.17 which does not have `analyzepsbt`.
+    pub effective_fee_rate: Option<FeeRate>,
+}
+
+/// An input selected by the wallet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PreviewInput {
+    /// The outpoint being spent.
+    pub outpoint: OutPoint,
+    /// The value of the output being spent.
+    pub amount: Amount,
+}
+
+/// The change output added by the wallet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PreviewChange {
+    /// The position of the change output in the transaction.
+    pub position: u32,
+    /// The change amount.
+    pub amount: Amount,
+}
+
+impl SendPreview {
+    /// Creates a preview from the fields of a `walletcreatefundedpsbt` result.
+    ///
+    /// Errors if the PSBT is missing UTXO information for any input or if `change_position` is
+    /// out of bounds.
+    pub fn from_funded_psbt(psbt: Psbt, fee: Amount, change_position: i64) -> Result<Self> {
+        let inputs = psbt
+            .unsigned_tx
+            .input
+            .iter()
+            .zip(psbt.inputs.iter())
+            .map(|(txin, input)| {
+                let outpoint = txin.previous_output;
+                let utxo = match (&input.witness_utxo, &input.non_witness_utxo) {
+                    (Some(utxo), _) => Some(utxo),
+                    (None, Some(tx)) => tx.output.get(outpoint.vout as usize),
+                    (None, None) => None,
+                };
+                let amount =
+                    utxo.map(|utxo: &TxOut| utxo.value).ok_or(Error::UnexpectedStructure)?;
+                Ok(PreviewInput { outpoint, amount })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let change = match u32::try_from(change_position) {
+            Ok(position) => {
+                let output = psbt
+                    .unsigned_tx
+                    .output
+                    .get(position as usize)
+                    .ok_or(Error::UnexpectedStructure)?;
+                Some(PreviewChange { position, amount: output.value })
+            }
+            Err(_) => None, // -1 means no change output.
+        };
+
+        Ok(Self { psbt, inputs, change, fee, effective_fee_rate: None })
+    }
+}
@@ -0,0 +1,156 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! The fund, sign, finalize and broadcast PSBT workflow.
+//!
+//! See `Client::psbt_pipeline`.
+
+use std::{error, fmt};
+
+use bitcoin::psbt::PsbtParseError;
+use bitcoin::{Psbt, SignedAmount, Transaction};
+
+use crate::client_sync::Error;
+
+/// A PSBT moving through the wallet workflow, `S` is the current stage.
+///
+/// Stages are:
+///
+/// - [`Funded`]: created by `walletcreatefundedpsbt` (or provided by the user).
+/// - [`Processed`]: updated and signed by `walletprocesspsbt`, possibly by several parties and
+///   combined with `combinepsbt`.
+/// - [`Finalized`]: finalized and extracted by `finalizepsbt`.
+///
+/// The stage transitions are implemented for each version specific `Client`.
+#[derive(Debug)]
+pub struct PsbtPipeline<'a, C, S> {
+    client: &'a C,
+    state: S,
+}
+
+impl<'a, C, S> PsbtPipeline<'a, C, S> {
+    /// Creates a pipeline at stage `state`.
+    pub fn new(client: &'a C, state: S) -> Self { Self { client, state } }
+
+    /// Returns the client used to move through the workflow.
+    pub fn client(&self) -> &'a C { self.client }
+
+    /// Returns the current stage.
+    pub fn state(&self) -> &S { &self.state }
+
+    /// Consumes the pipeline returning the current stage.
+    pub fn into_state(self) -> S { self.state }
+}
+
+/// A funded PSBT.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Funded {
+    /// The funded PSBT.
+    pub psbt: Psbt,
+    /// Fee the resulting transaction pays, if known.
+    pub fee: Option<SignedAmount>,
+    /// The position of the added change output, if there is one.
+    pub change_position: Option<u32>,
+}
+
+/// A PSBT that has been processed (updated and signed) by the wallet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Processed {
+    /// The processed PSBT.
+    pub psbt: Psbt,
+    /// If the PSBT has a complete set of signatures.
+    pub complete: bool,
+}
+
+/// A finalized transaction ready for broadcast.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Finalized {
+    /// The fully signed transaction.
+    pub tx: Transaction,
+}
+
+/// A stage of the PSBT workflow.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PsbtStage {
+    /// Funding the PSBT (`walletcreatefundedpsbt`).
+    Fund,
+    /// Processing the PSBT (`walletprocesspsbt`).
+    Process,
+    /// Combining PSBTs processed by several parties (`combinepsbt`).
+    Combine,
+    /// Finalizing the PSBT (`finalizepsbt`).
+    Finalize,
+    /// Broadcasting the transaction (`sendrawtransaction`).
+    Broadcast,
+}
+
+impl fmt::Display for PsbtStage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use PsbtStage::*;
+
+        match *self {
+            Fund => write!(f, "walletcreatefundedpsbt"),
+            Process => write!(f, "walletprocesspsbt"),
+            Combine => write!(f, "combinepsbt"),
+            Finalize => write!(f, "finalizepsbt"),
+            Broadcast => write!(f, "sendrawtransaction"),
+        }
+    }
+}
+
+/// Error returned when a stage of the PSBT workflow fails.
+#[derive(Debug)]
+pub enum PsbtPipelineError {
+    /// The client returned an error.
+    Client {
+        /// The stage that failed.
+        stage: PsbtStage,
+        /// The underlying error.
+        error: Error,
+    },
+    /// The daemon returned an invalid PSBT.
+    InvalidPsbt {
+        /// The stage that failed.
+        stage: PsbtStage,
+        /// The underlying error.
+        error: PsbtParseError,
+    },
+    /// The PSBT could not be finalized because it is missing signatures.
+    Incomplete(Box<Psbt>),
+}
+
+impl PsbtPipelineError {
+    /// Returns the stage that failed.
+    pub fn stage(&self) -> PsbtStage {
+        use PsbtPipelineError::*;
+
+        match *self {
+            Client { stage, .. } | InvalidPsbt { stage, .. } => stage,
+            Incomplete(_) => PsbtStage::Finalize,
+        }
+    }
+}
+
+impl fmt::Display for PsbtPipelineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use PsbtPipelineError::*;
+
+        match *self {
+            Client { stage, ref error } => write!(f, "{} failed: {}", stage, error),
+            InvalidPsbt { stage, ref error } =>
+                write!(f, "{} returned an invalid PSBT: {}", stage, error),
+            Incomplete(_) => write!(f, "PSBT is missing signatures and cannot be finalized"),
+        }
+    }
+}
+
+impl error::Error for PsbtPipelineError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        use PsbtPipelineError::*;
+
+        match *self {
+            Client { ref error, .. } => Some(error),
+            InvalidPsbt { ref error, .. } => Some(error),
+            Incomplete(_) => None,
+        }
+    }
+}
@@ -0,0 +1,84 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Fee bumping of unconfirmed wallet transactions.
+//!
+//! See `Client::replace_by_fee`.
+
+use std::{error, fmt};
+
+use bitcoin::psbt::PsbtParseError;
+use bitcoin::{Psbt, Txid};
+
+use crate::client_sync::Error;
+use crate::types::v17::Bip125Replaceable;
+
+/// The replacement created by `Client::replace_by_fee`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Replacement {
+    /// The replacement was signed and broadcast by the wallet.
+    Txid(Txid),
+    /// The wallet cannot sign (e.g. it is watch-only), the unsigned replacement is returned.
+    Psbt(Psbt),
+}
+
+/// Error returned when a transaction could not be fee bumped.
+#[derive(Debug)]
+pub enum ReplaceByFeeError {
+    /// The client returned an error.
+    Client(Error),
+    /// The transaction is already confirmed.
+    AlreadyConfirmed {
+        /// Number of confirmations the transaction has.
+        confirmations: u32,
+    },
+    /// The transaction conflicts with a confirmed transaction.
+    Conflicted,
+    /// The transaction does not signal replaceability (BIP-125).
+    NotReplaceable,
+    /// The daemon returned an invalid PSBT.
+    InvalidPsbt(PsbtParseError),
+}
+
+impl From<Error> for ReplaceByFeeError {
+    fn from(e: Error) -> Self { Self::Client(e) }
+}
+
+impl fmt::Display for ReplaceByFeeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ReplaceByFeeError::*;
+
+        match *self {
+            Client(ref e) => write!(f, "client error: {}", e),
+            AlreadyConfirmed { confirmations } =>
+                write!(f, "transaction already has {} confirmations", confirmations),
+            Conflicted => write!(f, "transaction conflicts with a confirmed transaction"),
+            NotReplaceable => write!(f, "transaction does not signal BIP-125 replaceability"),
+            InvalidPsbt(ref e) => write!(f, "invalid PSBT: {}", e),
+        }
+    }
+}
+
+impl error::Error for ReplaceByFeeError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        use ReplaceByFeeError::*;
+
+        match *self {
+            Client(ref e) => Some(e),
+            InvalidPsbt(ref e) => Some(e),
+            AlreadyConfirmed { .. } | Conflicted | NotReplaceable => None,
+        }
+    }
+}
+
+/// Checks that a wallet transaction can be fee bumped, given its `gettransaction` fields.
+pub fn check_replaceable(
+    confirmations: i64,
+    replaceable: Bip125Replaceable,
+) -> Result<(), ReplaceByFeeError> {
+    match confirmations {
+        n if n > 0 => Err(ReplaceByFeeError::AlreadyConfirmed { confirmations: n as u32 }),
+        n if n < 0 => Err(ReplaceByFeeError::Conflicted),
+        _ if replaceable == Bip125Replaceable::No => Err(ReplaceByFeeError::NotReplaceable),
+        _ => Ok(()),
+    }
+}
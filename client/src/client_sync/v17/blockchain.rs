@@ -12,10 +12,32 @@
 /// Implements Bitcoin Core JSON-RPC API method `getblockchaininfo`.
 #[macro_export]
 macro_rules! impl_client_v17__get_blockchain_info {
+    ($($async:ident)?) => {
+        impl Client {
+            pub $($async)? fn get_blockchain_info(&self) -> Result<GetBlockchainInfo> {
+                $crate::maybe_await!($($async)? self.call("getblockchaininfo", &[]))
+            }
+        }
+    };
+}
+
+/// Implements a sync check using the JSON-RPC API method `getblockchaininfo`.
+///
+/// Requires `Client` to be in scope and implement `get_blockchain_info()`.
+#[macro_export]
+macro_rules! impl_client_v17__is_synced {
     () => {
         impl Client {
-            pub fn get_blockchain_info(&self) -> Result<GetBlockchainInfo> {
-                self.call("getblockchaininfo", &[])
+            /// Returns true if the node is not in IBD and is at most `threshold` blocks behind the
+            /// validated headers.
+            ///
+            /// See `model::GetBlockchainInfo::is_synced`.
+            pub fn is_synced(&self, threshold: u32) -> Result<bool> {
+                let info = self
+                    .get_blockchain_info()?
+                    .into_model()
+                    .map_err(|_| Error::UnexpectedStructure)?;
+                Ok(info.is_synced(threshold))
             }
         }
     };
@@ -24,16 +46,16 @@ macro_rules! impl_client_v17__get_blockchain_info {
 /// Implements Bitcoin Core JSON-RPC API method `getbestblockhash`.
 #[macro_export]
 macro_rules! impl_client_v17__get_best_block_hash {
-    () => {
+    ($($async:ident)?) => {
         impl Client {
             /// Gets the blockhash of the current chain tip.
-            pub fn best_block_hash(&self) -> Result<bitcoin::BlockHash> {
-                let json = self.get_best_block_hash()?;
+            pub $($async)? fn best_block_hash(&self) -> Result<bitcoin::BlockHash> {
+                let json = $crate::maybe_await!($($async)? self.get_best_block_hash())?;
                 Ok(json.block_hash()?)
             }
 
-            pub fn get_best_block_hash(&self) -> Result<GetBestBlockHash> {
-                self.call("getbestblockhash", &[])
+            pub $($async)? fn get_best_block_hash(&self) -> Result<GetBestBlockHash> {
+                $crate::maybe_await!($($async)? self.call("getbestblockhash", &[]))
             }
         }
     };
@@ -42,22 +64,33 @@ macro_rules! impl_client_v17__get_best_block_hash {
 /// Implements Bitcoin Core JSON-RPC API method `getblock`.
 #[macro_export]
 macro_rules! impl_client_v17__get_block {
-    () => {
+    ($($async:ident)?) => {
         impl Client {
             /// Gets a block by blockhash.
-            pub fn get_block(&self, hash: BlockHash) -> Result<Block> {
-                let json = self.get_block_verbose_zero(hash)?;
-                Ok(json.block()?)
+            pub $($async)? fn get_block(&self, hash: BlockHash) -> Result<Block> {
+                $crate::maybe_await!(
+                    $($async)? self.call_hex("getblock", &[into_json(hash)?, 0.into()])
+                )
             }
 
             /// Gets a block by blockhash with verbose set to 0.
-            pub fn get_block_verbose_zero(&self, hash: BlockHash) -> Result<GetBlockVerboseZero> {
-                self.call("getblock", &[into_json(hash)?, 0.into()])
+            pub $($async)? fn get_block_verbose_zero(
+                &self,
+                hash: BlockHash,
+            ) -> Result<GetBlockVerboseZero> {
+                $crate::maybe_await!(
+                    $($async)? self.call("getblock", &[into_json(hash)?, 0.into()])
+                )
             }
 
             /// Gets a block by blockhash with verbose set to 1.
-            pub fn get_block_verbose_one(&self, hash: BlockHash) -> Result<GetBlockVerboseOne> {
-                self.call("getblock", &[into_json(hash)?, 1.into()])
+            pub $($async)? fn get_block_verbose_one(
+                &self,
+                hash: BlockHash,
+            ) -> Result<GetBlockVerboseOne> {
+                $crate::maybe_await!(
+                    $($async)? self.call("getblock", &[into_json(hash)?, 1.into()])
+                )
             }
         }
     };
@@ -66,10 +99,10 @@ macro_rules! impl_client_v17__get_block {
 /// Implements Bitcoin Core JSON-RPC API method `getblockcount`.
 #[macro_export]
 macro_rules! impl_client_v17__get_block_count {
-    () => {
+    ($($async:ident)?) => {
         impl Client {
-            pub fn get_block_count(&self) -> Result<GetBlockCount> {
-                self.call("getblockcount", &[])
+            pub $($async)? fn get_block_count(&self) -> Result<GetBlockCount> {
+                $crate::maybe_await!($($async)? self.call("getblockcount", &[]))
             }
         }
     };
@@ -78,10 +111,10 @@ macro_rules! impl_client_v17__get_block_count {
 /// Implements Bitcoin Core JSON-RPC API method `getblockhash`.
 #[macro_export]
 macro_rules! impl_client_v17__get_block_hash {
-    () => {
+    ($($async:ident)?) => {
         impl Client {
-            pub fn get_block_hash(&self, height: u64) -> Result<GetBlockHash> {
-                self.call("getblockhash", &[into_json(height)?])
+            pub $($async)? fn get_block_hash(&self, height: u64) -> Result<GetBlockHash> {
+                $crate::maybe_await!($($async)? self.call("getblockhash", &[into_json(height)?]))
             }
         }
     };
@@ -90,18 +123,161 @@ macro_rules! impl_client_v17__get_block_hash {
 /// Implements Bitcoin Core JSON-RPC API method `getblockheader`.
 #[macro_export]
 macro_rules! impl_client_v17__get_block_header {
-    () => {
+    ($($async:ident)?) => {
         impl Client {
-            pub fn get_block_header(&self, hash: &BlockHash) -> Result<GetBlockHeader> {
-                self.call("getblockheader", &[into_json(hash)?, into_json(false)?])
+            pub $($async)? fn get_block_header(&self, hash: &BlockHash) -> Result<GetBlockHeader> {
+                $crate::maybe_await!(
+                    $($async)? self.call("getblockheader", &[into_json(hash)?, into_json(false)?])
+                )
             }
 
             // This is the same as calling getblockheader with verbose==true.
-            pub fn get_block_header_verbose(
+            pub $($async)? fn get_block_header_verbose(
                 &self,
                 hash: &BlockHash,
             ) -> Result<GetBlockHeaderVerbose> {
-                self.call("getblockheader", &[into_json(hash)?])
+                $crate::maybe_await!($($async)? self.call("getblockheader", &[into_json(hash)?]))
+            }
+        }
+    };
+}
+
+/// Implements a header-chain sync helper using `getblockhash` and `getblockheader`.
+///
+/// Requires `impl_client_v17__get_block_count` to also be implemented.
+#[macro_export]
+macro_rules! impl_client_v17__block_headers_from {
+    () => {
+        impl Client {
+            /// Gets all block headers from `start_height` up to and including the current tip.
+            ///
+            /// Headers are requested using JSON-RPC batches and validated before being returned:
+            /// each header must hash to the block hash reported for its height, meet its own
+            /// proof of work target, and commit to the header before it.
+            ///
+            /// If `start_height` is above the current tip an empty vector is returned. A reorg
+            /// while the headers are being downloaded shows up as a [`HeaderChainError`], the
+            /// caller should retry.
+            ///
+            /// [`HeaderChainError`]: $crate::client_sync::HeaderChainError
+            pub fn block_headers_from(
+                &self,
+                start_height: u32,
+            ) -> Result<Vec<(u32, bitcoin::block::Header)>> {
+                use $crate::client_sync::HeaderChainError as E;
+
+                // Keep each HTTP request body to a reasonable size.
+                const BATCH_SIZE: u32 = 2000;
+
+                let tip = u32::try_from(self.get_block_count()?.0)
+                    .map_err(|_| Error::UnexpectedStructure)?;
+
+                let mut headers: Vec<(u32, bitcoin::block::Header)> = vec![];
+                let mut height = start_height;
+                while height <= tip {
+                    let end = tip.min(height.saturating_add(BATCH_SIZE - 1));
+
+                    let args = (height..=end).map(|h| vec![h.into()]).collect::<Vec<_>>();
+                    let hashes: Vec<BlockHash> = self.call_batch("getblockhash", &args)?;
+
+                    let args = hashes
+                        .iter()
+                        .map(|hash| Ok(vec![into_json(hash)?, false.into()]))
+                        .collect::<Result<Vec<_>>>()?;
+                    let headers_batch: Vec<bitcoin::block::Header> =
+                        self.call_batch_hex("getblockheader", &args)?;
+
+                    for ((h, hash), header) in (height..=end).zip(hashes).zip(headers_batch) {
+                        if header.block_hash() != hash {
+                            return Err(E::HashMismatch { height: h }.into());
+                        }
+                        if header.validate_pow(header.target()).is_err() {
+                            return Err(E::InvalidProofOfWork { height: h }.into());
+                        }
+                        if let Some((_, prev)) = headers.last() {
+                            if header.prev_blockhash != prev.block_hash() {
+                                return Err(E::Disconnected { height: h }.into());
+                            }
+                        }
+                        headers.push((h, header));
+                    }
+
+                    height = match end.checked_add(1) {
+                        Some(next) => next,
+                        None => break,
+                    };
+                }
+                Ok(headers)
+            }
+        }
+    };
+}
+
+/// Implements a chain notifier using `waitfornewblock` and `getchaintips`.
+#[macro_export]
+macro_rules! impl_client_v17__into_chain_notifier {
+    () => {
+        impl Client {
+            /// Consumes the client and spawns a [`ChainNotifier`] that waits for tip changes with
+            /// `waitfornewblock`, for up to `poll_interval` per call.
+            ///
+            /// Blocks are reported as connected and disconnected (on reorg) relative to the chain
+            /// tip at the time this function is called. On a new tip the fork point is found with
+            /// `getchaintips`, where a reorged out tip is the tip of a stale branch, and the
+            /// connected blocks with `getblockhash`. If the stale branch has been extended past the
+            /// tracked tip the fork point is found by walking back the block headers instead.
+            ///
+            /// [`ChainNotifier`]: $crate::client_sync::notifier::ChainNotifier
+            pub fn into_chain_notifier(
+                self,
+                poll_interval: std::time::Duration,
+            ) -> Result<$crate::client_sync::notifier::ChainNotifier> {
+                use $crate::client_sync::notifier::{ChainNotifier, ChainTracker};
+
+                let (hash, height) = self.tip_hash_and_height()?;
+                let mut tracker = ChainTracker::new(hash, height);
+
+                Ok(ChainNotifier::spawn(poll_interval, move || {
+                    let tip = self
+                        .wait_for_new_block_with_timeout(poll_interval)?
+                        .into_model()
+                        .map_err(|_| Error::UnexpectedStructure)?;
+                    let (old_tip, old_height) = tracker.tip();
+                    if tip.hash == old_tip {
+                        return Ok(vec![]);
+                    }
+
+                    let old_tip = old_tip.to_string();
+                    // A reorged out tip is the tip of a stale branch, otherwise it is in the best
+                    // chain (checked by `update_at_fork`).
+                    let tips = self.get_chain_tips()?;
+                    let fork_height = match tips.0.iter().find(|t| t.hash == old_tip) {
+                        Some(stale) => {
+                            let branch_length = u32::try_from(stale.branch_length)
+                                .map_err(|_| Error::UnexpectedStructure)?;
+                            old_height.saturating_sub(branch_length)
+                        }
+                        None => old_height,
+                    };
+                    let block_hash =
+                        |height| Ok(self.get_block_hash(u64::from(height))?.block_hash()?);
+                    match tracker.update_at_fork(tip.hash, tip.height, fork_height, block_hash)? {
+                        Some(events) => Ok(events),
+                        None => tracker.update(tip.hash, tip.height, |hash| {
+                            let header: bitcoin::block::Header =
+                                self.call_hex("getblockheader", &[into_json(hash)?, false.into()])?;
+                            Ok(header.prev_blockhash)
+                        }),
+                    }
+                }))
+            }
+
+            /// Gets the hash and height of the current chain tip.
+            fn tip_hash_and_height(&self) -> Result<(BlockHash, u32)> {
+                let hash = self.best_block_hash()?;
+                let height = self.get_block_header_verbose(&hash)?.height;
+                let height = u32::try_from(height).map_err(|_| Error::UnexpectedStructure)?;
+                Ok((hash, height))
             }
         }
     };
@@ -123,12 +299,81 @@ macro_rules! impl_client_v17__get_block_stats {
     };
 }
 
+/// Implements Bitcoin Core JSON-RPC API method `getblockstats` for a range of heights.
+#[macro_export]
+macro_rules! impl_client_v17__block_stats_range {
+    () => {
+        impl Client {
+            /// Gets the block stats for each height from `start` up to and including `end`.
+            ///
+            /// Heights are requested using JSON-RPC batches, sent from a few threads at once. If
+            /// `stats` is empty all stats are returned and `T` is typically `GetBlockStats`,
+            /// otherwise `T` needs to deserialize from only the selected stats.
+            ///
+            /// Results are returned in height order, an empty vector if `start` is above `end`.
+            pub fn block_stats_range<T>(
+                &self,
+                start: u32,
+                end: u32,
+                stats: &[&str],
+            ) -> Result<Vec<T>>
+            where
+                T: for<'a> serde::de::Deserialize<'a> + core::marker::Send,
+            {
+                // Stats are larger than headers, keep each HTTP request body to a reasonable size.
+                const BATCH_SIZE: usize = 500;
+                const THREADS: usize = 4;
+
+                if start > end {
+                    return Ok(vec![]);
+                }
+
+                let selected = into_json(stats)?;
+                let args = (start..=end)
+                    .map(|height| {
+                        if stats.is_empty() {
+                            vec![height.into()]
+                        } else {
+                            vec![height.into(), selected.clone()]
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                let batches = args.chunks(BATCH_SIZE).collect::<Vec<_>>();
+                let per_thread = batches.len().div_ceil(THREADS);
+
+                std::thread::scope(|scope| {
+                    let handles = batches
+                        .chunks(per_thread)
+                        .map(|batches| {
+                            scope.spawn(move || {
+                                batches
+                                    .iter()
+                                    .map(|batch| self.call_batch::<T>("getblockstats", batch))
+                                    .collect::<Result<Vec<_>>>()
+                            })
+                        })
+                        .collect::<Vec<_>>();
+
+                    let mut all = Vec::with_capacity(args.len());
+                    for handle in handles {
+                        let batches = handle.join().expect("getblockstats thread panicked")?;
+                        all.extend(batches.into_iter().flatten());
+                    }
+                    Ok(all)
+                })
+            }
+        }
+    };
+}
+
 /// Implements Bitcoin Core JSON-RPC API method `getchaintips`.
 #[macro_export]
 macro_rules! impl_client_v17__get_chain_tips {
-    () => {
+    ($($async:ident)?) => {
         impl Client {
-            pub fn get_chain_tips(&self) -> Result<GetChainTips> { self.call("getchaintips", &[]) }
+            pub $($async)? fn get_chain_tips(&self) -> Result<GetChainTips> {
+                $crate::maybe_await!($($async)? self.call("getchaintips", &[]))
+            }
         }
     };
 }
@@ -148,10 +393,10 @@ macro_rules! impl_client_v17__get_chain_tx_stats {
 /// Implements Bitcoin Core JSON-RPC API method `getdifficulty`.
 #[macro_export]
 macro_rules! impl_client_v17__get_difficulty {
-    () => {
+    ($($async:ident)?) => {
         impl Client {
-            pub fn get_difficulty(&self) -> Result<GetDifficulty> {
-                self.call("getdifficulty", &[])
+            pub $($async)? fn get_difficulty(&self) -> Result<GetDifficulty> {
+                $crate::maybe_await!($($async)? self.call("getdifficulty", &[]))
             }
         }
     };
@@ -200,10 +445,10 @@ macro_rules! impl_client_v17__get_mempool_descendants {
 /// Implements Bitcoin Core JSON-RPC API method `getmempoolentry`.
 #[macro_export]
 macro_rules! impl_client_v17__get_mempool_entry {
-    () => {
+    ($($async:ident)?) => {
         impl Client {
-            pub fn get_mempool_entry(&self, txid: Txid) -> Result<GetMempoolEntry> {
-                self.call("getmempoolentry", &[into_json(txid)?])
+            pub $($async)? fn get_mempool_entry(&self, txid: Txid) -> Result<GetMempoolEntry> {
+                $crate::maybe_await!($($async)? self.call("getmempoolentry", &[into_json(txid)?]))
             }
         }
     };
@@ -212,10 +457,10 @@ macro_rules! impl_client_v17__get_mempool_entry {
 /// Implements Bitcoin Core JSON-RPC API method `getmempoolinfo`.
 #[macro_export]
 macro_rules! impl_client_v17__get_mempool_info {
-    () => {
+    ($($async:ident)?) => {
         impl Client {
-            pub fn get_mempool_info(&self) -> Result<GetMempoolInfo> {
-                self.call("getmempoolinfo", &[])
+            pub $($async)? fn get_mempool_info(&self) -> Result<GetMempoolInfo> {
+                $crate::maybe_await!($($async)? self.call("getmempoolinfo", &[]))
             }
         }
     };
@@ -224,15 +469,15 @@ macro_rules! impl_client_v17__get_mempool_info {
 /// Implements Bitcoin Core JSON-RPC API method `getrawmempool`.
 #[macro_export]
 macro_rules! impl_client_v17__get_raw_mempool {
-    () => {
+    ($($async:ident)?) => {
         impl Client {
-            pub fn get_raw_mempool(&self) -> Result<GetRawMempool> {
+            pub $($async)? fn get_raw_mempool(&self) -> Result<GetRawMempool> {
                 // Equivalent to self.call("getrawmempool", &[into_json(false)?])
-                self.call("getrawmempool", &[])
+                $crate::maybe_await!($($async)? self.call("getrawmempool", &[]))
             }
 
-            pub fn get_raw_mempool_verbose(&self) -> Result<GetRawMempoolVerbose> {
-                self.call("getrawmempool", &[into_json(true)?])
+            pub $($async)? fn get_raw_mempool_verbose(&self) -> Result<GetRawMempoolVerbose> {
+                $crate::maybe_await!($($async)? self.call("getrawmempool", &[into_json(true)?]))
             }
         }
     };
@@ -241,10 +486,12 @@ macro_rules! impl_client_v17__get_raw_mempool {
 /// Implements Bitcoin Core JSON-RPC API method `gettxout`.
 #[macro_export]
 macro_rules! impl_client_v17__get_tx_out {
-    () => {
+    ($($async:ident)?) => {
         impl Client {
-            pub fn get_tx_out(&self, txid: Txid, vout: u64) -> Result<GetTxOut> {
-                self.call("gettxout", &[into_json(txid)?, into_json(vout)?])
+            pub $($async)? fn get_tx_out(&self, txid: Txid, vout: u64) -> Result<GetTxOut> {
+                $crate::maybe_await!(
+                    $($async)? self.call("gettxout", &[into_json(txid)?, into_json(vout)?])
+                )
             }
         }
     };
@@ -337,6 +584,14 @@ macro_rules! impl_client_v17__scan_tx_out_set {
                 self.call("scantxoutset", &[into_json("start")?, into_json(scan_objects)?])
             }
 
+            /// Starts a scan of the UTXO set for the typed `scan_objects`, e.g. ranged descriptors.
+            pub fn scan_tx_out_set_start_with_objects(
+                &self,
+                scan_objects: &[ScanObject],
+            ) -> Result<ScanTxOutSetStart> {
+                self.call("scantxoutset", &[into_json("start")?, into_json(scan_objects)?])
+            }
+
             /// Checks the status of an ongoing `scantxoutset` scan.
             pub fn scan_tx_out_set_status(&self) -> Result<Option<ScanTxOutSetStatus>> {
                 self.call("scantxoutset", &[into_json("status")?])
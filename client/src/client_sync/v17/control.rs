@@ -44,9 +44,11 @@ macro_rules! impl_client_v17__logging {
 /// Implements Bitcoin Core JSON-RPC API method `stop`.
 #[macro_export]
 macro_rules! impl_client_v17__stop {
-    () => {
+    ($($async:ident)?) => {
         impl Client {
-            pub fn stop(&self) -> Result<String> { self.call("stop", &[]) }
+            pub $($async)? fn stop(&self) -> Result<String> {
+                $crate::maybe_await!($($async)? self.call("stop", &[]))
+            }
         }
     };
 }
@@ -54,9 +56,11 @@ macro_rules! impl_client_v17__stop {
 /// Implements Bitcoin Core JSON-RPC API method `uptime`.
 #[macro_export]
 macro_rules! impl_client_v17__uptime {
-    () => {
+    ($($async:ident)?) => {
         impl Client {
-            pub fn uptime(&self) -> Result<u32> { self.call("uptime", &[]) }
+            pub $($async)? fn uptime(&self) -> Result<u32> {
+                $crate::maybe_await!($($async)? self.call("uptime", &[]))
+            }
         }
     };
 }
@@ -34,8 +34,23 @@ macro_rules! impl_client_v17__estimate_raw_fee {
 macro_rules! impl_client_v17__wait_for_block {
     () => {
         impl Client {
+            /// Waits for the block with `hash` to be the tip, for up to the long poll timeout.
+            ///
+            /// See `Builder::long_poll_timeout`, returns the current tip on timeout.
             pub fn wait_for_block(&self, hash: &bitcoin::BlockHash) -> Result<WaitForBlock> {
-                self.call("waitforblock", &[into_json(hash)?])
+                self.wait_for_block_with_timeout(hash, self.long_poll.timeout())
+            }
+
+            /// Waits for the block with `hash` to be the tip, for up to `timeout`.
+            ///
+            /// Returns the current tip on timeout.
+            pub fn wait_for_block_with_timeout(
+                &self,
+                hash: &bitcoin::BlockHash,
+                timeout: std::time::Duration,
+            ) -> Result<WaitForBlock> {
+                let args = [into_json(hash)?, $crate::client_sync::timeout_millis(timeout)];
+                self.call_long_poll("waitforblock", &args, timeout)
             }
         }
     };
@@ -46,8 +61,23 @@ macro_rules! impl_client_v17__wait_for_block {
 macro_rules! impl_client_v17__wait_for_block_height {
     () => {
         impl Client {
+            /// Waits for the tip to be at least at `height`, for up to the long poll timeout.
+            ///
+            /// See `Builder::long_poll_timeout`, returns the current tip on timeout.
             pub fn wait_for_block_height(&self, height: u64) -> Result<WaitForBlockHeight> {
-                self.call("waitforblockheight", &[into_json(height)?])
+                self.wait_for_block_height_with_timeout(height, self.long_poll.timeout())
+            }
+
+            /// Waits for the tip to be at least at `height`, for up to `timeout`.
+            ///
+            /// Returns the current tip on timeout.
+            pub fn wait_for_block_height_with_timeout(
+                &self,
+                height: u64,
+                timeout: std::time::Duration,
+            ) -> Result<WaitForBlockHeight> {
+                let args = [into_json(height)?, $crate::client_sync::timeout_millis(timeout)];
+                self.call_long_poll("waitforblockheight", &args, timeout)
             }
         }
     };
@@ -58,8 +88,22 @@ macro_rules! impl_client_v17__wait_for_block_height {
 macro_rules! impl_client_v17__wait_for_new_block {
     () => {
         impl Client {
+            /// Waits for a new tip, for up to the long poll timeout.
+            ///
+            /// See `Builder::long_poll_timeout`, returns the current tip on timeout.
             pub fn wait_for_new_block(&self) -> Result<WaitForNewBlock> {
-                self.call("waitfornewblock", &[])
+                self.wait_for_new_block_with_timeout(self.long_poll.timeout())
+            }
+
+            /// Waits for a new tip, for up to `timeout`.
+            ///
+            /// Returns the current tip on timeout.
+            pub fn wait_for_new_block_with_timeout(
+                &self,
+                timeout: std::time::Duration,
+            ) -> Result<WaitForNewBlock> {
+                let args = [$crate::client_sync::timeout_millis(timeout)];
+                self.call_long_poll("waitfornewblock", &args, timeout)
             }
         }
     };
@@ -24,6 +24,40 @@ macro_rules! impl_client_v17__get_block_template {
     };
 }
 
+/// Implements a long polling iterator over `getblocktemplate` results.
+///
+/// Requires `Client` to be in scope and implement `get_block_template()`.
+#[macro_export]
+macro_rules! impl_client_v17__block_templates {
+    () => {
+        impl Client {
+            /// Returns an iterator that yields a new block template each time the tip or mempool
+            /// changes, using the `longpollid` mechanism of `getblocktemplate`.
+            ///
+            /// Any `longpollid` in `request` is replaced by the ID of the last template returned.
+            pub fn block_templates(
+                &self,
+                request: &TemplateRequest,
+            ) -> Result<$crate::client_sync::long_poll::BlockTemplates<'_, GetBlockTemplate>> {
+                let request = into_json(request)?;
+                if !request.is_object() {
+                    return Err(Error::UnexpectedStructure);
+                }
+
+                Ok($crate::client_sync::long_poll::BlockTemplates::new(move |long_poll_id| {
+                    let mut request = request.clone();
+                    if let Some(id) = long_poll_id {
+                        request["longpollid"] = id.into();
+                    }
+                    let template: GetBlockTemplate = self.call("getblocktemplate", &[request])?;
+                    let id = template.long_poll_id.clone();
+                    Ok((template, id))
+                }))
+            }
+        }
+    };
+}
+
 /// Implements Bitcoin Core JSON-RPC API method `getmininginfo`.
 #[macro_export]
 macro_rules! impl_client_v17__get_mining_info {
@@ -27,15 +27,20 @@ use crate::types::v17::*;
 
 crate::define_jsonrpc_bitreq_client!("v17");
 crate::impl_client_check_expected_server_version!({ [170200] });
+crate::impl_client_deprecated_methods!();
 
 // == Blockchain ==
 crate::impl_client_v17__get_best_block_hash!();
 crate::impl_client_v17__get_block!();
 crate::impl_client_v17__get_blockchain_info!();
+crate::impl_client_v17__is_synced!();
 crate::impl_client_v17__get_block_count!();
 crate::impl_client_v17__get_block_hash!();
 crate::impl_client_v17__get_block_header!();
+crate::impl_client_v17__block_headers_from!();
+crate::impl_client_v17__into_chain_notifier!();
 crate::impl_client_v17__get_block_stats!();
+crate::impl_client_v17__block_stats_range!();
 crate::impl_client_v17__get_chain_tips!();
 crate::impl_client_v17__get_chain_tx_stats!();
 crate::impl_client_v17__get_difficulty!();
@@ -76,6 +81,7 @@ crate::impl_client_v17__reconsider_block!();
 
 // == Mining ==
 crate::impl_client_v17__get_block_template!();
+crate::impl_client_v17__block_templates!();
 crate::impl_client_v17__get_mining_info!();
 crate::impl_client_v17__get_network_hashes_per_second!();
 crate::impl_client_v17__prioritise_transaction!();
@@ -107,7 +113,11 @@ crate::impl_client_v17__decode_script!();
 crate::impl_client_v17__finalize_psbt!();
 crate::impl_client_v17__fund_raw_transaction!();
 crate::impl_client_v17__get_raw_transaction!();
+crate::impl_client_v17__wait_for_confirmations!();
 crate::impl_client_v17__send_raw_transaction!();
+crate::impl_client_v17__broadcast_checked!();
+crate::impl_client_v17__cpfp!();
+crate::impl_client_v17__submit_cpfp!();
 crate::impl_client_v17__sign_raw_transaction!();
 crate::impl_client_v17__sign_raw_transaction_with_key!();
 crate::impl_client_v17__test_mempool_accept!();
@@ -125,6 +135,7 @@ crate::impl_client_v17__abort_rescan!();
 crate::impl_client_v17__add_multisig_address!();
 crate::impl_client_v17__backup_wallet!();
 crate::impl_client_v17__bump_fee!();
+crate::impl_client_v17__replace_by_fee!();
 crate::impl_client_v17__create_wallet!();
 crate::impl_client_v17__dump_priv_key!();
 crate::impl_client_v17__dump_wallet!();
@@ -150,7 +161,9 @@ crate::impl_client_v17__list_labels!();
 crate::impl_client_v17__list_lock_unspent!();
 crate::impl_client_v17__list_received_by_address!();
 crate::impl_client_v17__list_since_block!();
+crate::impl_client_v17__sync_wallet!();
 crate::impl_client_v17__list_transactions!();
+crate::impl_client_v17__list_transactions_paged!();
 crate::impl_client_v17__list_unspent!();
 crate::impl_client_v17__list_wallets!();
 crate::impl_client_v17__load_wallet!();
@@ -165,6 +178,8 @@ crate::impl_client_v17__sign_message!();
 crate::impl_client_v17__sign_raw_transaction_with_wallet!();
 crate::impl_client_v17__unload_wallet!();
 crate::impl_client_v17__wallet_create_funded_psbt!();
+crate::impl_client_v17__preview_send!();
+crate::impl_client_v17__psbt_pipeline!();
 crate::impl_client_v17__wallet_lock!();
 crate::impl_client_v17__wallet_passphrase!();
 crate::impl_client_v17__wallet_passphrase_change!();
@@ -221,7 +236,7 @@ pub enum TemplateRules {
 }
 
 /// Input used as parameter to `create_raw_transaction`.
-#[derive(Debug, Serialize)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct Input {
     /// The txid of the transaction that contains the UTXO.
     pub txid: bitcoin::Txid,
@@ -235,15 +250,15 @@ pub struct Input {
 // Abuse `HashMap` so we can derive serialize to get the correct JSON object.
 #[derive(Debug, Serialize)]
 pub struct Output(
-    /// Map of address to value. Always only has a single item in it.
-    HashMap<String, f64>,
+    /// Map of address to value in BTC. Always only has a single item in it.
+    HashMap<String, serde_json::Value>,
 );
 
 impl Output {
     /// Creates a single output that serializes as Core expects.
     pub fn new(addr: Address, value: Amount) -> Self {
         let mut map = HashMap::new();
-        map.insert(addr.to_string(), value.to_btc());
+        map.insert(addr.to_string(), crate::client_sync::amount_json(value));
         Output(map)
     }
 }
@@ -260,6 +275,112 @@ impl WalletCreateFundedPsbtInput {
     pub fn new(txid: Txid, vout: u32) -> Self { Self { txid, vout } }
 }
 
+/// The `options` argument of method `walletcreatefundedpsbt`.
+///
+/// Options left as `None` are not sent, so the wallet defaults are used.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct WalletCreateFundedPsbtOptions {
+    /// The address to send the change to.
+    #[serde(rename = "changeAddress", skip_serializing_if = "Option::is_none")]
+    pub change_address: Option<Address<NetworkChecked>>,
+    /// The index of the change output.
+    #[serde(rename = "changePosition", skip_serializing_if = "Option::is_none")]
+    pub change_position: Option<u32>,
+    /// Also select inputs which are watch only.
+    #[serde(rename = "includeWatching", skip_serializing_if = "Option::is_none")]
+    pub include_watching: Option<bool>,
+    /// Lock the selected unspent outputs.
+    #[serde(rename = "lockUnspents", skip_serializing_if = "Option::is_none")]
+    pub lock_unspents: Option<bool>,
+    /// The fee rate to pay, instead of estimating it.
+    #[serde(
+        rename = "feeRate",
+        serialize_with = "crate::client_sync::serialize_fee_rate_btc_per_kvb",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub fee_rate: Option<bitcoin::FeeRate>,
+    /// The indexes of the outputs the fee is deducted from, equally.
+    #[serde(rename = "subtractFeeFromOutputs", skip_serializing_if = "Option::is_none")]
+    pub subtract_fee_from_outputs: Option<Vec<u32>>,
+    /// Signal BIP-125 replaceability.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replaceable: Option<bool>,
+    /// The confirmation target in blocks, used to estimate the fee rate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conf_target: Option<u32>,
+}
+
+/// The `options` argument of method `fundrawtransaction`.
+///
+/// Options left as `None` are not sent, so the wallet defaults are used.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct FundRawTransactionOptions {
+    /// The address to send the change to.
+    #[serde(rename = "changeAddress", skip_serializing_if = "Option::is_none")]
+    pub change_address: Option<Address<NetworkChecked>>,
+    /// The index of the change output.
+    #[serde(rename = "changePosition", skip_serializing_if = "Option::is_none")]
+    pub change_position: Option<u32>,
+    /// Also select inputs which are watch only.
+    #[serde(rename = "includeWatching", skip_serializing_if = "Option::is_none")]
+    pub include_watching: Option<bool>,
+    /// Lock the selected unspent outputs.
+    #[serde(rename = "lockUnspents", skip_serializing_if = "Option::is_none")]
+    pub lock_unspents: Option<bool>,
+    /// The fee rate to pay, instead of estimating it.
+    #[serde(
+        rename = "feeRate",
+        serialize_with = "crate::client_sync::serialize_fee_rate_btc_per_kvb",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub fee_rate: Option<bitcoin::FeeRate>,
+    /// The indexes of the outputs the fee is deducted from, equally.
+    #[serde(rename = "subtractFeeFromOutputs", skip_serializing_if = "Option::is_none")]
+    pub subtract_fee_from_outputs: Option<Vec<u32>>,
+    /// Signal BIP-125 replaceability.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replaceable: Option<bool>,
+    /// The confirmation target in blocks, used to estimate the fee rate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conf_target: Option<u32>,
+    /// The weights of the inputs not from the wallet, from Core v23.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_weights: Option<Vec<InputWeight>>,
+}
+
+/// An element in the `input_weights` option of methods `fundrawtransaction` and `send`.
+///
+/// Gives the weight of an input the wallet can not solve, so its fee can be accounted for.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct InputWeight {
+    /// The txid of the transaction that contains the UTXO.
+    pub txid: Txid,
+    /// The vout for the UTXO.
+    pub vout: u32,
+    /// The maximum weight of the input, including the outpoint, the sequence and the witness.
+    pub weight: bitcoin::Weight,
+}
+
+/// The `sighashtype` argument of method `walletprocesspsbt`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub enum SighashType {
+    /// Use `SIGHASH_DEFAULT` for taproot inputs and `SIGHASH_ALL` otherwise, from Core v22.
+    #[serde(rename = "DEFAULT")]
+    Default,
+    #[serde(rename = "ALL")]
+    All,
+    #[serde(rename = "NONE")]
+    None,
+    #[serde(rename = "SINGLE")]
+    Single,
+    #[serde(rename = "ALL|ANYONECANPAY")]
+    AllAnyoneCanPay,
+    #[serde(rename = "NONE|ANYONECANPAY")]
+    NoneAnyoneCanPay,
+    #[serde(rename = "SINGLE|ANYONECANPAY")]
+    SingleAnyoneCanPay,
+}
+
 /// Args for the `addnode` method.
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
@@ -321,3 +442,48 @@ impl Serialize for ImportMultiTimestamp {
         }
     }
 }
+
+/// A scan object of the `scantxoutset` and `scanblocks` methods.
+///
+/// Serializes as `{"desc": .., "range": ..}`, for a ranged descriptor Core scans child indexes
+/// 0 to 1000 if no range is given.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ScanObject {
+    /// The output descriptor to scan for.
+    #[serde(rename = "desc")]
+    pub descriptor: String,
+    /// The range of child indexes of a ranged descriptor, both inclusive.
+    ///
+    /// Core v17 only accepts the end of the range, so the start must be 0. A range starting at 0
+    /// is serialized as its end, which all versions accept.
+    #[serde(skip_serializing_if = "Option::is_none", serialize_with = "serialize_range")]
+    pub range: Option<(u32, u32)>,
+}
+
+impl ScanObject {
+    /// Constructs a scan object for `descriptor`.
+    pub fn new(descriptor: impl Into<String>) -> Self {
+        ScanObject { descriptor: descriptor.into(), range: None }
+    }
+
+    /// Scans the child indexes `begin` to `end` of a ranged descriptor, both inclusive.
+    pub fn with_range(mut self, begin: u32, end: u32) -> Self {
+        self.range = Some((begin, end));
+        self
+    }
+}
+
+/// Serializes the range of a [`ScanObject`] as `end` if it starts at 0 and `[begin, end]` otherwise.
+fn serialize_range<S>(
+    range: &Option<(u32, u32)>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match range {
+        Some((0, end)) => serializer.serialize_u32(*end),
+        Some(range) => range.serialize(serializer),
+        None => serializer.serialize_none(),
+    }
+}
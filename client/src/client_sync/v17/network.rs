@@ -72,10 +72,10 @@ macro_rules! impl_client_v17__get_added_node_info {
 /// Implements Bitcoin Core JSON-RPC API method `getconnectioncount`.
 #[macro_export]
 macro_rules! impl_client_v17__get_connection_count {
-    () => {
+    ($($async:ident)?) => {
         impl Client {
-            pub fn get_connection_count(&self) -> Result<GetConnectionCount> {
-                self.call("getconnectioncount", &[])
+            pub $($async)? fn get_connection_count(&self) -> Result<GetConnectionCount> {
+                $crate::maybe_await!($($async)? self.call("getconnectioncount", &[]))
             }
         }
     };
@@ -94,16 +94,16 @@ macro_rules! impl_client_v17__get_net_totals {
 /// Implements Bitcoin Core JSON-RPC API method `getnetworkinfo`.
 #[macro_export]
 macro_rules! impl_client_v17__get_network_info {
-    () => {
+    ($($async:ident)?) => {
         impl Client {
             /// Returns the server version field of `GetNetworkInfo`.
-            pub fn server_version(&self) -> Result<usize> {
-                let info = self.get_network_info()?;
+            pub $($async)? fn server_version(&self) -> Result<usize> {
+                let info = $crate::maybe_await!($($async)? self.get_network_info())?;
                 Ok(info.version)
             }
 
-            pub fn get_network_info(&self) -> Result<GetNetworkInfo> {
-                self.call("getnetworkinfo", &[])
+            pub $($async)? fn get_network_info(&self) -> Result<GetNetworkInfo> {
+                $crate::maybe_await!($($async)? self.call("getnetworkinfo", &[]))
             }
         }
     };
@@ -112,9 +112,11 @@ macro_rules! impl_client_v17__get_network_info {
 /// Implements Bitcoin Core JSON-RPC API method `getpeerinfo`.
 #[macro_export]
 macro_rules! impl_client_v17__get_peer_info {
-    () => {
+    ($($async:ident)?) => {
         impl Client {
-            pub fn get_peer_info(&self) -> Result<GetPeerInfo> { self.call("getpeerinfo", &[]) }
+            pub $($async)? fn get_peer_info(&self) -> Result<GetPeerInfo> {
+                $crate::maybe_await!($($async)? self.call("getpeerinfo", &[]))
+            }
         }
     };
 }
@@ -149,6 +149,16 @@ macro_rules! impl_client_v17__fund_raw_transaction {
                 let hex = bitcoin::consensus::encode::serialize_hex(tx);
                 self.call("fundrawtransaction", &[hex.into()])
             }
+
+            /// Calls `fundrawtransaction` with the coin selection and fee `options`.
+            pub fn fund_raw_transaction_with_options(
+                &self,
+                tx: &bitcoin::Transaction,
+                options: &FundRawTransactionOptions,
+            ) -> Result<FundRawTransaction> {
+                let hex = bitcoin::consensus::encode::serialize_hex(tx);
+                self.call("fundrawtransaction", &[hex.into(), into_json(options)?])
+            }
         }
     };
 }
@@ -156,17 +166,95 @@ macro_rules! impl_client_v17__fund_raw_transaction {
 /// Implements Bitcoin Core JSON-RPC API method `getrawtransaction`.
 #[macro_export]
 macro_rules! impl_client_v17__get_raw_transaction {
-    () => {
+    ($($async:ident)?) => {
         impl Client {
-            pub fn get_raw_transaction(&self, txid: bitcoin::Txid) -> Result<GetRawTransaction> {
-                self.call("getrawtransaction", &[into_json(&txid)?, false.into()])
+            pub $($async)? fn get_raw_transaction(
+                &self,
+                txid: bitcoin::Txid,
+            ) -> Result<GetRawTransaction> {
+                $crate::maybe_await!(
+                    $($async)? self.call("getrawtransaction", &[into_json(&txid)?, false.into()])
+                )
             }
 
-            pub fn get_raw_transaction_verbose(
+            pub $($async)? fn get_raw_transaction_verbose(
                 &self,
                 txid: Txid,
             ) -> Result<GetRawTransactionVerbose> {
-                self.call("getrawtransaction", &[into_json(&txid)?, true.into()])
+                $crate::maybe_await!(
+                    $($async)? self.call("getrawtransaction", &[into_json(&txid)?, true.into()])
+                )
+            }
+        }
+    };
+}
+
+/// Implements a helper that waits for a transaction to be confirmed.
+///
+/// Requires `Client` to be in scope and implement `get_transaction()`,
+/// `get_raw_transaction_verbose()` and `get_block_header_verbose()`.
+#[macro_export]
+macro_rules! impl_client_v17__wait_for_confirmations {
+    () => {
+        impl Client {
+            /// Waits until `txid` has at least `confirmations` confirmations in the best chain.
+            ///
+            /// The confirming block is re-checked on each poll so that a block being reorged out
+            /// is not reported. Works for wallet transactions and, if `txindex` is enabled, any
+            /// other transaction. A `confirmations` of zero is treated as one. Only the transaction
+            /// not being found is retried, any other error (e.g. a failed connection) is returned.
+            ///
+            /// # Returns
+            ///
+            /// The hash and height of the block that includes the transaction.
+            pub fn wait_for_confirmations(
+                &self,
+                txid: Txid,
+                confirmations: u32,
+                timeout: std::time::Duration,
+            ) -> Result<(BlockHash, u32)> {
+                const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(250);
+
+                let start = std::time::Instant::now();
+                let confirmations = i64::from(confirmations.max(1));
+                loop {
+                    if let Some(hash) = self.confirming_block_hash(txid)? {
+                        // A header that is not in the best chain has -1 confirmations.
+                        let header = self.get_block_header_verbose(&hash)?;
+                        if header.confirmations >= confirmations {
+                            let height = u32::try_from(header.height)
+                                .map_err(|_| Error::UnexpectedStructure)?;
+                            return Ok((hash, height));
+                        }
+                    }
+                    if start.elapsed() >= timeout {
+                        return Err(Error::Timeout);
+                    }
+                    std::thread::sleep(POLL_INTERVAL);
+                }
+            }
+
+            /// Returns the hash of the block that includes `txid`, if it is in a block.
+            fn confirming_block_hash(&self, txid: Txid) -> Result<Option<BlockHash>> {
+                use $crate::client_sync::RpcErrorCode;
+
+                let block_hash = match self.get_transaction(txid) {
+                    Ok(tx) => tx.block_hash,
+                    // Not a wallet transaction (or no wallet loaded).
+                    Err(e)
+                        if e.is_invalid_address_or_key()
+                            || e.is_wallet_not_loaded()
+                            || e.is_method_not_found()
+                            || e.is_rpc_code(RpcErrorCode::WalletNotSpecified) =>
+                        match self.get_raw_transaction_verbose(txid) {
+                            Ok(tx) => tx.block_hash,
+                            // Unknown transaction (e.g. not seen yet), keep waiting.
+                            Err(e) if e.is_invalid_address_or_key() => None,
+                            Err(e) => return Err(e),
+                        },
+                    Err(e) => return Err(e),
+                };
+                Ok(block_hash.map(|hash| hash.parse::<BlockHash>()).transpose()?)
             }
         }
     };
@@ -175,14 +263,207 @@ macro_rules! impl_client_v17__get_raw_transaction {
 /// Implements Bitcoin Core JSON-RPC API method `sendrawtransaction`.
 #[macro_export]
 macro_rules! impl_client_v17__send_raw_transaction {
-    () => {
+    ($($async:ident)?) => {
         impl Client {
-            pub fn send_raw_transaction(
+            pub $($async)? fn send_raw_transaction(
                 &self,
                 tx: &bitcoin::Transaction,
             ) -> Result<SendRawTransaction> {
                 let hex = bitcoin::consensus::encode::serialize_hex(tx);
-                self.call("sendrawtransaction", &[hex.into()])
+                $crate::maybe_await!($($async)? self.call("sendrawtransaction", &[hex.into()]))
+            }
+        }
+    };
+}
+
+/// Implements a checked broadcast using the JSON-RPC API methods `testmempoolaccept` and
+/// `sendrawtransaction`.
+///
+/// Requires `Client` to be in scope and implement `test_mempool_accept()` and
+/// `send_raw_transaction()`.
+#[macro_export]
+macro_rules! impl_client_v17__broadcast_checked {
+    () => {
+        impl Client {
+            /// Broadcasts `tx` after checking it would be accepted to the mempool.
+            ///
+            /// If the node would reject the transaction a categorised rejection is returned.
+            pub fn broadcast_checked(
+                &self,
+                tx: &bitcoin::Transaction,
+            ) -> std::result::Result<Txid, $crate::client_sync::broadcast::BroadcastError> {
+                use $crate::client_sync::broadcast::{BroadcastError, Rejection};
+
+                let json = self.test_mempool_accept(std::slice::from_ref(tx))?;
+                let result = json.0.into_iter().next().ok_or(Error::UnexpectedStructure)?;
+                if !result.allowed {
+                    let reason = result.reject_reason.unwrap_or_default();
+                    return Err(BroadcastError::Rejected(Rejection::from_reason(reason)));
+                }
+
+                let json = self.send_raw_transaction(tx)?;
+                Ok(json.txid().map_err(Error::from)?)
+            }
+        }
+    };
+}
+
+/// Implements fee bumping by CPFP using the JSON-RPC API methods `walletprocesspsbt` and
+/// `finalizepsbt`.
+///
+/// Requires `Client` to be in scope and implement `submit_cpfp()`, see
+/// `impl_client_v17__submit_cpfp`.
+#[macro_export]
+macro_rules! impl_client_v17__cpfp {
+    () => {
+        impl Client {
+            /// Bumps the fee of `parent` by spending its output `vout` back to the wallet,
+            /// such that the parent and child together pay `package_fee_rate`.
+            ///
+            /// The output must be spendable by the wallet, the parent does not need to be in the
+            /// mempool yet (e.g. if its fee is below the mempool minimum). Parent and child are
+            /// submitted as a package if the node supports `submitpackage`, otherwise they are
+            /// sent one after the other.
+            pub fn cpfp(
+                &self,
+                parent: &bitcoin::Transaction,
+                vout: u32,
+                package_fee_rate: bitcoin::FeeRate,
+            ) -> std::result::Result<
+                $crate::client_sync::cpfp::CpfpPackage,
+                $crate::client_sync::cpfp::CpfpError,
+            > {
+                use $crate::client_sync::cpfp::{self, CpfpPackage};
+
+                let parent_fee = self.cpfp_parent_fee(parent)?;
+                let parent_vsize = parent.vsize() as u64;
+                let script_pubkey = self.new_address()?.script_pubkey();
+
+                // The signature size is only known once signed, re-sign until the fee is enough.
+                let mut fee = bitcoin::Amount::ZERO;
+                let child = loop {
+                    let psbt = cpfp::unsigned_child(parent, vout, script_pubkey.clone(), fee)?;
+                    let child = self.cpfp_sign(&psbt)?;
+                    let required = cpfp::child_fee(
+                        parent_fee,
+                        parent_vsize,
+                        child.vsize() as u64,
+                        package_fee_rate,
+                    );
+                    if fee >= required {
+                        break child;
+                    }
+                    fee = required;
+                };
+
+                let submission = self.submit_cpfp(parent, &child)?;
+                let package_fee_rate =
+                    cpfp::package_fee_rate(parent_fee + fee, parent_vsize + child.vsize() as u64);
+                Ok(CpfpPackage {
+                    parent_txid: parent.compute_txid(),
+                    child,
+                    child_fee: fee,
+                    package_fee_rate,
+                    submission,
+                })
+            }
+
+            /// Returns the fee paid by `parent`.
+            fn cpfp_parent_fee(
+                &self,
+                parent: &bitcoin::Transaction,
+            ) -> std::result::Result<Amount, $crate::client_sync::cpfp::CpfpError> {
+                use $crate::client_sync::cpfp::CpfpError;
+
+                match self.get_mempool_entry(parent.compute_txid()) {
+                    Ok(json) =>
+                        return Ok(Amount::from_btc(json.0.fees.base)
+                            .map_err(|_| Error::UnexpectedStructure)?),
+                    // Not in the mempool, use the value of the outputs it spends.
+                    Err(Error::JsonRpc(jsonrpc::Error::Rpc(_))) => {}
+                    Err(e) => return Err(e.into()),
+                }
+
+                let mut input_value = Amount::ZERO;
+                for txin in &parent.input {
+                    let outpoint = txin.previous_output;
+                    let json: Option<GetTxOut> = self.call(
+                        "gettxout",
+                        &[into_json(outpoint.txid)?, into_json(outpoint.vout)?],
+                    )?;
+                    let json = json.ok_or(CpfpError::MissingPrevout(outpoint))?;
+                    input_value +=
+                        Amount::from_btc(json.value).map_err(|_| Error::UnexpectedStructure)?;
+                }
+                let output_value = parent.output.iter().map(|txout| txout.value).sum();
+                Ok(input_value.checked_sub(output_value).ok_or(Error::UnexpectedStructure)?)
+            }
+
+            /// Signs and finalizes a CPFP child using the wallet.
+            fn cpfp_sign(
+                &self,
+                psbt: &bitcoin::Psbt,
+            ) -> std::result::Result<bitcoin::Transaction, $crate::client_sync::cpfp::CpfpError>
+            {
+                use $crate::client_sync::cpfp::CpfpError;
+
+                let json = self.wallet_process_psbt(psbt)?;
+                if !json.complete {
+                    return Err(CpfpError::Incomplete);
+                }
+                let json: FinalizePsbt =
+                    self.call("finalizepsbt", &[json.psbt.into(), true.into()])?;
+                match json.hex {
+                    Some(hex) if json.complete => Ok($crate::client_sync::decode_hex(&hex)?),
+                    _ => Err(CpfpError::Incomplete),
+                }
+            }
+
+            /// Sends `parent` then `child` using `sendrawtransaction`.
+            ///
+            /// A transaction that is already known to the node is not an error.
+            fn submit_cpfp_sequential(
+                &self,
+                parent: &bitcoin::Transaction,
+                child: &bitcoin::Transaction,
+            ) -> std::result::Result<(), $crate::client_sync::cpfp::CpfpError> {
+                use $crate::client_sync::broadcast::{RejectCategory, Rejection};
+                use $crate::client_sync::cpfp::CpfpError;
+
+                for tx in [parent, child] {
+                    match self.send_raw_transaction(tx) {
+                        Ok(_) => {}
+                        Err(Error::JsonRpc(jsonrpc::Error::Rpc(e))) => {
+                            let rejection = Rejection::from_reason(e.message);
+                            if rejection.category != RejectCategory::AlreadyKnown {
+                                let txid = tx.compute_txid();
+                                return Err(CpfpError::Rejected { txid, rejection });
+                            }
+                        }
+                        Err(e) => return Err(e.into()),
+                    }
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+/// Implements submitting a CPFP package for versions without `submitpackage`.
+#[macro_export]
+macro_rules! impl_client_v17__submit_cpfp {
+    () => {
+        impl Client {
+            fn submit_cpfp(
+                &self,
+                parent: &bitcoin::Transaction,
+                child: &bitcoin::Transaction,
+            ) -> std::result::Result<
+                $crate::client_sync::cpfp::Submission,
+                $crate::client_sync::cpfp::CpfpError,
+            > {
+                self.submit_cpfp_sequential(parent, child)?;
+                Ok($crate::client_sync::cpfp::Submission::Sequential)
             }
         }
     };
@@ -225,9 +506,9 @@ macro_rules! impl_client_v17__sign_raw_transaction_with_key {
 /// Implements Bitcoin Core JSON-RPC API method `testmempoolaccept`.
 #[macro_export]
 macro_rules! impl_client_v17__test_mempool_accept {
-    () => {
+    ($($async:ident)?) => {
         impl Client {
-            pub fn test_mempool_accept(
+            pub $($async)? fn test_mempool_accept(
                 &self,
                 txs: &[bitcoin::Transaction],
             ) -> Result<TestMempoolAccept> {
@@ -235,7 +516,9 @@ macro_rules! impl_client_v17__test_mempool_accept {
                     .iter()
                     .map(|tx| bitcoin::consensus::encode::serialize_hex(tx))
                     .collect::<Vec<String>>();
-                self.call("testmempoolaccept", &[into_json(encoded)?])
+                $crate::maybe_await!(
+                    $($async)? self.call("testmempoolaccept", &[into_json(encoded)?])
+                )
             }
         }
     };
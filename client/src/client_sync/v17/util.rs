@@ -28,10 +28,10 @@ macro_rules! impl_client_v17__create_multisig {
 /// Implements Bitcoin Core JSON-RPC API method `estimatesmartfee`.
 #[macro_export]
 macro_rules! impl_client_v17__estimate_smart_fee {
-    () => {
+    ($($async:ident)?) => {
         impl Client {
-            pub fn estimate_smart_fee(&self, blocks: u32) -> Result<EstimateSmartFee> {
-                self.call("estimatesmartfee", &[blocks.into()])
+            pub $($async)? fn estimate_smart_fee(&self, blocks: u32) -> Result<EstimateSmartFee> {
+                $crate::maybe_await!($($async)? self.call("estimatesmartfee", &[blocks.into()]))
             }
         }
     };
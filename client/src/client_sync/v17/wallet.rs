@@ -87,6 +87,40 @@ macro_rules! impl_client_v17__bump_fee {
     };
 }
 
+/// Implements a fee bumping helper using the JSON-RPC API method `bumpfee`.
+///
+/// Requires `Client` to be in scope and implement `get_transaction()`.
+#[macro_export]
+macro_rules! impl_client_v17__replace_by_fee {
+    () => {
+        impl Client {
+            /// Replaces an unconfirmed wallet transaction with one paying `fee_rate`.
+            ///
+            /// Checks the transaction is still unconfirmed and signals replaceability before
+            /// calling `bumpfee`.
+            pub fn replace_by_fee(
+                &self,
+                txid: Txid,
+                fee_rate: bitcoin::FeeRate,
+            ) -> std::result::Result<
+                $crate::client_sync::rbf::Replacement,
+                $crate::client_sync::rbf::ReplaceByFeeError,
+            > {
+                use $crate::client_sync::rbf;
+
+                let tx = self.get_transaction(txid)?;
+                rbf::check_replaceable(tx.confirmations, tx.bip125_replaceable)?;
+
+                let fee_rate = $crate::client_sync::fee_rate_btc_per_kvb(fee_rate);
+                let options = serde_json::json!({ "feeRate": fee_rate });
+                let json: BumpFee = self.call("bumpfee", &[into_json(txid)?, options])?;
+                let txid = json.txid.parse::<Txid>().map_err(Error::from)?;
+                Ok(rbf::Replacement::Txid(txid))
+            }
+        }
+    };
+}
+
 /// Implements Bitcoin Core JSON-RPC API method `createwallet`.
 #[macro_export]
 macro_rules! impl_client_v17__create_wallet {
@@ -131,7 +165,7 @@ macro_rules! impl_client_v17__encrypt_wallet {
         impl Client {
             // filename is either absolute or relative to bitcoind.
             pub fn encrypt_wallet(&self, passphrase: &str) -> Result<EncryptWallet> {
-                self.call("encryptwallet", &[into_json(passphrase)?])
+                self.call_with_secrets("encryptwallet", &mut [into_json(passphrase)?])
             }
         }
     };
@@ -164,9 +198,11 @@ macro_rules! impl_client_v17__get_address_info {
 /// Implements Bitcoin Core JSON-RPC API method `getbalance`.
 #[macro_export]
 macro_rules! impl_client_v17__get_balance {
-    () => {
+    ($($async:ident)?) => {
         impl Client {
-            pub fn get_balance(&self) -> Result<GetBalance> { self.call("getbalance", &[]) }
+            pub $($async)? fn get_balance(&self) -> Result<GetBalance> {
+                $crate::maybe_await!($($async)? self.call("getbalance", &[]))
+            }
         }
     };
 }
@@ -174,46 +210,50 @@ macro_rules! impl_client_v17__get_balance {
 /// Implements Bitcoin Core JSON-RPC API method `getnewaddress`.
 #[macro_export]
 macro_rules! impl_client_v17__get_new_address {
-    () => {
+    ($($async:ident)?) => {
         impl Client {
             /// Gets a new address from `bitcoind` and parses it assuming its correct.
-            pub fn new_address(&self) -> Result<bitcoin::Address> {
-                let json = self.get_new_address(None, None)?;
+            pub $($async)? fn new_address(&self) -> Result<bitcoin::Address> {
+                let json = $crate::maybe_await!($($async)? self.get_new_address(None, None))?;
                 let model = json.into_model().unwrap();
                 Ok(model.0.assume_checked())
             }
 
             /// Gets a new address from `bitcoind` and parses it assuming its correct.
-            pub fn new_address_with_type(&self, ty: AddressType) -> Result<bitcoin::Address> {
-                let json = self.get_new_address(None, Some(ty))?;
+            pub $($async)? fn new_address_with_type(
+                &self,
+                ty: AddressType,
+            ) -> Result<bitcoin::Address> {
+                let json = $crate::maybe_await!($($async)? self.get_new_address(None, Some(ty)))?;
                 let model = json.into_model().unwrap();
                 Ok(model.0.assume_checked())
             }
 
             /// Gets a new address with label from `bitcoind` and parses it assuming its correct.
             // FIXME: unchecked network here is ugly and not uniform with other functions.
-            pub fn new_address_with_label(
+            pub $($async)? fn new_address_with_label(
                 &self,
                 label: &str,
             ) -> Result<bitcoin::Address<bitcoin::address::NetworkUnchecked>> {
-                let json = self.get_new_address(Some(label), None)?;
+                let json =
+                    $crate::maybe_await!($($async)? self.get_new_address(Some(label), None))?;
                 let model = json.into_model().unwrap();
                 Ok(model.0)
             }
 
             /// Gets a new address - low level RPC call.
-            pub fn get_new_address(
+            pub $($async)? fn get_new_address(
                 &self,
                 label: Option<&str>,
                 ty: Option<AddressType>,
             ) -> Result<GetNewAddress> {
-                match (label, ty) {
-                    (Some(label), Some(ty)) =>
-                        self.call("getnewaddress", &[into_json(label)?, into_json(ty)?]),
-                    (Some(label), None) => self.call("getnewaddress", &[into_json(label)?]),
-                    (None, Some(ty)) => self.call("getnewaddress", &["".into(), into_json(ty)?]),
-                    (None, None) => self.call("getnewaddress", &[]),
-                }
+                let args = match (label, ty) {
+                    (Some(label), Some(ty)) => vec![into_json(label)?, into_json(ty)?],
+                    (Some(label), None) => vec![into_json(label)?],
+                    (None, Some(ty)) => vec!["".into(), into_json(ty)?],
+                    (None, None) => vec![],
+                };
+                $crate::maybe_await!($($async)? self.call("getnewaddress", &args))
             }
         }
     };
@@ -249,10 +289,10 @@ macro_rules! impl_client_v17__get_received_by_address {
 /// Implements Bitcoin Core JSON-RPC API method `gettransaction`.
 #[macro_export]
 macro_rules! impl_client_v17__get_transaction {
-    () => {
+    ($($async:ident)?) => {
         impl Client {
-            pub fn get_transaction(&self, txid: Txid) -> Result<GetTransaction> {
-                self.call("gettransaction", &[into_json(txid)?])
+            pub $($async)? fn get_transaction(&self, txid: Txid) -> Result<GetTransaction> {
+                $crate::maybe_await!($($async)? self.call("gettransaction", &[into_json(txid)?]))
             }
         }
     };
@@ -273,10 +313,10 @@ macro_rules! impl_client_v17__get_unconfirmed_balance {
 /// Implements Bitcoin Core JSON-RPC API method `getwalletinfo`.
 #[macro_export]
 macro_rules! impl_client_v17__get_wallet_info {
-    () => {
+    ($($async:ident)?) => {
         impl Client {
-            pub fn get_wallet_info(&self) -> Result<GetWalletInfo> {
-                self.call("getwalletinfo", &[])
+            pub $($async)? fn get_wallet_info(&self) -> Result<GetWalletInfo> {
+                $crate::maybe_await!($($async)? self.call("getwalletinfo", &[]))
             }
         }
     };
@@ -455,6 +495,25 @@ macro_rules! impl_client_v17__list_since_block {
     };
 }
 
+/// Implements a reorg safe wallet sync using the JSON-RPC API method `listsinceblock`.
+#[macro_export]
+macro_rules! impl_client_v17__sync_wallet {
+    () => {
+        impl Client {
+            /// Syncs the wallet transactions since the previous sync tracked by `sync`.
+            ///
+            /// Returns the transactions added, confirmed and removed (due to a reorg) since then.
+            pub fn sync_wallet(
+                &self,
+                sync: &mut $crate::client_sync::wallet_sync::WalletSync,
+            ) -> Result<Vec<$crate::client_sync::wallet_sync::WalletEvent>> {
+                let since = self.call("listsinceblock", &sync.list_since_block_args())?;
+                Ok(sync.apply(since))
+            }
+        }
+    };
+}
+
 /// Implements Bitcoin Core JSON-RPC API method `listtransactions`.
 #[macro_export]
 macro_rules! impl_client_v17__list_transactions {
@@ -467,12 +526,41 @@ macro_rules! impl_client_v17__list_transactions {
     };
 }
 
+/// Implements an iterator over the wallet transactions using the JSON-RPC API method
+/// `listtransactions` with `count` and `skip`.
+#[macro_export]
+macro_rules! impl_client_v17__list_transactions_paged {
+    () => {
+        impl Client {
+            /// Returns an iterator over the wallet transactions, most recent first.
+            ///
+            /// Fetches `page_size` transactions at a time, see `pages::Pages`.
+            pub fn list_transactions_paged(
+                &self,
+                page_size: usize,
+            ) -> $crate::client_sync::pages::Pages<'_, TransactionItem> {
+                $crate::client_sync::pages::Pages::new(page_size, move |count, skip| {
+                    let label = "*"; // All transactions.
+                    let json: ListTransactions =
+                        self.call("listtransactions", &[label.into(), count.into(), skip.into()])?;
+                    // Core returns the page oldest first.
+                    let mut page = json.0;
+                    page.reverse();
+                    Ok(page)
+                })
+            }
+        }
+    };
+}
+
 /// Implements Bitcoin Core JSON-RPC API method `listunspent`.
 #[macro_export]
 macro_rules! impl_client_v17__list_unspent {
-    () => {
+    ($($async:ident)?) => {
         impl Client {
-            pub fn list_unspent(&self) -> Result<ListUnspent> { self.call("listunspent", &[]) }
+            pub $($async)? fn list_unspent(&self) -> Result<ListUnspent> {
+                $crate::maybe_await!($($async)? self.call("listunspent", &[]))
+            }
         }
     };
 }
@@ -560,9 +648,11 @@ macro_rules! impl_client_v17__send_many {
         impl Client {
             pub fn send_many(&self, amounts: BTreeMap<Address, Amount>) -> Result<SendMany> {
                 let dummy = ""; // Must be set to "" for backwards compatibility.
-                let amount_btc: BTreeMap<String, f64> = amounts
+                let amount_btc: BTreeMap<String, serde_json::Value> = amounts
                     .into_iter()
-                    .map(|(addr, amount)| (addr.to_string(), amount.to_btc()))
+                    .map(|(addr, amount)| {
+                        (addr.to_string(), $crate::client_sync::amount_json(amount))
+                    })
                     .collect();
                 self.call("sendmany", &[into_json(dummy)?, into_json(amount_btc)?])
             }
@@ -573,20 +663,20 @@ macro_rules! impl_client_v17__send_many {
 /// Implements Bitcoin Core JSON-RPC API method `sendtoaddress`.
 #[macro_export]
 macro_rules! impl_client_v17__send_to_address {
-    () => {
+    ($($async:ident)?) => {
         impl Client {
             // Send to address - no RBF.
-            pub fn send_to_address(
+            pub $($async)? fn send_to_address(
                 &self,
                 address: &Address<NetworkChecked>,
                 amount: Amount,
             ) -> Result<SendToAddress> {
-                let args = [address.to_string().into(), into_json(amount.to_btc())?];
-                self.call("sendtoaddress", &args)
+                let args = [address.to_string().into(), $crate::client_sync::amount_json(amount)];
+                $crate::maybe_await!($($async)? self.call("sendtoaddress", &args))
             }
 
             // Send to address - with RBF.
-            pub fn send_to_address_rbf(
+            pub $($async)? fn send_to_address_rbf(
                 &self,
                 address: &Address<NetworkChecked>,
                 amount: Amount,
@@ -598,13 +688,13 @@ macro_rules! impl_client_v17__send_to_address {
 
                 let args = [
                     address.to_string().into(),
-                    into_json(amount.to_btc())?,
+                    $crate::client_sync::amount_json(amount),
                     comment.into(),
                     comment_to.into(),
                     subtract_fee_from_amount.into(),
                     replaceable.into(),
                 ];
-                self.call("sendtoaddress", &args)
+                $crate::maybe_await!($($async)? self.call("sendtoaddress", &args))
             }
         }
     };
@@ -632,7 +722,7 @@ macro_rules! impl_client_v17__set_tx_fee {
     () => {
         impl Client {
             pub fn set_tx_fee(&self, fee_rate: bitcoin::FeeRate) -> Result<SetTxFee> {
-                let fee_rate_btc_kvb = fee_rate.to_sat_per_vb_floor() as f64 / 100_000.0;
+                let fee_rate_btc_kvb = $crate::client_sync::fee_rate_btc_per_kvb(fee_rate);
                 self.call("settxfee", &[fee_rate_btc_kvb.into()])
             }
         }
@@ -690,7 +780,9 @@ macro_rules! impl_client_v17__wallet_passphrase {
     () => {
         impl Client {
             pub fn wallet_passphrase(&self, passphrase: &str, timeout: u64) -> Result<()> {
-                match self.call("walletpassphrase", &[passphrase.into(), timeout.into()]) {
+                let mut args = [passphrase.into(), timeout.into()];
+                let res = self.call_with_secrets("walletpassphrase", &mut args);
+                match res {
                     Ok(serde_json::Value::Null) => Ok(()),
                     Ok(res) => Err(Error::Returned(res.to_string())),
                     Err(err) => Err(err.into()),
@@ -710,17 +802,223 @@ macro_rules! impl_client_v17__wallet_create_funded_psbt {
                 inputs: Vec<WalletCreateFundedPsbtInput>,
                 outputs: Vec<BTreeMap<Address, Amount>>,
             ) -> Result<WalletCreateFundedPsbt> {
-                // Convert outputs: Vec<BTreeMap<Address, Amount>> to Vec<BTreeMap<String, f64>>
+                // Convert outputs: Vec<BTreeMap<Address, Amount>> to Vec<BTreeMap<String, BTC>>
                 let outputs_json: Vec<_> = outputs
                     .into_iter()
                     .map(|map| {
                         map.into_iter()
-                            .map(|(addr, amt)| (addr.to_string(), amt.to_btc()))
+                            .map(|(addr, amt)| {
+                                (addr.to_string(), $crate::client_sync::amount_json(amt))
+                            })
                             .collect::<BTreeMap<_, _>>()
                     })
                     .collect();
                 self.call("walletcreatefundedpsbt", &[into_json(inputs)?, into_json(outputs_json)?])
             }
+
+            /// Calls `walletcreatefundedpsbt` with all its arguments.
+            ///
+            /// `bip32_derivs` includes the BIP-32 derivation paths of the wallet keys in the PSBT.
+            pub fn wallet_create_funded_psbt_with_options(
+                &self,
+                inputs: Vec<WalletCreateFundedPsbtInput>,
+                outputs: Vec<BTreeMap<Address, Amount>>,
+                lock_time: bitcoin::absolute::LockTime,
+                options: &WalletCreateFundedPsbtOptions,
+                bip32_derivs: bool,
+            ) -> Result<WalletCreateFundedPsbt> {
+                let outputs_json: Vec<_> = outputs
+                    .into_iter()
+                    .map(|map| {
+                        map.into_iter()
+                            .map(|(addr, amt)| {
+                                (addr.to_string(), $crate::client_sync::amount_json(amt))
+                            })
+                            .collect::<BTreeMap<_, _>>()
+                    })
+                    .collect();
+                self.call(
+                    "walletcreatefundedpsbt",
+                    &[
+                        into_json(inputs)?,
+                        into_json(outputs_json)?,
+                        lock_time.to_consensus_u32().into(),
+                        into_json(options)?,
+                        bip32_derivs.into(),
+                    ],
+                )
+            }
+        }
+    };
+}
+
+/// Implements a coin selection preview using the JSON-RPC API method `walletcreatefundedpsbt`.
+///
+/// Requires `Client` to be in scope and implement `wallet_create_funded_psbt()`.
+#[macro_export]
+macro_rules! impl_client_v17__preview_send {
+    () => {
+        impl Client {
+            /// Runs coin selection for a send without signing or broadcasting anything.
+            ///
+            /// Inputs are not locked, the effective fee rate is not available for this version.
+            pub fn preview_send(
+                &self,
+                inputs: Vec<WalletCreateFundedPsbtInput>,
+                outputs: Vec<BTreeMap<Address, Amount>>,
+            ) -> Result<$crate::client_sync::preview::SendPreview> {
+                let json = self.wallet_create_funded_psbt(inputs, outputs)?;
+                let psbt =
+                    json.psbt.parse::<bitcoin::Psbt>().map_err(|_| Error::UnexpectedStructure)?;
+                let fee = Amount::from_btc(json.fee).map_err(|_| Error::UnexpectedStructure)?;
+                $crate::client_sync::preview::SendPreview::from_funded_psbt(
+                    psbt,
+                    fee,
+                    json.change_position,
+                )
+            }
+        }
+    };
+}
+
+/// Implements the fund, process, finalize and broadcast PSBT workflow.
+///
+/// Requires `Client` to be in scope and implement `wallet_create_funded_psbt()`,
+/// `wallet_process_psbt()`, `combine_psbt()`, `finalize_psbt()` and `send_raw_transaction()`.
+#[macro_export]
+macro_rules! impl_client_v17__psbt_pipeline {
+    () => {
+        use $crate::client_sync::psbt::{
+            Finalized, Funded, Processed, PsbtPipeline, PsbtPipelineError, PsbtStage,
+        };
+
+        impl Client {
+            /// Starts a PSBT workflow by funding a PSBT with `walletcreatefundedpsbt`.
+            ///
+            /// Use the returned pipeline to process, finalize and broadcast the transaction.
+            pub fn psbt_pipeline(
+                &self,
+                inputs: Vec<WalletCreateFundedPsbtInput>,
+                outputs: Vec<BTreeMap<Address, Amount>>,
+            ) -> std::result::Result<PsbtPipeline<'_, Client, Funded>, PsbtPipelineError> {
+                let stage = PsbtStage::Fund;
+                let client_err = |error| PsbtPipelineError::Client { stage, error };
+
+                let json = self.wallet_create_funded_psbt(inputs, outputs).map_err(client_err)?;
+                let psbt = json
+                    .psbt
+                    .parse::<bitcoin::Psbt>()
+                    .map_err(|error| PsbtPipelineError::InvalidPsbt { stage, error })?;
+                let fee = bitcoin::SignedAmount::from_btc(json.fee)
+                    .map_err(|_| client_err(Error::UnexpectedStructure))?;
+                let change_position = u32::try_from(json.change_position).ok();
+
+                Ok(PsbtPipeline::new(self, Funded { psbt, fee: Some(fee), change_position }))
+            }
+
+            /// Starts a PSBT workflow from an already funded PSBT, e.g. one created externally.
+            pub fn psbt_pipeline_from_psbt(
+                &self,
+                psbt: bitcoin::Psbt,
+            ) -> PsbtPipeline<'_, Client, Funded> {
+                let funded = Funded { psbt, fee: None, change_position: None };
+                PsbtPipeline::new(self, funded)
+            }
+        }
+
+        impl<'a> PsbtPipeline<'a, Client, Funded> {
+            /// Updates and signs the PSBT with the wallet using `walletprocesspsbt`.
+            pub fn process(
+                self,
+            ) -> std::result::Result<PsbtPipeline<'a, Client, Processed>, PsbtPipelineError> {
+                let stage = PsbtStage::Process;
+                let json = self
+                    .client()
+                    .wallet_process_psbt(&self.state().psbt)
+                    .map_err(|error| PsbtPipelineError::Client { stage, error })?;
+                let psbt = json
+                    .psbt
+                    .parse::<bitcoin::Psbt>()
+                    .map_err(|error| PsbtPipelineError::InvalidPsbt { stage, error })?;
+
+                Ok(PsbtPipeline::new(self.client(), Processed { psbt, complete: json.complete }))
+            }
+
+            /// Collects signatures from several parties and combines them using `combinepsbt`.
+            ///
+            /// Each of `signers` processes the PSBT with `walletprocesspsbt`, the results are
+            /// combined with `psbts` (e.g. PSBTs signed by external parties). The pipeline's own
+            /// client is only used to sign if it is also included in `signers`.
+            pub fn process_multi_party(
+                self,
+                signers: &[&Client],
+                psbts: Vec<bitcoin::Psbt>,
+            ) -> std::result::Result<PsbtPipeline<'a, Client, Processed>, PsbtPipelineError> {
+                let stage = PsbtStage::Process;
+                let mut all = psbts;
+                for signer in signers {
+                    let json = signer
+                        .wallet_process_psbt(&self.state().psbt)
+                        .map_err(|error| PsbtPipelineError::Client { stage, error })?;
+                    let psbt = json
+                        .psbt
+                        .parse::<bitcoin::Psbt>()
+                        .map_err(|error| PsbtPipelineError::InvalidPsbt { stage, error })?;
+                    all.push(psbt);
+                }
+                if all.is_empty() {
+                    all.push(self.state().psbt.clone());
+                }
+
+                let stage = PsbtStage::Combine;
+                let client_err = |error| PsbtPipelineError::Client { stage, error };
+                let json = self.client().combine_psbt(&all).map_err(client_err)?;
+                let psbt = json
+                    .0
+                    .parse::<bitcoin::Psbt>()
+                    .map_err(|error| PsbtPipelineError::InvalidPsbt { stage, error })?;
+                // Combining only merges the PSBTs, check if they now have a complete set of signatures.
+                let complete = self.client().finalize_psbt(&psbt).map_err(client_err)?.complete;
+
+                Ok(PsbtPipeline::new(self.client(), Processed { psbt, complete }))
+            }
+        }
+
+        impl<'a> PsbtPipeline<'a, Client, Processed> {
+            /// Finalizes the PSBT and extracts the transaction using `finalizepsbt`.
+            ///
+            /// Errors with `PsbtPipelineError::Incomplete` if the PSBT is missing signatures.
+            pub fn finalize(
+                self,
+            ) -> std::result::Result<PsbtPipeline<'a, Client, Finalized>, PsbtPipelineError> {
+                let stage = PsbtStage::Finalize;
+                let client_err = |error| PsbtPipelineError::Client { stage, error };
+
+                let psbt = self.state().psbt.to_string();
+                let json: FinalizePsbt = self
+                    .client()
+                    .call("finalizepsbt", &[psbt.into(), true.into()])
+                    .map_err(client_err)?;
+                match json.hex {
+                    Some(hex) if json.complete => {
+                        let tx = $crate::client_sync::decode_hex(&hex).map_err(client_err)?;
+                        Ok(PsbtPipeline::new(self.client(), Finalized { tx }))
+                    }
+                    _ => Err(PsbtPipelineError::Incomplete(Box::new(self.into_state().psbt))),
+                }
+            }
+        }
+
+        impl<'a> PsbtPipeline<'a, Client, Finalized> {
+            /// Broadcasts the transaction using `sendrawtransaction`.
+            pub fn broadcast(self) -> std::result::Result<bitcoin::Txid, PsbtPipelineError> {
+                let stage = PsbtStage::Broadcast;
+                let client_err = |error| PsbtPipelineError::Client { stage, error };
+
+                let json =
+                    self.client().send_raw_transaction(&self.state().tx).map_err(client_err)?;
+                json.txid().map_err(|e| client_err(Error::from(e)))
+            }
         }
     };
 }
@@ -751,9 +1049,9 @@ macro_rules! impl_client_v17__wallet_passphrase_change {
                 old_passphrase: &str,
                 new_passphrase: &str,
             ) -> Result<()> {
-                match self
-                    .call("walletpassphrasechange", &[old_passphrase.into(), new_passphrase.into()])
-                {
+                let mut args = [old_passphrase.into(), new_passphrase.into()];
+                let res = self.call_with_secrets("walletpassphrasechange", &mut args);
+                match res {
                     Ok(serde_json::Value::Null) => Ok(()),
                     Ok(res) => Err(Error::Returned(res.to_string())),
                     Err(err) => Err(err.into()),
@@ -775,6 +1073,24 @@ macro_rules! impl_client_v17__wallet_process_psbt {
                 let psbt = format!("{}", psbt);
                 self.call("walletprocesspsbt", &[psbt.into()])
             }
+
+            /// Calls `walletprocesspsbt` with all its arguments.
+            ///
+            /// If `sign` is false the PSBT is only updated with the wallet's information. Note that
+            /// `SighashType::Default` requires Core v22 or later.
+            pub fn wallet_process_psbt_with_options(
+                &self,
+                psbt: &bitcoin::Psbt,
+                sign: bool,
+                sighash_type: SighashType,
+                bip32_derivs: bool,
+            ) -> Result<WalletProcessPsbt> {
+                let psbt = format!("{}", psbt);
+                self.call(
+                    "walletprocesspsbt",
+                    &[psbt.into(), sign.into(), into_json(sighash_type)?, bip32_derivs.into()],
+                )
+            }
         }
     };
 }
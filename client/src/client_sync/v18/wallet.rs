@@ -43,3 +43,41 @@ macro_rules! impl_client_v18__list_wallet_dir {
         }
     };
 }
+
+/// Implements a coin selection preview using the JSON-RPC API methods `walletcreatefundedpsbt`
+/// and `analyzepsbt`.
+///
+/// Requires `Client` to be in scope and implement `wallet_create_funded_psbt()` and
+/// `analyze_psbt()`.
+#[macro_export]
+macro_rules! impl_client_v18__preview_send {
+    () => {
+        impl Client {
+            /// Runs coin selection for a send without signing or broadcasting anything.
+            ///
+            /// Inputs are not locked, `analyzepsbt` is used to estimate the effective fee rate.
+            pub fn preview_send(
+                &self,
+                inputs: Vec<WalletCreateFundedPsbtInput>,
+                outputs: Vec<BTreeMap<Address, Amount>>,
+            ) -> Result<$crate::client_sync::preview::SendPreview> {
+                let json = self.wallet_create_funded_psbt(inputs, outputs)?;
+                let psbt =
+                    json.psbt.parse::<bitcoin::Psbt>().map_err(|_| Error::UnexpectedStructure)?;
+                let fee = Amount::from_btc(json.fee).map_err(|_| Error::UnexpectedStructure)?;
+
+                let analysis = self.analyze_psbt(&psbt)?;
+                let mut preview = $crate::client_sync::preview::SendPreview::from_funded_psbt(
+                    psbt,
+                    fee,
+                    json.change_position,
+                )?;
+                preview.effective_fee_rate = analysis
+                    .estimated_vsize
+                    .filter(|vsize| *vsize > 0)
+                    .map(|vsize| fee / bitcoin::Weight::from_vb_unchecked(u64::from(vsize)));
+                Ok(preview)
+            }
+        }
+    };
+}
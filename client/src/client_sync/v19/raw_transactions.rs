@@ -0,0 +1,36 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Macros for implementing JSON-RPC methods on a client.
+//!
+//! Specifically this is methods found under the `== Rawtransactions ==` section of the
+//! API docs of Bitcoin Core `v0.19`.
+//!
+//! All macros require `Client` to be in scope.
+//!
+//! See or use the `define_jsonrpc_bitreq_client!` macro to define a `Client`.
+
+/// Implements Bitcoin Core JSON-RPC API method `testmempoolaccept` with the `maxfeerate` argument.
+#[macro_export]
+macro_rules! impl_client_v19__test_mempool_accept_with_max_fee_rate {
+    () => {
+        impl Client {
+            /// Tests if `txs` would be accepted to the mempool, they are rejected if their fee rate
+            /// is higher than `max_fee_rate`.
+            ///
+            /// `FeeRate::ZERO` accepts any fee rate. From Core v22 `txs` may be a package,
+            /// topologically sorted, e.g. a parent and a child paying for it.
+            pub fn test_mempool_accept_with_max_fee_rate(
+                &self,
+                txs: &[bitcoin::Transaction],
+                max_fee_rate: bitcoin::FeeRate,
+            ) -> Result<TestMempoolAccept> {
+                let encoded = txs
+                    .iter()
+                    .map(|tx| bitcoin::consensus::encode::serialize_hex(tx))
+                    .collect::<Vec<String>>();
+                let max_fee_rate = $crate::client_sync::fee_rate_btc_per_kvb(max_fee_rate);
+                self.call("testmempoolaccept", &[into_json(encoded)?, max_fee_rate.into()])
+            }
+        }
+    };
+}
@@ -19,23 +19,30 @@ use crate::types::v20::*;
 #[rustfmt::skip]                // Keep public re-exports separate.
 pub use crate::client_sync::{
     v17::{
-        AddressType, AddNodeCommand, ImportMultiRequest, ImportMultiScriptPubKey, ImportMultiTimestamp, Input, Output, SetBanCommand, TemplateRequest, TemplateRules,
-        WalletCreateFundedPsbtInput,
+        AddressType, AddNodeCommand, FundRawTransactionOptions, ImportMultiRequest, ImportMultiScriptPubKey, ImportMultiTimestamp, Input, InputWeight, Output, ScanObject, SetBanCommand, TemplateRequest, TemplateRules,
+        WalletCreateFundedPsbtInput, WalletCreateFundedPsbtOptions, SighashType,
     },
 };
 
 crate::define_jsonrpc_bitreq_client!("v20");
 crate::impl_client_check_expected_server_version!({ [200200] });
+crate::impl_client_deprecated_methods! {
+    "getunconfirmedbalance" => "use `getbalances`",
+}
 
 // == Blockchain ==
 crate::impl_client_v17__get_best_block_hash!();
 crate::impl_client_v17__get_block!();
 crate::impl_client_v17__get_blockchain_info!();
+crate::impl_client_v17__is_synced!();
 crate::impl_client_v17__get_block_count!();
 crate::impl_client_v19__get_block_filter!();
 crate::impl_client_v17__get_block_hash!();
 crate::impl_client_v17__get_block_header!();
+crate::impl_client_v17__block_headers_from!();
+crate::impl_client_v17__into_chain_notifier!();
 crate::impl_client_v17__get_block_stats!();
+crate::impl_client_v17__block_stats_range!();
 crate::impl_client_v17__get_chain_tips!();
 crate::impl_client_v17__get_chain_tx_stats!();
 crate::impl_client_v17__get_difficulty!();
@@ -78,6 +85,7 @@ crate::impl_client_v20__mock_scheduler!();
 
 // == Mining ==
 crate::impl_client_v17__get_block_template!();
+crate::impl_client_v17__block_templates!();
 crate::impl_client_v17__get_mining_info!();
 crate::impl_client_v17__get_network_hashes_per_second!();
 crate::impl_client_v17__prioritise_transaction!();
@@ -112,11 +120,16 @@ crate::impl_client_v17__decode_script!();
 crate::impl_client_v17__finalize_psbt!();
 crate::impl_client_v17__fund_raw_transaction!();
 crate::impl_client_v17__get_raw_transaction!();
+crate::impl_client_v17__wait_for_confirmations!();
 crate::impl_client_v18__join_psbts!();
 crate::impl_client_v17__send_raw_transaction!();
+crate::impl_client_v17__broadcast_checked!();
+crate::impl_client_v17__cpfp!();
+crate::impl_client_v17__submit_cpfp!();
 crate::impl_client_v17__sign_raw_transaction!();
 crate::impl_client_v17__sign_raw_transaction_with_key!();
 crate::impl_client_v17__test_mempool_accept!();
+crate::impl_client_v19__test_mempool_accept_with_max_fee_rate!();
 crate::impl_client_v18__utxo_update_psbt!();
 
 // == Util ==
@@ -134,6 +147,7 @@ crate::impl_client_v17__abort_rescan!();
 crate::impl_client_v17__add_multisig_address!();
 crate::impl_client_v17__backup_wallet!();
 crate::impl_client_v17__bump_fee!();
+crate::impl_client_v17__replace_by_fee!();
 crate::impl_client_v17__create_wallet!();
 crate::impl_client_v17__dump_priv_key!();
 crate::impl_client_v17__dump_wallet!();
@@ -162,7 +176,9 @@ crate::impl_client_v18__list_received_by_label!();
 crate::impl_client_v17__list_lock_unspent!();
 crate::impl_client_v17__list_received_by_address!();
 crate::impl_client_v17__list_since_block!();
+crate::impl_client_v17__sync_wallet!();
 crate::impl_client_v17__list_transactions!();
+crate::impl_client_v17__list_transactions_paged!();
 crate::impl_client_v17__list_unspent!();
 crate::impl_client_v18__list_wallet_dir!();
 crate::impl_client_v17__list_wallets!();
@@ -179,6 +195,8 @@ crate::impl_client_v17__sign_message!();
 crate::impl_client_v17__sign_raw_transaction_with_wallet!();
 crate::impl_client_v17__unload_wallet!();
 crate::impl_client_v17__wallet_create_funded_psbt!();
+crate::impl_client_v18__preview_send!();
+crate::impl_client_v17__psbt_pipeline!();
 crate::impl_client_v17__wallet_lock!();
 crate::impl_client_v17__wallet_passphrase!();
 crate::impl_client_v17__wallet_passphrase_change!();
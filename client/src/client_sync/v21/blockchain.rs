@@ -12,19 +12,73 @@
 /// Implements Bitcoin Core JSON-RPC API method `getrawmempool`.
 #[macro_export]
 macro_rules! impl_client_v21__get_raw_mempool {
-    () => {
+    ($($async:ident)?) => {
         impl Client {
-            pub fn get_raw_mempool(&self) -> Result<GetRawMempool> {
+            pub $($async)? fn get_raw_mempool(&self) -> Result<GetRawMempool> {
                 // Equivalent to self.call("getrawmempool", &[into_json(false)?])
-                self.call("getrawmempool", &[])
+                $crate::maybe_await!($($async)? self.call("getrawmempool", &[]))
+            }
+
+            pub $($async)? fn get_raw_mempool_verbose(&self) -> Result<GetRawMempoolVerbose> {
+                $crate::maybe_await!($($async)? self.call("getrawmempool", &[into_json(true)?]))
             }
 
-            pub fn get_raw_mempool_verbose(&self) -> Result<GetRawMempoolVerbose> {
-                self.call("getrawmempool", &[into_json(true)?])
+            pub $($async)? fn get_raw_mempool_sequence(&self) -> Result<GetRawMempoolSequence> {
+                $crate::maybe_await!(
+                    $($async)? self.call("getrawmempool", &[into_json(false)?, into_json(true)?])
+                )
+            }
+        }
+    };
+}
+
+/// Implements a polling based mempool watcher using `getrawmempool` and batches of
+/// `getrawtransaction`.
+#[macro_export]
+macro_rules! impl_client_v21__into_mempool_watcher {
+    () => {
+        impl Client {
+            /// Consumes the client and spawns a [`MempoolWatcher`] that polls the node for mempool
+            /// changes every `poll_interval`.
+            ///
+            /// Transactions are reported as added and removed relative to the mempool at the time
+            /// this function is called.
+            ///
+            /// [`MempoolWatcher`]: $crate::client_sync::notifier::MempoolWatcher
+            pub fn into_mempool_watcher(
+                self,
+                poll_interval: std::time::Duration,
+            ) -> Result<$crate::client_sync::notifier::MempoolWatcher> {
+                use $crate::client_sync::notifier::{MempoolTracker, MempoolWatcher};
+
+                let mut tracker = MempoolTracker::new();
+                self.update_mempool_tracker(&mut tracker)?;
+
+                Ok(MempoolWatcher::spawn(poll_interval, move || {
+                    self.update_mempool_tracker(&mut tracker)
+                }))
             }
 
-            pub fn get_raw_mempool_sequence(&self) -> Result<GetRawMempoolSequence> {
-                self.call("getrawmempool", &[into_json(false)?, into_json(true)?])
+            /// Updates `tracker` with the current mempool.
+            fn update_mempool_tracker(
+                &self,
+                tracker: &mut $crate::client_sync::notifier::MempoolTracker,
+            ) -> Result<Vec<$crate::client_sync::notifier::MempoolEvent>> {
+                let mempool = self.get_raw_mempool_sequence()?.into_model()?;
+                tracker.update(mempool.txids, mempool.mempool_sequence, |txids| {
+                    let mut batch = self.batch();
+                    let calls = txids
+                        .iter()
+                        .map(|txid| {
+                            batch.call_hex::<bitcoin::Transaction>(
+                                "getrawtransaction",
+                                &[into_json(txid)?],
+                            )
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                    let mut results = batch.send()?;
+                    Ok(calls.into_iter().map(|call| results.take(call)).collect())
+                })
             }
         }
     };
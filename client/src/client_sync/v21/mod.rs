@@ -23,23 +23,30 @@ use crate::types::v21::*;
 #[rustfmt::skip]                // Keep public re-exports separate.
 pub use crate::client_sync::{
     v17::{
-        AddNodeCommand, AddressType, ImportMultiRequest, ImportMultiScriptPubKey, ImportMultiTimestamp, Input, Output, SetBanCommand, TemplateRequest,
-        TemplateRules, WalletCreateFundedPsbtInput,
+        AddNodeCommand, AddressType, FundRawTransactionOptions, ImportMultiRequest, ImportMultiScriptPubKey, ImportMultiTimestamp, Input, InputWeight, Output, ScanObject, SetBanCommand, TemplateRequest,
+        TemplateRules, WalletCreateFundedPsbtInput, WalletCreateFundedPsbtOptions, SighashType,
     },
 };
 
 crate::define_jsonrpc_bitreq_client!("v21");
 crate::impl_client_check_expected_server_version!({ [210200] });
+crate::impl_client_deprecated_methods! {
+    "getunconfirmedbalance" => "use `getbalances`",
+}
 
 // == Blockchain ==
 crate::impl_client_v17__get_best_block_hash!();
 crate::impl_client_v17__get_block!();
 crate::impl_client_v17__get_blockchain_info!();
+crate::impl_client_v17__is_synced!();
 crate::impl_client_v17__get_block_count!();
 crate::impl_client_v19__get_block_filter!();
 crate::impl_client_v17__get_block_hash!();
 crate::impl_client_v17__get_block_header!();
+crate::impl_client_v17__block_headers_from!();
+crate::impl_client_v17__into_chain_notifier!();
 crate::impl_client_v17__get_block_stats!();
+crate::impl_client_v17__block_stats_range!();
 crate::impl_client_v17__get_chain_tips!();
 crate::impl_client_v17__get_chain_tx_stats!();
 crate::impl_client_v17__get_difficulty!();
@@ -48,6 +55,7 @@ crate::impl_client_v17__get_mempool_descendants!();
 crate::impl_client_v17__get_mempool_entry!();
 crate::impl_client_v17__get_mempool_info!();
 crate::impl_client_v21__get_raw_mempool!();
+crate::impl_client_v21__into_mempool_watcher!();
 crate::impl_client_v17__get_tx_out!();
 crate::impl_client_v17__get_tx_out_proof!();
 crate::impl_client_v17__get_tx_out_set_info!();
@@ -84,6 +92,7 @@ crate::impl_client_v20__mock_scheduler!();
 
 // == Mining ==
 crate::impl_client_v17__get_block_template!();
+crate::impl_client_v17__block_templates!();
 crate::impl_client_v17__get_mining_info!();
 crate::impl_client_v17__get_network_hashes_per_second!();
 crate::impl_client_v17__prioritise_transaction!();
@@ -118,11 +127,16 @@ crate::impl_client_v17__decode_script!();
 crate::impl_client_v17__finalize_psbt!();
 crate::impl_client_v17__fund_raw_transaction!();
 crate::impl_client_v17__get_raw_transaction!();
+crate::impl_client_v17__wait_for_confirmations!();
 crate::impl_client_v18__join_psbts!();
 crate::impl_client_v17__send_raw_transaction!();
+crate::impl_client_v17__broadcast_checked!();
+crate::impl_client_v17__cpfp!();
+crate::impl_client_v17__submit_cpfp!();
 crate::impl_client_v17__sign_raw_transaction!();
 crate::impl_client_v17__sign_raw_transaction_with_key!();
 crate::impl_client_v17__test_mempool_accept!();
+crate::impl_client_v19__test_mempool_accept_with_max_fee_rate!();
 crate::impl_client_v18__utxo_update_psbt!();
 
 // == Util ==
@@ -158,6 +172,7 @@ crate::impl_client_v17__get_unconfirmed_balance!();
 crate::impl_client_v17__get_wallet_info!();
 crate::impl_client_v17__import_address!();
 crate::impl_client_v21__import_descriptors!();
+crate::impl_client_v21__import_descriptor_and_rescan!();
 crate::impl_client_v17__import_multi!();
 crate::impl_client_v17__import_privkey!();
 crate::impl_client_v17__import_pruned_funds!();
@@ -170,13 +185,16 @@ crate::impl_client_v18__list_received_by_label!();
 crate::impl_client_v17__list_lock_unspent!();
 crate::impl_client_v17__list_received_by_address!();
 crate::impl_client_v17__list_since_block!();
+crate::impl_client_v17__sync_wallet!();
 crate::impl_client_v17__list_transactions!();
+crate::impl_client_v17__list_transactions_paged!();
 crate::impl_client_v17__list_unspent!();
 crate::impl_client_v18__list_wallet_dir!();
 crate::impl_client_v17__list_wallets!();
 crate::impl_client_v17__load_wallet!();
 crate::impl_client_v17__lock_unspent!();
 crate::impl_client_v21__psbt_bump_fee!();
+crate::impl_client_v21__replace_by_fee!();
 crate::impl_client_v17__remove_pruned_funds!();
 crate::impl_client_v17__rescan_blockchain!();
 crate::impl_client_v21__send!();
@@ -191,6 +209,8 @@ crate::impl_client_v17__sign_raw_transaction_with_wallet!();
 crate::impl_client_v21__unload_wallet!();
 crate::impl_client_v21__upgrade_wallet!();
 crate::impl_client_v17__wallet_create_funded_psbt!();
+crate::impl_client_v18__preview_send!();
+crate::impl_client_v17__psbt_pipeline!();
 crate::impl_client_v17__wallet_lock!();
 crate::impl_client_v17__wallet_passphrase!();
 crate::impl_client_v17__wallet_passphrase_change!();
@@ -215,3 +235,57 @@ impl ImportDescriptorsRequest {
         ImportDescriptorsRequest { descriptor: descriptor.into(), timestamp: timestamp.into() }
     }
 }
+
+/// The `options` argument of method `send`.
+///
+/// Options left as `None` are not sent, so the wallet defaults are used.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct SendOptions {
+    /// Select more inputs than `inputs` if needed, by default only if `inputs` is not set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub add_inputs: Option<bool>,
+    /// Add the transaction to the wallet and broadcast it, true by default.
+    ///
+    /// If false the signed transaction is returned in `hex` instead.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub add_to_wallet: Option<bool>,
+    /// The address to send the change to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub change_address: Option<Address<NetworkChecked>>,
+    /// The index of the change output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub change_position: Option<u32>,
+    /// The confirmation target in blocks, used to estimate the fee rate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conf_target: Option<u32>,
+    /// The fee rate to pay, instead of estimating it.
+    #[serde(
+        serialize_with = "crate::client_sync::serialize_fee_rate_sat_per_vb",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub fee_rate: Option<bitcoin::FeeRate>,
+    /// Also select inputs which are watch only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_watching: Option<bool>,
+    /// The inputs to spend.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inputs: Option<Vec<Input>>,
+    /// The lock time of the transaction.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locktime: Option<u32>,
+    /// Lock the selected unspent outputs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lock_unspents: Option<bool>,
+    /// Always return a PSBT, implies `add_to_wallet` is false.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub psbt: Option<bool>,
+    /// The indexes of the outputs the fee is deducted from, equally.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subtract_fee_from_outputs: Option<Vec<u32>>,
+    /// Signal BIP-125 replaceability.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replaceable: Option<bool>,
+    /// The weights of the inputs not from the wallet, from Core v23.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_weights: Option<Vec<InputWeight>>,
+}
@@ -74,6 +74,69 @@ macro_rules! impl_client_v21__import_descriptors {
     };
 }
 
+/// Implements a helper that imports a descriptor and waits for the rescan to finish.
+///
+/// Requires `Client` to be in scope and implement `import_descriptors()`, `get_wallet_info()`
+/// and `list_since_block()`.
+#[macro_export]
+macro_rules! impl_client_v21__import_descriptor_and_rescan {
+    () => {
+        impl Client {
+            /// Imports `descriptor`, rescanning the chain from `start_time` (UNIX epoch time).
+            ///
+            /// If the `importdescriptors` request times out while the node is rescanning the
+            /// rescan is waited on by polling `getwalletinfo`, for at most the long poll timeout
+            /// (see `Builder::long_poll_timeout`), after which `Error::Timeout` is returned.
+            ///
+            /// # Returns
+            ///
+            /// The `listsinceblock` entries of the wallet transactions found by the rescan.
+            pub fn import_descriptor_and_rescan(
+                &self,
+                descriptor: &str,
+                start_time: u64,
+            ) -> Result<Vec<TransactionItem>> {
+                const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(250);
+
+                let known = self
+                    .list_since_block()?
+                    .transactions
+                    .into_iter()
+                    .map(|tx| tx.txid)
+                    .collect::<std::collections::BTreeSet<_>>();
+
+                let request = ImportDescriptorsRequest::new(descriptor, start_time);
+                match self.import_descriptors(&[request]) {
+                    Ok(json) =>
+                        for result in json.0 {
+                            if !result.success {
+                                let error = result.error.map(|e| e.to_string()).unwrap_or_default();
+                                return Err(Error::Returned(error));
+                            }
+                        },
+                    // The node carries on rescanning, wait for it below.
+                    Err(Error::JsonRpc(jsonrpc::Error::Transport(_))) => {}
+                    Err(e) => return Err(e),
+                }
+
+                let start = std::time::Instant::now();
+                while !matches!(
+                    self.get_wallet_info()?.scanning,
+                    GetWalletInfoScanning::NotScanning(_)
+                ) {
+                    if start.elapsed() >= self.long_poll.timeout() {
+                        return Err(Error::Timeout);
+                    }
+                    std::thread::sleep(POLL_INTERVAL);
+                }
+
+                let txs = self.list_since_block()?.transactions;
+                Ok(txs.into_iter().filter(|tx| !known.contains(&tx.txid)).collect())
+            }
+        }
+    };
+}
+
 /// Implements Bitcoin Core JSON-RPC API method `psbtbumpfee`.
 #[macro_export]
 macro_rules! impl_client_v21__psbt_bump_fee {
@@ -86,14 +149,82 @@ macro_rules! impl_client_v21__psbt_bump_fee {
     };
 }
 
+/// Implements a fee bumping helper using the JSON-RPC API methods `bumpfee` and `psbtbumpfee`.
+///
+/// Requires `Client` to be in scope and implement `get_transaction()` and `get_wallet_info()`.
+#[macro_export]
+macro_rules! impl_client_v21__replace_by_fee {
+    () => {
+        impl Client {
+            /// Replaces an unconfirmed wallet transaction with one paying `fee_rate`.
+            ///
+            /// Checks the transaction is still unconfirmed and signals replaceability. If the
+            /// wallet can sign the replacement is broadcast using `bumpfee`, otherwise (e.g. for a
+            /// watch-only wallet) an unsigned replacement is created using `psbtbumpfee`.
+            pub fn replace_by_fee(
+                &self,
+                txid: Txid,
+                fee_rate: bitcoin::FeeRate,
+            ) -> std::result::Result<
+                $crate::client_sync::rbf::Replacement,
+                $crate::client_sync::rbf::ReplaceByFeeError,
+            > {
+                use $crate::client_sync::rbf::{self, ReplaceByFeeError, Replacement};
+
+                let tx = self.get_transaction(txid)?;
+                rbf::check_replaceable(tx.confirmations, tx.bip125_replaceable)?;
+
+                let fee_rate = $crate::client_sync::fee_rate_sat_per_vb(fee_rate);
+                let options = serde_json::json!({ "fee_rate": fee_rate });
+                let args = [into_json(txid)?, options];
+                if self.get_wallet_info()?.private_keys_enabled {
+                    let json: BumpFee = self.call("bumpfee", &args)?;
+                    let txid = json.txid.parse::<Txid>().map_err(Error::from)?;
+                    Ok(Replacement::Txid(txid))
+                } else {
+                    let json: PsbtBumpFee = self.call("psbtbumpfee", &args)?;
+                    let psbt = json.psbt.parse().map_err(ReplaceByFeeError::InvalidPsbt)?;
+                    Ok(Replacement::Psbt(psbt))
+                }
+            }
+        }
+    };
+}
+
 /// Implements Bitcoin Core JSON-RPC API method `send`.
 #[macro_export]
 macro_rules! impl_client_v21__send {
     () => {
         impl Client {
-            pub fn send(&self, outputs: &BTreeMap<String, f64>) -> Result<Send> {
+            pub fn send(&self, outputs: &BTreeMap<Address, Amount>) -> Result<Send> {
+                let outputs: BTreeMap<String, serde_json::Value> = outputs
+                    .iter()
+                    .map(|(addr, amount)| {
+                        (addr.to_string(), $crate::client_sync::amount_json(*amount))
+                    })
+                    .collect();
                 self.call("send", &[into_json(outputs)?])
             }
+
+            /// Calls `send` with the coin selection and fee `options`.
+            pub fn send_with_options(
+                &self,
+                outputs: &BTreeMap<Address, Amount>,
+                options: &SendOptions,
+            ) -> Result<Send> {
+                let outputs: BTreeMap<String, serde_json::Value> = outputs
+                    .iter()
+                    .map(|(addr, amount)| {
+                        (addr.to_string(), $crate::client_sync::amount_json(*amount))
+                    })
+                    .collect();
+                // The fee arguments are also accepted as options, null is the same as omitted.
+                let null = serde_json::Value::Null;
+                self.call(
+                    "send",
+                    &[into_json(outputs)?, null.clone(), null.clone(), null, into_json(options)?],
+                )
+            }
         }
     };
 }
@@ -108,9 +239,11 @@ macro_rules! impl_client_v21__send_many_verbose {
                 amounts: BTreeMap<Address, Amount>,
             ) -> Result<SendManyVerbose> {
                 let dummy = ""; // Backwards compatibility dummy.
-                let amount_btc: BTreeMap<String, f64> = amounts
+                let amount_btc: BTreeMap<String, serde_json::Value> = amounts
                     .into_iter()
-                    .map(|(addr, amount)| (addr.to_string(), amount.to_btc()))
+                    .map(|(addr, amount)| {
+                        (addr.to_string(), $crate::client_sync::amount_json(amount))
+                    })
                     .collect();
                 let minconf = 1u64;
                 let comment = "";
@@ -20,25 +20,32 @@ use crate::types::v23::*;
 #[rustfmt::skip]                // Keep public re-exports separate.
 pub use crate::client_sync::{
     v17::{
-        AddNodeCommand, ImportMultiRequest, ImportMultiScriptPubKey, ImportMultiTimestamp, Input, Output, SetBanCommand, TemplateRequest, TemplateRules,
-        WalletCreateFundedPsbtInput,
+        AddNodeCommand, FundRawTransactionOptions, ImportMultiRequest, ImportMultiScriptPubKey, ImportMultiTimestamp, Input, InputWeight, Output, ScanObject, SetBanCommand, TemplateRequest, TemplateRules,
+        WalletCreateFundedPsbtInput, WalletCreateFundedPsbtOptions, SighashType,
     },
-    v21::ImportDescriptorsRequest,
+    v21::{ImportDescriptorsRequest, SendOptions},
 };
 
 crate::define_jsonrpc_bitreq_client!("v23");
 crate::impl_client_check_expected_server_version!({ [230200] });
+crate::impl_client_deprecated_methods! {
+    "getunconfirmedbalance" => "use `getbalances`",
+}
 
 // == Blockchain ==
 crate::impl_client_v17__get_best_block_hash!();
 crate::impl_client_v17__get_block!();
 crate::impl_client_v17__get_blockchain_info!();
+crate::impl_client_v17__is_synced!();
 crate::impl_client_v17__get_block_count!();
 crate::impl_client_v19__get_block_filter!();
 crate::impl_client_v23__get_block_from_peer!();
 crate::impl_client_v17__get_block_hash!();
 crate::impl_client_v17__get_block_header!();
+crate::impl_client_v17__block_headers_from!();
+crate::impl_client_v17__into_chain_notifier!();
 crate::impl_client_v17__get_block_stats!();
+crate::impl_client_v17__block_stats_range!();
 crate::impl_client_v17__get_chain_tips!();
 crate::impl_client_v17__get_chain_tx_stats!();
 crate::impl_client_v23__get_deployment_info!();
@@ -48,6 +55,7 @@ crate::impl_client_v17__get_mempool_descendants!();
 crate::impl_client_v17__get_mempool_entry!();
 crate::impl_client_v17__get_mempool_info!();
 crate::impl_client_v21__get_raw_mempool!();
+crate::impl_client_v21__into_mempool_watcher!();
 crate::impl_client_v17__get_tx_out!();
 crate::impl_client_v17__get_tx_out_proof!();
 crate::impl_client_v17__get_tx_out_set_info!();
@@ -85,6 +93,7 @@ crate::impl_client_v20__mock_scheduler!();
 
 // == Mining ==
 crate::impl_client_v17__get_block_template!();
+crate::impl_client_v17__block_templates!();
 crate::impl_client_v17__get_mining_info!();
 crate::impl_client_v17__get_network_hashes_per_second!();
 crate::impl_client_v17__prioritise_transaction!();
@@ -119,11 +128,16 @@ crate::impl_client_v17__decode_script!();
 crate::impl_client_v17__finalize_psbt!();
 crate::impl_client_v17__fund_raw_transaction!();
 crate::impl_client_v17__get_raw_transaction!();
+crate::impl_client_v17__wait_for_confirmations!();
 crate::impl_client_v18__join_psbts!();
 crate::impl_client_v17__send_raw_transaction!();
+crate::impl_client_v17__broadcast_checked!();
+crate::impl_client_v17__cpfp!();
+crate::impl_client_v17__submit_cpfp!();
 crate::impl_client_v17__sign_raw_transaction!();
 crate::impl_client_v17__sign_raw_transaction_with_key!();
 crate::impl_client_v17__test_mempool_accept!();
+crate::impl_client_v19__test_mempool_accept_with_max_fee_rate!();
 crate::impl_client_v18__utxo_update_psbt!();
 
 // == Signer ==
@@ -162,6 +176,7 @@ crate::impl_client_v17__get_unconfirmed_balance!();
 crate::impl_client_v17__get_wallet_info!();
 crate::impl_client_v17__import_address!();
 crate::impl_client_v21__import_descriptors!();
+crate::impl_client_v21__import_descriptor_and_rescan!();
 crate::impl_client_v17__import_multi!();
 crate::impl_client_v17__import_privkey!();
 crate::impl_client_v17__import_pruned_funds!();
@@ -175,7 +190,9 @@ crate::impl_client_v17__list_labels!();
 crate::impl_client_v17__list_lock_unspent!();
 crate::impl_client_v17__list_received_by_address!();
 crate::impl_client_v17__list_since_block!();
+crate::impl_client_v17__sync_wallet!();
 crate::impl_client_v17__list_transactions!();
+crate::impl_client_v17__list_transactions_paged!();
 crate::impl_client_v17__list_unspent!();
 crate::impl_client_v18__list_wallet_dir!();
 crate::impl_client_v17__list_wallets!();
@@ -183,6 +200,7 @@ crate::impl_client_v22__load_wallet!();
 crate::impl_client_v17__lock_unspent!();
 crate::impl_client_v23__new_keypool!();
 crate::impl_client_v21__psbt_bump_fee!();
+crate::impl_client_v21__replace_by_fee!();
 crate::impl_client_v17__remove_pruned_funds!();
 crate::impl_client_v17__rescan_blockchain!();
 crate::impl_client_v23__restore_wallet!();
@@ -198,6 +216,8 @@ crate::impl_client_v17__sign_raw_transaction_with_wallet!();
 crate::impl_client_v21__unload_wallet!();
 crate::impl_client_v21__upgrade_wallet!();
 crate::impl_client_v17__wallet_create_funded_psbt!();
+crate::impl_client_v18__preview_send!();
+crate::impl_client_v17__psbt_pipeline!();
 crate::impl_client_v22__wallet_display_address!();
 crate::impl_client_v17__wallet_lock!();
 crate::impl_client_v17__wallet_passphrase!();
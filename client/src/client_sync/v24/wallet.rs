@@ -29,6 +29,26 @@ macro_rules! impl_client_v24__send_all {
             pub fn send_all(&self, recipients: &[Address]) -> Result<SendAll> {
                 self.call("sendall", &[into_json(recipients)?])
             }
+
+            /// Calls `sendall` with the coin selection and fee `options`.
+            pub fn send_all_with_options(
+                &self,
+                recipients: &[Address],
+                options: &SendAllOptions,
+            ) -> Result<SendAll> {
+                // The fee arguments are also accepted as options, null is the same as omitted.
+                let null = serde_json::Value::Null;
+                self.call(
+                    "sendall",
+                    &[
+                        into_json(recipients)?,
+                        null.clone(),
+                        null.clone(),
+                        null,
+                        into_json(options)?,
+                    ],
+                )
+            }
         }
     };
 }
@@ -24,6 +24,26 @@ macro_rules! impl_client_v25__scan_blocks {
                 self.call("scanblocks", &[into_json("start")?, into_json(scan_objects)?])
             }
 
+            /// Starts a scan of blocks for the typed `scan_objects`, with the given `options`.
+            pub fn scan_blocks_start_with_options(
+                &self,
+                scan_objects: &[ScanObject],
+                options: &ScanBlocksOptions,
+            ) -> Result<ScanBlocksStart> {
+                let mut params = vec![
+                    into_json("start")?,
+                    into_json(scan_objects)?,
+                    into_json(options.start_height)?,
+                    into_json(options.stop_height)?,
+                    into_json("basic")?,
+                ];
+                // Only sent if set, so the call works with nodes that don't know the argument.
+                if let Some(filter) = options.filter_false_positives {
+                    params.push(serde_json::json!({ "filter_false_positives": filter }));
+                }
+                self.call("scanblocks", &params)
+            }
+
             /// Checks the status of an ongoing `scanblocks` scan.
             pub fn scan_blocks_status(&self) -> Result<Option<ScanBlocksStatus>> {
                 self.call("scanblocks", &[into_json("status")?])
@@ -27,3 +27,55 @@ macro_rules! impl_client_v26__submit_package {
         }
     };
 }
+
+/// Implements submitting a CPFP package using the JSON-RPC API method `submitpackage`.
+///
+/// Falls back to sending the transactions one after the other if the node refuses the call
+/// (e.g. `submitpackage` is regtest only before Core v28).
+#[macro_export]
+macro_rules! impl_client_v26__submit_cpfp {
+    () => {
+        impl Client {
+            fn submit_cpfp(
+                &self,
+                parent: &bitcoin::Transaction,
+                child: &bitcoin::Transaction,
+            ) -> std::result::Result<
+                $crate::client_sync::cpfp::Submission,
+                $crate::client_sync::cpfp::CpfpError,
+            > {
+                use $crate::client_sync::broadcast::Rejection;
+                use $crate::client_sync::cpfp::{CpfpError, Submission};
+
+                let package = [parent, child]
+                    .iter()
+                    .map(|tx| bitcoin::consensus::encode::serialize_hex(*tx))
+                    .collect::<Vec<_>>();
+                let json: SubmitPackage = match self.call("submitpackage", &[package.into()]) {
+                    Ok(json) => json,
+                    Err(Error::JsonRpc(jsonrpc::Error::Rpc(_))) => {
+                        self.submit_cpfp_sequential(parent, child)?;
+                        return Ok(Submission::Sequential);
+                    }
+                    Err(e) => return Err(e.into()),
+                };
+
+                for result in json.tx_results.into_values() {
+                    if let Some(reason) = result.error {
+                        let txid = result.txid.parse::<Txid>().map_err(Error::from)?;
+                        return Err(CpfpError::Rejected {
+                            txid,
+                            rejection: Rejection::from_reason(reason),
+                        });
+                    }
+                }
+                if json.package_msg != "success" {
+                    let txid = child.compute_txid();
+                    let rejection = Rejection::from_reason(json.package_msg);
+                    return Err(CpfpError::Rejected { txid, rejection });
+                }
+                Ok(Submission::Package)
+            }
+        }
+    };
+}
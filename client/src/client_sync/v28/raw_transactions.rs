@@ -25,8 +25,8 @@ macro_rules! impl_client_v28__submit_package {
                     .map(|tx| bitcoin::consensus::encode::serialize_hex(tx))
                     .collect::<Vec<_>>();
                 let max_fee_rate_btc_kvb =
-                    max_fee_rate.map(|r| r.to_sat_per_vb_floor() as f64 / 100_000.0);
-                let max_burn_amount_btc = max_burn_amount.map(|a| a.to_btc());
+                    max_fee_rate.map($crate::client_sync::fee_rate_btc_per_kvb);
+                let max_burn_amount_btc = max_burn_amount.map($crate::client_sync::amount_json);
                 self.call(
                     "submitpackage",
                     &[package_txs.into(), max_fee_rate_btc_kvb.into(), max_burn_amount_btc.into()],
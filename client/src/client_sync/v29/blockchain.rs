@@ -17,6 +17,14 @@ macro_rules! impl_client_v29__dump_tx_out_set {
             pub fn dump_tx_out_set(&self, path: &str, snapshot_type: &str) -> Result<DumpTxOutSet> {
                 self.call("dumptxoutset", &[path.into(), snapshot_type.into()])
             }
+
+            /// Calls `dumptxoutset` to dump the UTXO set as of the block at `height`.
+            ///
+            /// The node rolls back its chain to `height` for the dump, then replays it.
+            pub fn dump_tx_out_set_rollback(&self, path: &str, height: u32) -> Result<DumpTxOutSet> {
+                let options = serde_json::json!({ "rollback": height });
+                self.call("dumptxoutset", &[path.into(), "rollback".into(), options])
+            }
         }
     };
 }
@@ -40,32 +48,64 @@ macro_rules! impl_client_v29__get_descriptor_activity {
 /// Implements Bitcoin Core JSON-RPC API method `getblock`.
 #[macro_export]
 macro_rules! impl_client_v29__get_block {
-    () => {
+    ($($async:ident)?) => {
         impl Client {
             /// Gets a block by blockhash. Kept for compatibility; uses verbose set to 0.
-            pub fn get_block(&self, hash: BlockHash) -> Result<Block> {
-                let json = self.get_block_verbose_zero(hash)?;
-                Ok(json.block()?)
+            pub $($async)? fn get_block(&self, hash: BlockHash) -> Result<Block> {
+                $crate::maybe_await!(
+                    $($async)? self.call_hex("getblock", &[into_json(hash)?, 0.into()])
+                )
             }
 
             /// Gets a block by blockhash with verbose set to 0.
-            pub fn get_block_verbose_zero(&self, hash: BlockHash) -> Result<GetBlockVerboseZero> {
-                self.call("getblock", &[into_json(hash)?, 0.into()])
+            pub $($async)? fn get_block_verbose_zero(
+                &self,
+                hash: BlockHash,
+            ) -> Result<GetBlockVerboseZero> {
+                $crate::maybe_await!(
+                    $($async)? self.call("getblock", &[into_json(hash)?, 0.into()])
+                )
             }
 
             /// Gets a block by blockhash with verbose set to 1.
-            pub fn get_block_verbose_one(&self, hash: BlockHash) -> Result<GetBlockVerboseOne> {
-                self.call("getblock", &[into_json(hash)?, 1.into()])
+            pub $($async)? fn get_block_verbose_one(
+                &self,
+                hash: BlockHash,
+            ) -> Result<GetBlockVerboseOne> {
+                $crate::maybe_await!(
+                    $($async)? self.call("getblock", &[into_json(hash)?, 1.into()])
+                )
             }
 
             /// Gets a block by blockhash with verbose set to 2.
-            pub fn get_block_verbose_two(&self, hash: BlockHash) -> Result<GetBlockVerboseTwo> {
-                self.call("getblock", &[into_json(hash)?, 2.into()])
+            pub $($async)? fn get_block_verbose_two(
+                &self,
+                hash: BlockHash,
+            ) -> Result<GetBlockVerboseTwo> {
+                $crate::maybe_await!(
+                    $($async)? self.call("getblock", &[into_json(hash)?, 2.into()])
+                )
+            }
+
+            /// Gets a block by blockhash with verbose set to 2, without deserializing the
+            /// transactions.
+            pub $($async)? fn get_block_verbose_two_lazy(
+                &self,
+                hash: BlockHash,
+            ) -> Result<GetBlockVerboseTwoLazy> {
+                $crate::maybe_await!(
+                    $($async)? self.call("getblock", &[into_json(hash)?, 2.into()])
+                )
             }
 
             /// Gets a block by blockhash with verbose set to 3.
-            pub fn get_block_verbose_three(&self, hash: BlockHash) -> Result<GetBlockVerboseThree> {
-                self.call("getblock", &[into_json(hash)?, 3.into()])
+            pub $($async)? fn get_block_verbose_three(
+                &self,
+                hash: BlockHash,
+            ) -> Result<GetBlockVerboseThree> {
+                $crate::maybe_await!(
+                    $($async)? self.call("getblock", &[into_json(hash)?, 3.into()])
+                )
             }
         }
     };
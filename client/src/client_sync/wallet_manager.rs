@@ -0,0 +1,248 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Routing of calls to the wallets of a node using one client per wallet.
+//!
+//! See `WalletManager` and `Client::wallet_manager`.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::client_sync::dyn_client::DynClient;
+use crate::client_sync::{Error, Result, RpcErrorCode};
+
+/// Creates a client for a URL.
+type Connect<C> = Box<dyn Fn(&str) -> Result<C> + Send + Sync>;
+
+/// Manages the wallets of a node and a client for each of them.
+///
+/// Wallets are loaded the first time they are used and their clients are cached. Calls made with
+/// `with_wallet` or `call` are retried once if the wallet is not loaded, e.g. after the node
+/// restarted, after loading the wallet. Calls that fail with a transport error are only retried,
+/// after recreating the clients (re-reading the cookie file, which changes when the node
+/// restarts), if enabled with `retries`.
+pub struct WalletManager<C> {
+    /// The URL of the node, without a wallet.
+    url: String,
+    /// The maximum number of retries of a call that failed with a transport error.
+    retries: usize,
+    /// Creates a client for a URL.
+    connect: Connect<C>,
+    /// The node client and the cached wallet clients.
+    state: Mutex<State<C>>,
+}
+
+/// The clients of a [`WalletManager`].
+struct State<C> {
+    node: Arc<C>,
+    wallets: BTreeMap<String, Arc<C>>,
+}
+
+impl<C: DynClient> WalletManager<C> {
+    /// Creates a manager for the node at `url`, `connect` creates a client for a node or wallet URL.
+    pub fn new<F>(url: &str, connect: F) -> Result<Self>
+    where
+        F: Fn(&str) -> Result<C> + Send + Sync + 'static,
+    {
+        let url = url.trim_end_matches('/').to_owned();
+        let node = Arc::new(connect(&url)?);
+        let state = Mutex::new(State { node, wallets: BTreeMap::new() });
+        Ok(Self { url, retries: 0, connect: Box::new(connect), state })
+    }
+
+    /// Sets the number of times a call that failed with a transport error is retried, after
+    /// recreating the clients, defaults to 0.
+    ///
+    /// A call that timed out may have been executed by the node, so retrying a call that is not
+    /// idempotent e.g., `sendtoaddress` can execute it twice.
+    pub fn retries(mut self, retries: usize) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Returns the client for calls that are not for a wallet.
+    pub fn node(&self) -> Arc<C> { Arc::clone(&self.lock().node) }
+
+    /// Returns the names of the loaded wallets using `listwallets`.
+    pub fn list_wallets(&self) -> Result<Vec<String>> {
+        Ok(serde_json::from_value(self.node().call_json("listwallets", &[])?)?)
+    }
+
+    /// Returns the names of the wallets in the wallet directory using `listwalletdir`.
+    ///
+    /// Requires Core v18 or later.
+    pub fn list_wallet_dir(&self) -> Result<Vec<String>> {
+        #[derive(Deserialize)]
+        struct WalletDir {
+            wallets: Vec<Entry>,
+        }
+        #[derive(Deserialize)]
+        struct Entry {
+            name: String,
+        }
+
+        let dir: WalletDir = serde_json::from_value(self.node().call_json("listwalletdir", &[])?)?;
+        Ok(dir.wallets.into_iter().map(|entry| entry.name).collect())
+    }
+
+    /// Loads `wallet` using `loadwallet`, if it is not loaded already.
+    pub fn load(&self, wallet: &str) -> Result<()> {
+        if self.list_wallets()?.iter().any(|name| name == wallet) {
+            return Ok(());
+        }
+        match self.node().call_json("loadwallet", &[wallet.into()]) {
+            // Someone else loaded the wallet in the meantime.
+            Err(e) if e.is_rpc_code(RpcErrorCode::WalletAlreadyLoaded) => Ok(()),
+            result => result.map(|_| ()),
+        }
+    }
+
+    /// Unloads `wallet` using `unloadwallet` and drops its client.
+    pub fn unload(&self, wallet: &str) -> Result<()> {
+        self.node().call_json("unloadwallet", &[wallet.into()])?;
+        self.lock().wallets.remove(wallet);
+        Ok(())
+    }
+
+    /// Returns the client for `wallet`, loading the wallet if this is the first time it is used.
+    ///
+    /// Calls made directly with the client are not retried, see `with_wallet`.
+    pub fn wallet(&self, wallet: &str) -> Result<Arc<C>> {
+        if let Some(client) = self.lock().wallets.get(wallet) {
+            return Ok(Arc::clone(client));
+        }
+        self.load(wallet)?;
+        let client = Arc::new((self.connect)(&format!("{}/wallet/{}", self.url, wallet))?);
+        Ok(Arc::clone(self.lock().wallets.entry(wallet.to_owned()).or_insert(client)))
+    }
+
+    /// Calls `f` with the client for `wallet`, `f` is called again if the call can be retried.
+    ///
+    /// The call is retried once if the wallet is not loaded, and on a transport error up to the
+    /// number of times set with `retries`.
+    pub fn with_wallet<T, F>(&self, wallet: &str, f: F) -> Result<T>
+    where
+        F: Fn(&C) -> Result<T>,
+    {
+        let mut retry = 0;
+        loop {
+            let client = self.wallet(wallet)?;
+            match f(&client) {
+                Err(e) if e.is_wallet_not_loaded() => {
+                    log::debug!(target: "corepc", "wallet {} not loaded, loading it", wallet);
+                    self.load(wallet)?;
+                    return f(&client);
+                }
+                Err(e @ Error::JsonRpc(jsonrpc::Error::Transport(_))) if retry < self.retries => {
+                    log::debug!(target: "corepc", "reconnecting to the node: {}", e);
+                    retry += 1;
+                    self.reconnect()?;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Calls an RPC `method` with `args` on `wallet`.
+    pub fn call<T: DeserializeOwned>(
+        &self,
+        wallet: &str,
+        method: &str,
+        args: &[serde_json::Value],
+    ) -> Result<T> {
+        self.with_wallet(wallet, |client| {
+            Ok(serde_json::from_value(client.call_json(method, args)?)?)
+        })
+    }
+
+    /// Recreates the node client and drops the wallet clients.
+    fn reconnect(&self) -> Result<()> {
+        let node = Arc::new((self.connect)(&self.url)?);
+        let mut state = self.lock();
+        state.node = node;
+        state.wallets.clear();
+        Ok(())
+    }
+
+    /// Locks the state, the clients are still usable if another thread panicked.
+    fn lock(&self) -> MutexGuard<'_, State<C>> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<C> fmt::Debug for WalletManager<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        f.debug_struct("WalletManager")
+            .field("wallets", &state.wallets.keys().collect::<Vec<_>>())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    /// A client whose `getbalance` calls fail with a transport error `failures` times.
+    struct Flaky {
+        failures: Arc<AtomicUsize>,
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl DynClient for Flaky {
+        fn call_json(&self, method: &str, _: &[serde_json::Value]) -> Result<serde_json::Value> {
+            match method {
+                "listwallets" => Ok(serde_json::json!(["default"])),
+                _ => {
+                    self.calls.fetch_add(1, Ordering::SeqCst);
+                    let fail = self
+                        .failures
+                        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                        .is_ok();
+                    if fail {
+                        Err(jsonrpc::Error::Transport("connection refused".into()).into())
+                    } else {
+                        Ok(serde_json::json!(1.0))
+                    }
+                }
+            }
+        }
+    }
+
+    fn flaky_manager(failures: usize) -> (WalletManager<Flaky>, Arc<AtomicUsize>) {
+        let failures = Arc::new(AtomicUsize::new(failures));
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let manager = WalletManager::new("http://127.0.0.1:18443", move |_| {
+            Ok(Flaky { failures: Arc::clone(&failures), calls: Arc::clone(&counter) })
+        })
+        .unwrap();
+        (manager, calls)
+    }
+
+    #[test]
+    fn transport_errors_are_not_retried_by_default() {
+        let (manager, calls) = flaky_manager(1);
+        let err = manager.call::<f64>("default", "getbalance", &[]).unwrap_err();
+        assert!(matches!(err, Error::JsonRpc(jsonrpc::Error::Transport(_))), "{}", err);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn transport_errors_are_retried_up_to_retries() {
+        let (manager, calls) = flaky_manager(2);
+        let manager = manager.retries(2);
+        assert_eq!(manager.call::<f64>("default", "getbalance", &[]).unwrap(), 1.0);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        let (manager, calls) = flaky_manager(2);
+        let manager = manager.retries(1);
+        assert!(manager.call::<f64>("default", "getbalance", &[]).is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
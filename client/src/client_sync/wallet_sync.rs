@@ -0,0 +1,146 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Reorg safe syncing of wallet transactions using `listsinceblock`.
+//!
+//! See `Client::sync_wallet`.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use bitcoin::{BlockHash, Txid};
+use serde::Deserialize;
+
+/// A change to the transactions of a wallet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WalletEvent {
+    /// A transaction was seen for the first time.
+    Added {
+        /// The transaction id.
+        txid: Txid,
+    },
+    /// A transaction was included in a block of the best chain.
+    Confirmed {
+        /// The transaction id.
+        txid: Txid,
+        /// The hash of the block that includes the transaction.
+        block_hash: BlockHash,
+    },
+    /// A block including a transaction was disconnected from the best chain (i.e. a reorg).
+    Removed {
+        /// The transaction id.
+        txid: Txid,
+        /// The hash of the disconnected block.
+        block_hash: BlockHash,
+    },
+}
+
+/// Tracks the wallet transactions seen by repeated calls to `listsinceblock`.
+///
+/// Each sync asks for the transactions since the `lastblock` returned by the previous one, which
+/// is `target_confirmations - 1` blocks deep. Transactions are tracked until they have at least
+/// `target_confirmations` confirmations, reorgs shallower than that are detected from the
+/// tracked state and deeper ones from the `removed` transactions returned by the node.
+#[derive(Clone, Debug)]
+pub struct WalletSync {
+    /// The `lastblock` returned by the previous sync.
+    last_block: Option<BlockHash>,
+    /// The number of confirmations after which a transaction is no longer tracked.
+    target_confirmations: u32,
+    /// The tracked transactions and the block they are included in, if any.
+    seen: BTreeMap<Txid, Option<BlockHash>>,
+}
+
+impl WalletSync {
+    /// Creates a sync that starts from the genesis block, i.e. reports all wallet transactions.
+    ///
+    /// A `target_confirmations` of zero is treated as one.
+    pub fn new(target_confirmations: u32) -> Self {
+        Self {
+            last_block: None,
+            target_confirmations: target_confirmations.max(1),
+            seen: BTreeMap::new(),
+        }
+    }
+
+    /// Creates a sync that only reports wallet transactions since `block_hash`.
+    pub fn from_block(block_hash: BlockHash, target_confirmations: u32) -> Self {
+        Self { last_block: Some(block_hash), ..Self::new(target_confirmations) }
+    }
+
+    /// Returns the `lastblock` returned by the previous sync.
+    pub fn last_block(&self) -> Option<BlockHash> { self.last_block }
+
+    /// Returns the arguments for the next `listsinceblock` call.
+    pub fn list_since_block_args(&self) -> [serde_json::Value; 3] {
+        let block_hash = match self.last_block {
+            Some(hash) => hash.to_string().into(),
+            None => serde_json::Value::Null,
+        };
+        // Always include watch-only transactions.
+        [block_hash, self.target_confirmations.into(), true.into()]
+    }
+
+    /// Updates the tracked state with the result of a `listsinceblock` call.
+    pub fn apply(&mut self, since: SinceBlock) -> Vec<WalletEvent> {
+        let mut events = vec![];
+
+        // Transactions from blocks disconnected by a reorg deeper than the tracked window.
+        let mut removed = BTreeSet::new();
+        for item in since.removed {
+            if let (Some(txid), Some(block_hash)) = (item.txid, item.block_hash) {
+                if removed.insert(txid) {
+                    events.push(WalletEvent::Removed { txid, block_hash });
+                    self.seen.insert(txid, None);
+                }
+            }
+        }
+
+        let mut reported = BTreeSet::new();
+        for item in since.transactions {
+            let txid = match item.txid {
+                Some(txid) => txid,
+                None => continue,
+            };
+            // There is an entry per output/category, only handle each transaction once.
+            if !reported.insert(txid) {
+                continue;
+            }
+
+            let block_hash = item.block_hash;
+            match self.seen.insert(txid, block_hash) {
+                None => events.push(WalletEvent::Added { txid }),
+                Some(previous) if previous == block_hash => continue,
+                Some(Some(previous)) =>
+                    events.push(WalletEvent::Removed { txid, block_hash: previous }),
+                Some(None) => {}
+            }
+            if let Some(block_hash) = block_hash {
+                events.push(WalletEvent::Confirmed { txid, block_hash });
+            }
+        }
+
+        // Transactions no longer reported have enough confirmations.
+        self.seen.retain(|txid, _| reported.contains(txid));
+        self.last_block = Some(since.last_block);
+        events
+    }
+}
+
+/// The parts of a `listsinceblock` result used by [`WalletSync`].
+///
+/// Deserializes the result of any supported Core version.
+#[derive(Clone, Debug, Deserialize)]
+pub struct SinceBlock {
+    transactions: Vec<SinceBlockItem>,
+    #[serde(default)]
+    removed: Vec<SinceBlockItem>,
+    #[serde(rename = "lastblock")]
+    last_block: BlockHash,
+}
+
+/// A transaction entry of a `listsinceblock` result.
+#[derive(Clone, Debug, Deserialize)]
+struct SinceBlockItem {
+    txid: Option<Txid>,
+    #[serde(rename = "blockhash")]
+    block_hash: Option<BlockHash>,
+}
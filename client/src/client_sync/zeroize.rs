@@ -0,0 +1,117 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Overwriting of secrets with zeros once they are no longer used.
+//!
+//! With the `zeroize` feature the client scrubs the RPC password (see `Password`), the serialized
+//! arguments of each request, the secret arguments passed to `call_with_secrets` (e.g. wallet
+//! passphrases) and the raw result of each response (e.g. dumped private keys). This includes
+//! long poll calls made with `call_long_poll`. Copies made by the HTTP client, by `simd-json`
+//! (with the `simd-json` feature) or by the operating system are not scrubbed.
+//!
+//! See `Zeroizing` to scrub a returned secret.
+//!
+//! ```ignore
+//! let key = Zeroizing::new(client.dump_priv_key(&address)?.0);
+//! ```
+
+pub use ::zeroize::{Zeroize, Zeroizing};
+use serde_json::value::RawValue;
+
+/// Scrubs the serialized arguments of a request.
+pub(crate) fn zeroize_raw(raw: Box<RawValue>) {
+    let mut raw: Box<str> = raw.into();
+    raw.zeroize();
+}
+
+/// Scrubs the raw result of a response.
+pub(crate) fn zeroize_response(mut resp: jsonrpc::Response) {
+    if let Some(raw) = resp.result.take() {
+        zeroize_raw(raw);
+    }
+}
+
+/// Scrubs the string arguments of a request, e.g. a passphrase.
+pub(crate) fn zeroize_args(args: &mut [serde_json::Value]) {
+    for arg in args {
+        if let serde_json::Value::String(ref mut s) = *arg {
+            s.zeroize();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    use super::*;
+    use crate::client_sync::v17::Client;
+    use crate::client_sync::{Auth, Password};
+
+    /// Serves one JSON-RPC call returning `null`, returns the body of the request.
+    fn serve_one(listener: TcpListener) -> thread::JoinHandle<String> {
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().expect("accept");
+            let mut reader = BufReader::new(stream.try_clone().expect("clone"));
+            let mut len = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).expect("read header");
+                if line == "\r\n" {
+                    break;
+                }
+                if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                    len = value.trim().parse().expect("content length");
+                }
+            }
+            let mut body = vec![0; len];
+            reader.read_exact(&mut body).expect("read body");
+            let body = String::from_utf8(body).expect("utf8");
+
+            let req: serde_json::Value = serde_json::from_str(&body).expect("request");
+            let resp = serde_json::json!({ "result": null, "error": null, "id": req["id"] });
+            let resp = resp.to_string();
+            write!(
+                &stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                resp.len(),
+                resp
+            )
+            .expect("write response");
+            body
+        })
+    }
+
+    #[test]
+    fn zeroize_args_clears_strings() {
+        let mut args = [serde_json::json!("passphrase"), serde_json::json!(60)];
+        zeroize_args(&mut args);
+        assert_eq!(args, [serde_json::json!(""), serde_json::json!(60)]);
+    }
+
+    #[test]
+    fn wallet_passphrase_args_are_scrubbed() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+        let url = format!("http://{}", listener.local_addr().expect("local addr"));
+        let server = serve_one(listener);
+
+        let auth = Auth::UserPass("user".to_owned(), Password::new("password"));
+        let client = Client::new_with_auth(&url, auth).expect("client");
+        let mut args = [serde_json::json!("secret passphrase"), serde_json::json!(60)];
+        let res: serde_json::Value =
+            client.call_with_secrets("walletpassphrase", &mut args).expect("walletpassphrase");
+        assert_eq!(res, serde_json::Value::Null);
+
+        // The passphrase was sent, then scrubbed from the arguments.
+        assert!(server.join().expect("server").contains("secret passphrase"));
+        assert_eq!(args, [serde_json::json!(""), serde_json::json!(60)]);
+    }
+
+    #[test]
+    fn password_debug_is_redacted() {
+        let auth = Auth::UserPass("user".to_owned(), Password::new("password"));
+        assert_eq!(format!("{:?}", auth), r#"UserPass("user", "[redacted]")"#);
+        assert_eq!(format!("{:?}", Password::new("password")), "Password([redacted])");
+    }
+}
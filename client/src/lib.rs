@@ -1,6 +1,12 @@
 // SPDX-License-Identifier: CC0-1.0
 
-//! Support for connecting to Bitcoin Core via JSON-RPC.
+//! Support for connecting to Bitcoin Core via JSON-RPC, or via its REST interface.
+
+// Experimental features we need.
+#![cfg_attr(bench, feature(test))]
+
+#[cfg(bench)]
+extern crate test;
 
 /// Re-export the `rust-bitcoin` crate.
 pub extern crate bitcoin;
@@ -8,6 +14,11 @@ pub extern crate bitcoin;
 /// Re-export the `corepc-types` crate.
 pub extern crate types;
 
+#[cfg(feature = "client-async")]
+#[macro_use]
+pub mod client_async;
 #[cfg(feature = "client-sync")]
 #[macro_use]
 pub mod client_sync;
+#[cfg(feature = "rest")]
+pub mod rest;
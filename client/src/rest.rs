@@ -0,0 +1,196 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! A client for the REST interface of Bitcoin Core, enabled on the node with `-rest`.
+//!
+//! The REST interface needs no authentication and serves blocks and transactions in their binary
+//! encoding, downloading many blocks is therefore a lot faster than using JSON-RPC. The interface
+//! is available on the RPC port of the node. See [`Client`].
+
+use std::time::{Duration, Instant};
+use std::{error, fmt};
+
+use bitcoin::block::Header;
+use bitcoin::consensus::encode;
+use bitcoin::{Block, BlockHash, Transaction, Txid};
+use serde::de::DeserializeOwned;
+use types::model::{GetBlockHash, GetBlockHeader, GetBlockVerboseZero, GetRawTransaction};
+
+/// The size of a serialized block header.
+const HEADER_SIZE: usize = 80;
+
+/// The maximum number of headers returned by `/rest/headers`.
+pub const MAX_HEADERS: u32 = 2000;
+
+/// Default timeout of a request.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Result type returned by the REST client.
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// A blocking client for the REST interface of a node.
+///
+/// ```no_run
+/// # fn main() -> corepc_client::rest::Result<()> {
+/// let rest = corepc_client::rest::Client::new("http://127.0.0.1:8332");
+/// let hash = rest.get_block_hash(0)?.0;
+/// let block = rest.get_block(hash)?.0;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Client {
+    /// The URL of the node, without a trailing slash.
+    url: String,
+    timeout: Duration,
+}
+
+impl Client {
+    /// Creates a client to the REST interface at `url` e.g., `http://127.0.0.1:8332`.
+    pub fn new(url: &str) -> Self {
+        Self { url: url.trim_end_matches('/').to_owned(), timeout: DEFAULT_TIMEOUT }
+    }
+
+    /// Sets the timeout of each request, rounded down to whole seconds.
+    ///
+    /// Defaults to 60 seconds.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Gets the block with `hash` using `GET /rest/block/<hash>.bin`.
+    pub fn get_block(&self, hash: BlockHash) -> Result<GetBlockVerboseZero> {
+        let block: Block = self.get_bin(&format!("block/{}.bin", hash))?;
+        Ok(GetBlockVerboseZero(block))
+    }
+
+    /// Gets the transaction with `txid` using `GET /rest/tx/<txid>.bin`.
+    ///
+    /// Like `getrawtransaction`, transactions not in the mempool are only found if the node runs
+    /// with `-txindex`.
+    pub fn get_raw_transaction(&self, txid: Txid) -> Result<GetRawTransaction> {
+        let tx: Transaction = self.get_bin(&format!("tx/{}.bin", txid))?;
+        Ok(GetRawTransaction(tx))
+    }
+
+    /// Gets up to `count` headers starting at the block with `hash` using `GET /rest/headers`.
+    ///
+    /// Fewer headers are returned if the chain is shorter, the node returns at most
+    /// [`MAX_HEADERS`].
+    pub fn get_block_headers(&self, hash: BlockHash, count: u32) -> Result<Vec<GetBlockHeader>> {
+        // The count in the path is deprecated since v24 but the query parameter that replaces it
+        // is not supported by older versions.
+        let bytes = self.get(&format!("headers/{}/{}.bin", count, hash))?;
+        if bytes.len() % HEADER_SIZE != 0 {
+            return Err(Error::UnexpectedLength(bytes.len()));
+        }
+        bytes
+            .chunks(HEADER_SIZE)
+            .map(|bytes| Ok(GetBlockHeader(encode::deserialize::<Header>(bytes)?)))
+            .collect()
+    }
+
+    /// Gets the hash of the block at `height` in the best chain using
+    /// `GET /rest/blockhashbyheight/<height>.bin`.
+    pub fn get_block_hash(&self, height: u32) -> Result<GetBlockHash> {
+        let hash: BlockHash = self.get_bin(&format!("blockhashbyheight/{}.bin", height))?;
+        Ok(GetBlockHash(hash))
+    }
+
+    /// Gets the state of the chain using `GET /rest/chaininfo.json`.
+    ///
+    /// The JSON is the same as returned by `getblockchaininfo` so `T` is the version specific
+    /// `GetBlockchainInfo` type of the node e.g., `types::v29::GetBlockchainInfo`.
+    pub fn get_blockchain_info<T: DeserializeOwned>(&self) -> Result<T> {
+        let bytes = self.get("chaininfo.json")?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    /// Gets `path` and decodes the body, which must be a single consensus encoded object.
+    fn get_bin<T: encode::Decodable>(&self, path: &str) -> Result<T> {
+        let bytes = self.get(path)?;
+        Ok(encode::deserialize(&bytes)?)
+    }
+
+    /// Gets `path` below `/rest/` and returns the body of the response.
+    fn get(&self, path: &str) -> Result<Vec<u8>> {
+        let url = format!("{}/rest/{}", self.url, path);
+        let start = Instant::now();
+        let resp = bitreq::get(url.as_str()).with_timeout(self.timeout.as_secs()).send();
+        log::debug!(target: "corepc", "rest: GET /rest/{} in {:?}", path, start.elapsed());
+
+        let resp = resp?;
+        if resp.status_code != 200 {
+            // The node explains the error in a plain text body.
+            let message = String::from_utf8_lossy(resp.as_bytes()).trim().to_owned();
+            return Err(Error::Status { status_code: resp.status_code, message });
+        }
+        Ok(resp.into_bytes())
+    }
+}
+
+/// The error type for errors produced by the REST client.
+#[derive(Debug)]
+pub enum Error {
+    /// The HTTP request failed.
+    Http(bitreq::Error),
+    /// The node responded with an error status e.g., 404 if the block is not found.
+    Status {
+        /// The HTTP status code.
+        status_code: i32,
+        /// The error message returned by the node.
+        message: String,
+    },
+    /// The response is not a valid consensus encoding.
+    Decode(encode::Error),
+    /// The response is not valid JSON, or not of the expected type.
+    Json(serde_json::Error),
+    /// The response is not a whole number of headers long.
+    UnexpectedLength(usize),
+}
+
+impl Error {
+    /// Returns true if the requested object was not found.
+    pub fn is_not_found(&self) -> bool { matches!(*self, Error::Status { status_code: 404, .. }) }
+}
+
+impl From<bitreq::Error> for Error {
+    fn from(e: bitreq::Error) -> Self { Self::Http(e) }
+}
+
+impl From<encode::Error> for Error {
+    fn from(e: encode::Error) -> Self { Self::Decode(e) }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self { Self::Json(e) }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use Error::*;
+
+        match *self {
+            Http(ref e) => write!(f, "HTTP error: {}", e),
+            Status { status_code, ref message } =>
+                write!(f, "the node returned status {}: {}", status_code, message),
+            Decode(ref e) => write!(f, "consensus decode error: {}", e),
+            Json(ref e) => write!(f, "JSON error: {}", e),
+            UnexpectedLength(len) =>
+                write!(f, "response of {} bytes is not a whole number of headers", len),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        use Error::*;
+
+        match *self {
+            Http(ref e) => Some(e),
+            Decode(ref e) => Some(e),
+            Json(ref e) => Some(e),
+            Status { .. } | UnexpectedLength(_) => None,
+        }
+    }
+}
@@ -2,4 +2,4 @@
 # shellcheck disable=SC2148
 
 # Crates in this workspace to test.
-CRATES=("bitreq" "client" "jsonrpc" "types")
+CRATES=("bitreq" "cli" "client" "jsonrpc" "types")
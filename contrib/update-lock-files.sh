@@ -7,7 +7,7 @@ set -euo pipefail
 REPO_DIR="$(git rev-parse --show-toplevel)"
 
 # Targets where `--all-features` is used.
-ALL_FEATURE_CRATES=(bitreq client fuzz jsonrpc types verify)
+ALL_FEATURE_CRATES=(bitreq cli client fuzz jsonrpc types verify)
 
 # Targets with conflicting features and only speficic features are used.
 SPECIFIC_FEATURES_CRATES=(integration_test node)
@@ -1,17 +1,149 @@
 //! Provides a macro that implements the tests.
 
-use std::path::PathBuf;
-
 use bitcoin::bip32::{Fingerprint, Xpriv, Xpub};
+use bitcoin::hashes::Hash as _;
 use bitcoin::secp256k1::{Secp256k1, XOnlyPublicKey};
-use bitcoin::Network;
-use node::{Conf, P2P};
-use rand::distributions::Alphanumeric;
-use rand::Rng;
+use bitcoin::{opcodes, Address, Amount, BlockHash, KnownHrp, Network, ScriptBuf};
+#[cfg(not(feature = "v22_and_below"))]
+use node::client::client_sync::Auth;
+use node::{serde_json, Conf, TmpFile, P2P};
+#[cfg(not(feature = "v22_and_below"))]
+use node::{AddressType, Client};
 
 #[rustfmt::skip]    // Keep public re-exports separate.
 pub use node::Node; // Re-export this to make test imports more terse.
 
+/// Defines a test that only runs against the given range of Core versions.
+///
+/// On other versions the test is not compiled, instead an ignored test with the same name is
+/// defined so it shows up in the test output as `ignored, not supported by this Core version`.
+/// Versions are the major version e.g., `17` for v0.17 and `22` for v22.
+///
+/// ```ignore
+/// integration_test::versioned_test! {
+///     22.. => fn wallet__new_feature() { /* Runs against v22 and later. */ }
+/// }
+/// integration_test::versioned_test! {
+///     ..=24 => fn wallet__removed_feature() { /* Runs against v24 and earlier. */ }
+/// }
+/// integration_test::versioned_test! {
+///     22..=24 => fn wallet__changed_feature() { /* Runs against v22 to v24. */ }
+/// }
+/// ```
+#[macro_export]
+macro_rules! versioned_test {
+    ($min:tt .. => $($test:tt)*) => {
+        $crate::versioned_test!(@min $min, 30, [$min ..], $($test)*);
+    };
+    (..= $max:tt => $($test:tt)*) => {
+        $crate::versioned_test!(@min 17, $max, [..= $max], $($test)*);
+    };
+    ($min:tt ..= $max:tt => $($test:tt)*) => {
+        $crate::versioned_test!(@min $min, $max, [$min ..= $max], $($test)*);
+    };
+    (@min 17, $($rest:tt)*) => {
+        $crate::versioned_test!(@max [all()], $($rest)*);
+    };
+    (@min 18, $($rest:tt)*) => {
+        $crate::versioned_test!(@max [not(feature = "v17")], $($rest)*);
+    };
+    (@min 19, $($rest:tt)*) => {
+        $crate::versioned_test!(@max [not(feature = "v18_and_below")], $($rest)*);
+    };
+    (@min 20, $($rest:tt)*) => {
+        $crate::versioned_test!(@max [not(feature = "v19_and_below")], $($rest)*);
+    };
+    (@min 21, $($rest:tt)*) => {
+        $crate::versioned_test!(@max [not(feature = "v20_and_below")], $($rest)*);
+    };
+    (@min 22, $($rest:tt)*) => {
+        $crate::versioned_test!(@max [not(feature = "v21_and_below")], $($rest)*);
+    };
+    (@min 23, $($rest:tt)*) => {
+        $crate::versioned_test!(@max [not(feature = "v22_and_below")], $($rest)*);
+    };
+    (@min 24, $($rest:tt)*) => {
+        $crate::versioned_test!(@max [not(feature = "v23_and_below")], $($rest)*);
+    };
+    (@min 25, $($rest:tt)*) => {
+        $crate::versioned_test!(@max [not(feature = "v24_and_below")], $($rest)*);
+    };
+    (@min 26, $($rest:tt)*) => {
+        $crate::versioned_test!(@max [not(feature = "v25_and_below")], $($rest)*);
+    };
+    (@min 27, $($rest:tt)*) => {
+        $crate::versioned_test!(@max [not(feature = "v26_and_below")], $($rest)*);
+    };
+    (@min 28, $($rest:tt)*) => {
+        $crate::versioned_test!(@max [not(feature = "v27_and_below")], $($rest)*);
+    };
+    (@min 29, $($rest:tt)*) => {
+        $crate::versioned_test!(@max [not(feature = "v28_and_below")], $($rest)*);
+    };
+    (@min 30, $($rest:tt)*) => {
+        $crate::versioned_test!(@max [not(feature = "v29_and_below")], $($rest)*);
+    };
+    (@max [$($min:tt)*], 30, $($rest:tt)*) => {
+        $crate::versioned_test!(@emit [$($min)*] [all()], $($rest)*);
+    };
+    (@max [$($min:tt)*], 29, $($rest:tt)*) => {
+        $crate::versioned_test!(@emit [$($min)*] [feature = "v29_and_below"], $($rest)*);
+    };
+    (@max [$($min:tt)*], 28, $($rest:tt)*) => {
+        $crate::versioned_test!(@emit [$($min)*] [feature = "v28_and_below"], $($rest)*);
+    };
+    (@max [$($min:tt)*], 27, $($rest:tt)*) => {
+        $crate::versioned_test!(@emit [$($min)*] [feature = "v27_and_below"], $($rest)*);
+    };
+    (@max [$($min:tt)*], 26, $($rest:tt)*) => {
+        $crate::versioned_test!(@emit [$($min)*] [feature = "v26_and_below"], $($rest)*);
+    };
+    (@max [$($min:tt)*], 25, $($rest:tt)*) => {
+        $crate::versioned_test!(@emit [$($min)*] [feature = "v25_and_below"], $($rest)*);
+    };
+    (@max [$($min:tt)*], 24, $($rest:tt)*) => {
+        $crate::versioned_test!(@emit [$($min)*] [feature = "v24_and_below"], $($rest)*);
+    };
+    (@max [$($min:tt)*], 23, $($rest:tt)*) => {
+        $crate::versioned_test!(@emit [$($min)*] [feature = "v23_and_below"], $($rest)*);
+    };
+    (@max [$($min:tt)*], 22, $($rest:tt)*) => {
+        $crate::versioned_test!(@emit [$($min)*] [feature = "v22_and_below"], $($rest)*);
+    };
+    (@max [$($min:tt)*], 21, $($rest:tt)*) => {
+        $crate::versioned_test!(@emit [$($min)*] [feature = "v21_and_below"], $($rest)*);
+    };
+    (@max [$($min:tt)*], 20, $($rest:tt)*) => {
+        $crate::versioned_test!(@emit [$($min)*] [feature = "v20_and_below"], $($rest)*);
+    };
+    (@max [$($min:tt)*], 19, $($rest:tt)*) => {
+        $crate::versioned_test!(@emit [$($min)*] [feature = "v19_and_below"], $($rest)*);
+    };
+    (@max [$($min:tt)*], 18, $($rest:tt)*) => {
+        $crate::versioned_test!(@emit [$($min)*] [feature = "v18_and_below"], $($rest)*);
+    };
+    (@max [$($min:tt)*], 17, $($rest:tt)*) => {
+        $crate::versioned_test!(@emit [$($min)*] [feature = "v17"], $($rest)*);
+    };
+    (
+        @emit [$($min:tt)*] [$($max:tt)*], [$($range:tt)*],
+        $(#[$attr:meta])* fn $name:ident() $body:block
+    ) => {
+        #[test]
+        #[cfg(all($($min)*, $($max)*))]
+        $(#[$attr])*
+        fn $name() $body
+
+        #[test]
+        #[cfg(not(all($($min)*, $($max)*)))]
+        #[ignore = "not supported by this Core version"]
+        $(#[$attr])*
+        fn $name() {
+            eprintln!("skipping {}: requires Core {}", stringify!($name), stringify!($($range)*));
+        }
+    };
+}
+
 /// Initialize a logger (configure with `RUST_LOG=trace cargo test`).
 #[allow(dead_code)] // Not all tests use this function.
 pub fn init_logger() { let _ = env_logger::try_init(); }
@@ -34,6 +166,18 @@ pub trait NodeExt {
     /// Generates 101 blocks to an address controlled by the loaded wallet.
     fn fund_wallet(&self);
 
+    /// Mines just enough blocks for the loaded wallet to have a spendable balance of `amount`.
+    ///
+    /// The block rewards needed are mined to the wallet and then matured with
+    /// [`NodeExt::mature_coinbases`], nothing is mined if the balance is already enough.
+    fn fund_wallet_with(&self, amount: Amount);
+
+    /// Mines just enough blocks for all the block rewards of the loaded wallet to be spendable.
+    ///
+    /// The blocks are mined to an address not controlled by the wallet so they do not add
+    /// immature rewards of their own.
+    fn mature_coinbases(&self);
+
     /// Mines a block.
     ///
     /// Should send mining reward to a new address for the loaded wallet.
@@ -55,6 +199,54 @@ pub trait NodeExt {
 
     /// Returns the number of peers connected to this node.
     fn peers_connected(&self) -> usize;
+
+    /// Invalidates the best block, the node reverts to its parent.
+    ///
+    /// # Returns
+    ///
+    /// The hash of the invalidated block.
+    fn invalidate_tip(&self) -> BlockHash;
+
+    /// Removes the invalidity status of `block_hash` and its descendants, set by
+    /// [`NodeExt::invalidate_tip`] or `invalidateblock`.
+    ///
+    /// The node reorgs to the chain of `block_hash` if it has the most work.
+    fn reconsider(&self, block_hash: BlockHash);
+
+    /// Replaces the last `depth` blocks of the chain with a longer competing chain.
+    ///
+    /// The `depth + 1` blocks of the new chain are mined to an address not controlled by the
+    /// wallet. The replaced blocks stay valid, the old tip is reported as a `valid-fork` by
+    /// `getchaintips`. Like the transactions in the mempool, the transactions of the replaced
+    /// blocks that are still valid are mined in the new chain.
+    ///
+    /// # Returns
+    ///
+    /// The stale tip and the new tip.
+    fn create_fork(&self, depth: usize) -> (BlockHash, BlockHash);
+
+    /// Returns a new bech32m (P2TR) address from the loaded wallet.
+    #[cfg(not(feature = "v22_and_below"))]
+    fn new_taproot_address(&self) -> Address;
+
+    /// Creates a descriptor wallet with only `tr()` descriptors, derived from [`test_keys`].
+    ///
+    /// The wallet is blank apart from the BIP-86 receive and change descriptors, request
+    /// addresses with `AddressType::Bech32m`.
+    ///
+    /// # Returns
+    ///
+    /// A client connected to the new wallet.
+    #[cfg(not(feature = "v22_and_below"))]
+    fn create_taproot_wallet(&self, wallet: &str) -> Client;
+
+    /// Creates a transaction in the mempool paying to a P2TR address of the loaded wallet.
+    ///
+    /// # Returns
+    ///
+    /// The receive address and the transaction.
+    #[cfg(not(feature = "v22_and_below"))]
+    fn create_taproot_transaction(&self) -> (Address, bitcoin::Txid);
 }
 
 impl NodeExt for Node {
@@ -80,6 +272,52 @@ impl NodeExt for Node {
         self.client.generate_to_address(101, &address).expect("failed to generate to address");
     }
 
+    fn fund_wallet_with(&self, amount: Amount) {
+        self.mature_coinbases();
+        let balance = self.client.get_balance().expect("getbalance").into_model().unwrap().0;
+        let Some(mut missing) = amount.checked_sub(balance) else { return };
+
+        let mut height = self.client.get_block_count().expect("getblockcount").0;
+        let mut blocks = 0;
+        while missing > Amount::ZERO {
+            height += 1;
+            let subsidy = regtest_block_subsidy(height);
+            assert!(subsidy > Amount::ZERO, "not enough block subsidy left to fund {}", amount);
+            missing = missing.checked_sub(subsidy).unwrap_or(Amount::ZERO);
+            blocks += 1;
+        }
+
+        let address = self.client.new_address().expect("failed to get new address");
+        self.client.generate_to_address(blocks, &address).expect("failed to generate to address");
+        self.mature_coinbases();
+    }
+
+    fn mature_coinbases(&self) {
+        // Only coinbases in the last `COINBASE_MATURITY` blocks can still be immature.
+        let height = self.client.get_block_count().expect("getblockcount").0;
+        let args = match height.checked_sub(COINBASE_MATURITY) {
+            Some(since) => vec![self.client.get_block_hash(since).expect("getblockhash").0.into()],
+            None => vec![],
+        };
+        let json: serde_json::Value =
+            self.client.call("listsinceblock", &args).expect("listsinceblock");
+
+        let confirmations = json["transactions"]
+            .as_array()
+            .expect("listsinceblock transactions")
+            .iter()
+            .filter(|tx| tx["category"] == "immature")
+            .filter_map(|tx| tx["confirmations"].as_u64())
+            .min();
+        if let Some(confirmations) = confirmations {
+            // The wallet considers a coinbase spendable once it has one block on top of maturity.
+            let blocks = (COINBASE_MATURITY + 1).saturating_sub(confirmations);
+            self.client
+                .generate_to_address(blocks as usize, &anyone_can_spend_address())
+                .expect("failed to generate to address");
+        }
+    }
+
     fn mine_a_block(&self) {
         let address = self.client.new_address().expect("failed to get new address");
         self.client.generate_to_address(1, &address).expect("failed to generate to address");
@@ -114,17 +352,125 @@ impl NodeExt for Node {
         let json = self.client.get_peer_info().expect("get_peer_info");
         json.0.len()
     }
+
+    fn invalidate_tip(&self) -> BlockHash {
+        let tip = self.client.best_block_hash().expect("best_block_hash");
+        self.client.invalidate_block(tip).expect("invalidateblock");
+        tip
+    }
+
+    fn reconsider(&self, block_hash: BlockHash) {
+        self.client.reconsider_block(block_hash).expect("reconsiderblock");
+    }
+
+    fn create_fork(&self, depth: usize) -> (BlockHash, BlockHash) {
+        let height = self.client.get_block_count().expect("getblockcount").0;
+        assert!(depth > 0 && depth as u64 <= height, "can not fork {} blocks deep", depth);
+
+        let stale_tip = self.client.best_block_hash().expect("best_block_hash");
+        let fork_height = height + 1 - depth as u64;
+        let replaced =
+            self.client.get_block_hash(fork_height).expect("getblockhash").block_hash().unwrap();
+
+        // The old chain is invalidated while the new one is mined, then reconsidered once the new
+        // chain has more work so both chains are valid.
+        self.client.invalidate_block(replaced).expect("invalidateblock");
+        self.client
+            .generate_to_address(depth + 1, &fork_address(stale_tip))
+            .expect("failed to generate to address");
+        self.reconsider(replaced);
+
+        let new_tip = self.client.best_block_hash().expect("best_block_hash");
+        assert_ne!(new_tip, stale_tip, "the node did not reorg to the fork");
+        (stale_tip, new_tip)
+    }
+
+    #[cfg(not(feature = "v22_and_below"))]
+    fn new_taproot_address(&self) -> Address {
+        self.client.new_address_with_type(AddressType::Bech32m).expect("failed to get new address")
+    }
+
+    #[cfg(not(feature = "v22_and_below"))]
+    fn create_taproot_wallet(&self, wallet: &str) -> Client {
+        // Args: wallet_name, disable_private_keys, blank, passphrase, avoid_reuse, descriptors.
+        let args = [wallet.into(), false.into(), true.into(), "".into(), false.into(), true.into()];
+        let _: serde_json::Value = self.client.call("createwallet", &args).expect("createwallet");
+        let client = Client::new_with_auth(
+            &self.rpc_url_with_wallet(wallet),
+            Auth::CookieFile(self.params.cookie_file.clone()),
+        )
+        .expect("failed to create wallet client");
+
+        let xprv = test_keys().xprv;
+        let requests = [false, true]
+            .into_iter()
+            .map(|internal| {
+                let raw = format!("tr({}/86h/1h/0h/{}/*)", xprv, u8::from(internal));
+                let info = client.get_descriptor_info(&raw).expect("getdescriptorinfo");
+                serde_json::json!({
+                    "desc": format!("{}#{}", raw, info.checksum),
+                    "timestamp": "now",
+                    "active": true,
+                    "internal": internal,
+                })
+            })
+            .collect::<Vec<_>>();
+        let json: serde_json::Value =
+            client.call("importdescriptors", &[requests.into()]).expect("importdescriptors");
+        let results = json.as_array().expect("importdescriptors result");
+        assert!(results.iter().all(|result| result["success"] == true), "{:?}", results);
+
+        client
+    }
+
+    #[cfg(not(feature = "v22_and_below"))]
+    fn create_taproot_transaction(&self) -> (Address, bitcoin::Txid) {
+        const MILLION_SATS: bitcoin::Amount = bitcoin::Amount::from_sat(1000000);
+
+        let address = self.new_taproot_address();
+        let txid = self
+            .client
+            .send_to_address(&address, MILLION_SATS)
+            .expect("failed to send to address")
+            .txid()
+            .expect("failed to convert hex to txid");
+        (address, txid)
+    }
 }
 
-/// Return a temporary file path.
-pub fn random_tmp_file() -> PathBuf {
-    let file: String =
-        rand::thread_rng().sample_iter(&Alphanumeric).take(7).map(char::from).collect();
-    let mut tmp = std::env::temp_dir();
-    tmp.push(&file);
-    tmp
+/// The number of blocks before a coinbase output can be spent.
+const COINBASE_MATURITY: u64 = 100;
+
+/// Returns the block subsidy at `height` on regtest, which halves every 150 blocks.
+fn regtest_block_subsidy(height: u64) -> Amount {
+    match height / 150 {
+        halvings if halvings >= 64 => Amount::ZERO,
+        halvings => Amount::from_sat(Amount::from_int_btc(50).to_sat() >> halvings),
+    }
+}
+
+/// Returns a P2WSH address for an `OP_TRUE` script, for blocks that should not fund any wallet.
+fn anyone_can_spend_address() -> Address {
+    let script = ScriptBuf::builder().push_opcode(opcodes::OP_TRUE).into_script();
+    Address::p2wsh(&script, KnownHrp::Regtest)
+}
+
+/// Returns a P2WSH address unique to the fork replacing the chain up to `stale_tip`.
+///
+/// The first block of the fork then has a different coinbase than the block it replaces, even if
+/// both are mined in the same second.
+fn fork_address(stale_tip: BlockHash) -> Address {
+    let script = ScriptBuf::builder()
+        .push_slice(stale_tip.to_byte_array())
+        .push_opcode(opcodes::all::OP_DROP)
+        .push_opcode(opcodes::OP_TRUE)
+        .into_script();
+    Address::p2wsh(&script, KnownHrp::Regtest)
 }
 
+/// Return a temporary file path, the file is deleted when the returned handle is dropped.
+pub fn tmp_file() -> TmpFile { TmpFile::new().expect("failed to create temporary file") }
+
 /// Creates a Bitcoin network with three connected nodes.
 pub fn three_node_network() -> (Node, Node, Node) {
     let exe = node::exe_path().expect("failed to get bitcoind executable");
@@ -173,3 +519,11 @@ pub fn test_keys() -> TestKeys {
         x_only_public_key: xprv.private_key.x_only_public_key(&secp).0,
     }
 }
+
+/// Returns the key path only P2TR address for the [`test_keys`] x-only public key.
+///
+/// Useful to build P2TR outputs without a wallet.
+pub fn test_taproot_address() -> Address {
+    let secp = Secp256k1::verification_only();
+    Address::p2tr(&secp, test_keys().x_only_public_key, None, KnownHrp::Regtest)
+}
@@ -4,11 +4,18 @@
 
 #![allow(non_snake_case)] // Test names intentionally use double underscore.
 
+use std::time::Duration;
+
 use bitcoin::consensus::encode;
 use bitcoin::hex;
 use integration_test::{Node, NodeExt as _, Wallet};
+use node::client::client_sync::dyn_client::DynClient;
+use node::client::client_sync::notifier::ChainEvent;
+#[cfg(not(feature = "v20_and_below"))]
+use node::client::client_sync::notifier::MempoolEvent;
+use node::client::client_sync::Auth;
 use node::vtype::*; // All the version specific types.
-use node::{mtype, Input, Output};
+use node::{mtype, Client, Input, Output};
 
 #[test]
 #[cfg(not(feature = "v25_and_below"))]
@@ -17,7 +24,7 @@ fn blockchain__dump_tx_out_set__modelled() {
     node.fund_wallet();
     let (_address, _tx) = node.create_mined_transaction();
 
-    let temp_path = integration_test::random_tmp_file();
+    let temp_path = integration_test::tmp_file();
     let path = temp_path.to_str().expect("temp path should be valid UTF-8");
     let json: DumpTxOutSet;
     #[cfg(feature = "v28_and_below")]
@@ -31,7 +38,28 @@ fn blockchain__dump_tx_out_set__modelled() {
     let model: Result<mtype::DumpTxOutSet, DumpTxOutSetError> = json.into_model();
     let dump = model.unwrap();
 
-    assert!(dump.coins_written.to_sat() > 0);
+    assert!(dump.coins_written > 0);
+}
+
+#[test]
+#[cfg(not(feature = "v28_and_below"))]
+fn blockchain__dump_tx_out_set_rollback__modelled() {
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+    let tip = node.client.get_block_count().expect("getblockcount").0;
+
+    let temp_path = integration_test::tmp_file();
+    let path = temp_path.to_str().expect("temp path should be valid UTF-8");
+    let json: DumpTxOutSet =
+        node.client.dump_tx_out_set_rollback(path, 100).expect("dumptxoutset rollback");
+    let model: Result<mtype::DumpTxOutSet, DumpTxOutSetError> = json.into_model();
+    let dump = model.unwrap();
+
+    let base_hash = node.client.get_block_hash(100).expect("getblockhash").block_hash().unwrap();
+    assert_eq!(dump.base_height, 100);
+    assert_eq!(dump.base_hash, base_hash);
+    // The chain is replayed back to the tip after the dump.
+    assert_eq!(node.client.get_block_count().expect("getblockcount").0, tip);
 }
 
 #[test]
@@ -82,6 +110,12 @@ fn blockchain__get_block__modelled() {
         assert!(!mined_entry.transaction.transaction.input.is_empty());
         assert!(!mined_entry.transaction.transaction.output.is_empty());
 
+        let json: GetBlockVerboseTwoLazy =
+            node.client.get_block_verbose_two_lazy(block_hash).expect("getblock verbose=2");
+        assert_eq!(json.tx.len(), block_v2.tx.len());
+        let txs = json.iter_transactions().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(txs, block_v2.tx);
+
         let json: GetBlockVerboseThree =
             node.client.get_block_verbose_three(block_hash).expect("getblock verbose=3");
         let model: Result<mtype::GetBlockVerboseThree, GetBlockVerboseThreeError> =
@@ -114,7 +148,65 @@ fn blockchain__get_blockchain_info__modelled() {
 
     let json: GetBlockchainInfo = node.client.get_blockchain_info().expect("rpc");
     let model: Result<mtype::GetBlockchainInfo, GetBlockchainInfoError> = json.into_model();
-    model.unwrap();
+    let model = model.unwrap();
+
+    assert_eq!(model.chain_network(), bitcoin::Network::Regtest);
+    assert_eq!(model.blocks_behind(), 0);
+    assert!((0.0..=1.0).contains(&model.progress()));
+}
+
+#[test]
+fn blockchain__dyn_client() {
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+    let (_, txid) = node.create_mempool_transaction();
+
+    let client: &dyn DynClient = &node.client;
+    let count = client.get_block_count().expect("getblockcount");
+    let hash = client.get_block_hash(count).expect("getblockhash");
+    assert_eq!(client.get_best_block_hash().expect("getbestblockhash"), hash);
+
+    let block = client.get_block(hash).expect("getblock");
+    assert_eq!(block.block_hash(), hash);
+    assert_eq!(client.get_block_header(hash).expect("getblockheader"), block.header);
+
+    assert_eq!(client.get_raw_mempool().expect("getrawmempool"), vec![txid]);
+    let tx = client.get_raw_transaction(txid).expect("getrawtransaction");
+    assert_eq!(tx.compute_txid(), txid);
+}
+
+#[test]
+fn blockchain__dyn_client__mock() {
+    struct Mock;
+
+    impl DynClient for Mock {
+        fn call_json(
+            &self,
+            method: &str,
+            _args: &[node::serde_json::Value],
+        ) -> node::client::client_sync::Result<node::serde_json::Value> {
+            match method {
+                "getblockcount" => Ok(42.into()),
+                _ => Err(node::client::client_sync::Error::Returned(method.to_owned())),
+            }
+        }
+    }
+
+    let client: Box<dyn DynClient> = Box::new(Mock);
+    assert_eq!(client.get_block_count().expect("getblockcount"), 42);
+    assert!(client.get_best_block_hash().is_err());
+}
+
+#[test]
+fn blockchain__is_synced() {
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+
+    let info = node.client.get_blockchain_info().expect("getblockchaininfo");
+    let model = info.into_model().expect("into_model");
+    let synced = node.client.is_synced(0).expect("is_synced");
+    assert_eq!(synced, model.is_synced(0));
+    assert!(synced);
 }
 
 #[test]
@@ -194,6 +286,157 @@ fn blockchain__get_block_header__modelled() {
     model.unwrap();
 }
 
+#[test]
+fn blockchain__block_headers_from() {
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+    let tip = node.client.best_block_hash().expect("best_block_hash");
+
+    let headers = node.client.block_headers_from(0).expect("block_headers_from");
+    assert_eq!(headers.len(), 102);
+    for (i, (height, _)) in headers.iter().enumerate() {
+        assert_eq!(*height as usize, i);
+    }
+    assert_eq!(headers.last().expect("tip header").1.block_hash(), tip);
+
+    let headers = node.client.block_headers_from(100).expect("block_headers_from");
+    assert_eq!(headers.len(), 2);
+    assert_eq!(headers[0].0, 100);
+
+    let headers = node.client.block_headers_from(1_000).expect("block_headers_from");
+    assert!(headers.is_empty());
+}
+
+#[test]
+fn blockchain__into_chain_notifier() {
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    let auth = Auth::CookieFile(node.params.cookie_file.clone());
+    let client = Client::new_with_auth(&node.rpc_url(), auth).expect("client");
+    let notifier =
+        client.into_chain_notifier(Duration::from_millis(100)).expect("into_chain_notifier");
+    let recv = || notifier.events().recv_timeout(Duration::from_secs(10)).expect("chain event");
+
+    node.mine_a_block();
+    let stale = node.client.best_block_hash().expect("best_block_hash");
+    assert_eq!(recv(), ChainEvent::BlockConnected { hash: stale, height: 1 });
+
+    // Replace the tip with a longer chain.
+    node.client.invalidate_block(stale).expect("invalidateblock");
+    node.mine_a_block();
+    node.mine_a_block();
+    let tip = node.client.best_block_hash().expect("best_block_hash");
+
+    let mut events = vec![];
+    while !events.contains(&ChainEvent::BlockConnected { hash: tip, height: 2 }) {
+        events.push(recv());
+    }
+    assert_eq!(events[0], ChainEvent::BlockDisconnected { hash: stale, height: 1 });
+}
+
+#[test]
+#[cfg(not(feature = "v20_and_below"))]
+fn blockchain__into_mempool_watcher() {
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+    let auth = Auth::CookieFile(node.params.cookie_file.clone());
+    let client = Client::new_with_auth(&node.rpc_url(), auth).expect("client");
+    let watcher =
+        client.into_mempool_watcher(Duration::from_millis(100)).expect("into_mempool_watcher");
+    let recv = || watcher.events().recv_timeout(Duration::from_secs(10)).expect("mempool event");
+
+    let address = node.client.new_address().expect("failed to create new address");
+    let txid = node
+        .client
+        .send_to_address_rbf(&address, bitcoin::Amount::from_sat(10_000))
+        .expect("sendtoaddress")
+        .txid()
+        .unwrap();
+    match recv() {
+        MempoolEvent::Added(tx) => assert_eq!(tx.compute_txid(), txid),
+        e => panic!("unexpected event: {:?}", e),
+    }
+
+    let replacement = node.client.bump_fee(txid).expect("bumpfee").into_model().unwrap().txid;
+    assert_eq!(recv(), MempoolEvent::Replaced { txid, replacement });
+    match recv() {
+        MempoolEvent::Added(tx) => assert_eq!(tx.compute_txid(), replacement),
+        e => panic!("unexpected event: {:?}", e),
+    }
+
+    node.mine_a_block();
+    assert_eq!(recv(), MempoolEvent::Removed(replacement));
+}
+
+#[test]
+fn blockchain__client_async() {
+    // The methods used return the same types for all versions of Core.
+    use node::client::client_async::v17::Client as AsyncClient;
+
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+    let (_, txid) = node.create_mempool_transaction();
+    let auth = Auth::CookieFile(node.params.cookie_file.clone());
+    let client = AsyncClient::new_with_auth(&node.rpc_url(), auth).expect("client");
+
+    let rt = tokio::runtime::Builder::new_current_thread().enable_all().build().expect("runtime");
+    rt.block_on(async {
+        let count = client.get_block_count().await.expect("getblockcount").0;
+        assert_eq!(count, 101);
+
+        let tip = client.best_block_hash().await.expect("best_block_hash");
+        assert_eq!(tip, node.client.best_block_hash().expect("best_block_hash"));
+        let block = client.get_block(tip).await.expect("getblock");
+        assert_eq!(block.block_hash(), tip);
+
+        let json = client.get_raw_transaction(txid).await.expect("getrawtransaction");
+        assert_eq!(json.transaction().expect("transaction").compute_txid(), txid);
+
+        // Any other method is called with the version specific type.
+        let json: GetBlockchainInfo = client.call("getblockchaininfo", &[]).await.expect("call");
+        assert_eq!(json.blocks, 101);
+
+        let err = client.call::<String>("nosuchmethod", &[]).await.unwrap_err();
+        assert!(err.rpc_error().is_some());
+    });
+}
+
+#[test]
+fn blockchain__batch() {
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+    let (_, txid) = node.create_mempool_transaction();
+    let hashes = (0..=101)
+        .map(|height| node.client.get_block_hash(height).expect("getblockhash").block_hash())
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    let mut batch = node.client.batch();
+    let count = batch.call::<GetBlockCount>("getblockcount", &[]).expect("call");
+    let blocks = hashes
+        .iter()
+        .map(|hash| {
+            batch.call_hex::<bitcoin::Block>("getblock", &[hash.to_string().into(), 0.into()])
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .expect("call_hex");
+    let tx = batch
+        .call_hex::<bitcoin::Transaction>("getrawtransaction", &[txid.to_string().into()])
+        .expect("call_hex");
+    let missing = batch.call::<GetBlockHash>("getblockhash", &[1_000.into()]).expect("call");
+    assert_eq!(batch.len(), 105);
+
+    let mut results = batch.send().expect("send");
+    assert_eq!(results.take(count).expect("getblockcount").0, 101);
+    for (hash, block) in hashes.iter().zip(blocks) {
+        assert_eq!(results.take(block).expect("getblock").block_hash(), *hash);
+    }
+    assert_eq!(results.take(tx).expect("getrawtransaction").compute_txid(), txid);
+    // Calls fail on their own.
+    assert!(results.take(missing).unwrap_err().rpc_error().is_some());
+    // A result can only be taken once.
+    assert!(results.take(count).is_err());
+}
+
 #[test]
 fn blockchain__get_block_stats__modelled() {
     // Version 17 and 18 cannot call `getblockstats` if `-txindex` is not enabled.
@@ -239,17 +482,36 @@ fn getblockstats_txindex() {
 }
 
 #[test]
-#[cfg(not(feature = "v25_and_below"))]
-fn blockchain__get_chain_states__modelled() {
-    let node = Node::with_wallet(Wallet::Default, &[]);
+fn blockchain__block_stats_range() {
+    // Version 17 and 18 cannot call `getblockstats` if `-txindex` is not enabled.
+    let node = Node::with_wallet(Wallet::Default, &["-txindex"]);
     node.fund_wallet();
-    let (_address, _tx) = node.create_mined_transaction();
 
-    let json: GetChainStates = node.client.get_chain_states().expect("getchainstates");
-    let model: Result<mtype::GetChainStates, GetChainStatesError> = json.into_model();
-    let chain_states = model.unwrap();
+    let stats: Vec<GetBlockStats> =
+        node.client.block_stats_range(1, 101, &[]).expect("block_stats_range");
+    assert_eq!(stats.len(), 101);
+    for (height, json) in (1..=101).zip(stats) {
+        assert_eq!(json.height, height);
+        assert_eq!(json, node.client.get_block_stats_by_height(height as u32).unwrap());
+    }
 
-    assert!(chain_states.chain_states[0].blocks > 0);
+    let stats: Vec<GetBlockStats> =
+        node.client.block_stats_range(102, 101, &[]).expect("block_stats_range");
+    assert!(stats.is_empty());
+}
+
+integration_test::versioned_test! {
+    26.. => fn blockchain__get_chain_states__modelled() {
+        let node = Node::with_wallet(Wallet::Default, &[]);
+        node.fund_wallet();
+        let (_address, _tx) = node.create_mined_transaction();
+
+        let json: GetChainStates = node.client.get_chain_states().expect("getchainstates");
+        let model: Result<mtype::GetChainStates, GetChainStatesError> = json.into_model();
+        let chain_states = model.unwrap();
+
+        assert!(chain_states.chain_states[0].blocks > 0);
+    }
 }
 
 #[test]
@@ -261,6 +523,35 @@ fn blockchain__get_chain_tips__modelled() {
     model.unwrap();
 }
 
+#[test]
+fn blockchain__create_fork() {
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+    let (_, txid) = node.create_mempool_transaction();
+    node.mine_a_block();
+    let height = node.client.get_block_count().expect("getblockcount").0;
+
+    let (stale_tip, new_tip) = node.create_fork(2);
+    assert_eq!(node.client.best_block_hash().expect("best_block_hash"), new_tip);
+    assert_eq!(node.client.get_block_count().expect("getblockcount").0, height + 1);
+    // The transaction mined in a replaced block is mined again in the new chain.
+    let mempool = node.client.get_raw_mempool().expect("getrawmempool").into_model().unwrap();
+    assert!(mempool.0.is_empty());
+    let tx = node.client.get_transaction(txid).expect("gettransaction").into_model().unwrap();
+    assert_eq!(tx.block_hash.map(|hash| hash != stale_tip), Some(true));
+
+    let tips = node.client.get_chain_tips().expect("getchaintips").into_model().unwrap();
+    let stale = tips.0.iter().find(|tip| tip.hash == stale_tip).expect("stale tip");
+    assert_eq!(stale.status, mtype::ChainTipsStatus::ValidFork);
+    assert_eq!(stale.branch_length, 2);
+
+    let invalidated = node.invalidate_tip();
+    assert_eq!(invalidated, new_tip);
+    assert_eq!(node.client.get_block_count().expect("getblockcount").0, height);
+    node.reconsider(invalidated);
+    assert_eq!(node.client.best_block_hash().expect("best_block_hash"), new_tip);
+}
+
 #[test]
 fn blockchain__get_chain_tx_stats__modelled() {
     let node = Node::with_wallet(Wallet::Default, &[]);
@@ -411,7 +702,15 @@ fn blockchain__get_mempool_entry__modelled() {
 
     let json: GetMempoolEntry = node.client.get_mempool_entry(txid).expect("getmempoolentry");
     let model: Result<mtype::GetMempoolEntry, MempoolEntryError> = json.into_model();
-    model.unwrap();
+    let entry = model.unwrap().0;
+
+    assert!(entry.fees.base > bitcoin::Amount::ZERO);
+    assert_eq!(entry.fees.ancestor, entry.fees.base);
+    #[cfg(not(feature = "v18_and_below"))]
+    {
+        let weight = entry.weight.expect("weight");
+        assert_eq!(u64::from(entry.vsize.expect("vsize")), weight.to_vbytes_ceil());
+    }
 }
 
 #[test]
@@ -426,6 +725,13 @@ fn blockchain__get_mempool_info__modelled() {
 
     // Sanity check.
     assert_eq!(info.size, 1);
+
+    #[cfg(not(feature = "v21_and_below"))]
+    {
+        let mempool = node.client.get_raw_mempool_verbose().expect("getrawmempool verbose");
+        let fee = mempool.into_model().expect("into_model").0.values().map(|e| e.fees.base).sum();
+        assert_eq!(info.total_fee, Some(fee));
+    }
 }
 
 #[test]
@@ -580,28 +886,67 @@ fn blockchain__savemempool() {
     }
 }
 
-#[test]
-#[cfg(not(feature = "v24_and_below"))]
-fn blockchain__scan_blocks_modelled() {
-    let node = Node::with_wallet(Wallet::None, &["-blockfilterindex=1"]);
+integration_test::versioned_test! {
+    25.. => fn blockchain__scan_blocks_modelled() {
+        let node = Node::with_wallet(Wallet::None, &["-blockfilterindex=1"]);
 
-    // Arbitrary scan descriptor
-    let scan_desc = "pkh(022afc20bf379bc96a2f4e9e63ffceb8652b2b6a097f63fbee6ecec2a49a48010e)";
+        // Arbitrary scan descriptor
+        let scan_desc = "pkh(022afc20bf379bc96a2f4e9e63ffceb8652b2b6a097f63fbee6ecec2a49a48010e)";
 
-    let json: ScanBlocksStart =
-        node.client.scan_blocks_start(&[scan_desc]).expect("scanblocks start");
-    let model: Result<mtype::ScanBlocksStart, ScanBlocksStartError> = json.into_model();
-    let model = model.unwrap();
+        let json: ScanBlocksStart =
+            node.client.scan_blocks_start(&[scan_desc]).expect("scanblocks start");
+        let model: Result<mtype::ScanBlocksStart, ScanBlocksStartError> = json.into_model();
+        let model = model.unwrap();
 
-    let _: Option<ScanBlocksStatus> = node.client.scan_blocks_status().expect("scanblocks status");
+        let _: Option<ScanBlocksStatus> =
+            node.client.scan_blocks_status().expect("scanblocks status");
 
-    let _: ScanBlocksAbort = node.client.scan_blocks_abort().expect("scanblocks abort");
+        let _: ScanBlocksAbort = node.client.scan_blocks_abort().expect("scanblocks abort");
 
-    assert!(model.from_height <= model.to_height);
+        assert!(model.from_height <= model.to_height);
 
-    #[cfg(not(feature = "v25_and_below"))]
-    {
-        assert!(model.completed.is_some());
+        #[cfg(not(feature = "v25_and_below"))]
+        {
+            assert!(model.completed.is_some());
+        }
+    }
+}
+
+integration_test::versioned_test! {
+    25.. => fn blockchain__scan_blocks_with_options__modelled() {
+        use node::{ScanBlocksOptions, ScanObject};
+
+        let node = Node::with_wallet(Wallet::Default, &["-blockfilterindex=1"]);
+        node.fund_wallet();
+        let (address, _tx) = node.create_mined_transaction();
+        let best = node.client.best_block_hash().expect("best");
+        let height = node.client.get_block_count().expect("getblockcount").0 as u32;
+
+        let scan = ScanObject::new(format!("addr({})", address));
+        // Only scans the block with the transaction.
+        let filter_false_positives = if cfg!(feature = "v25_and_below") { None } else { Some(true) };
+        let options =
+            ScanBlocksOptions { start_height: Some(height), filter_false_positives, stop_height: None };
+        let json: ScanBlocksStart = node
+            .client
+            .scan_blocks_start_with_options(std::slice::from_ref(&scan), &options)
+            .expect("scanblocks start");
+        let model: Result<mtype::ScanBlocksStart, ScanBlocksStartError> = json.into_model();
+        let model = model.unwrap();
+        assert_eq!(model.from_height, height);
+        assert_eq!(model.relevant_blocks, vec![best]);
+
+        // The block with the transaction is outside of the scanned heights.
+        let options = ScanBlocksOptions {
+            start_height: Some(0),
+            stop_height: Some(height - 1),
+            ..Default::default()
+        };
+        let json = node
+            .client
+            .scan_blocks_start_with_options(&[scan], &options)
+            .expect("scanblocks start");
+        assert!(!json.into_model().unwrap().relevant_blocks.contains(&best));
     }
 }
 
@@ -628,6 +973,61 @@ fn blockchain__scan_tx_out_set_modelled() {
     let _: ScanTxOutSetAbort = node.client.scan_tx_out_set_abort().expect("scantxoutset abort");
 }
 
+#[test]
+fn blockchain__scan_tx_out_set_with_objects__modelled() {
+    use bitcoin::bip32::ChildNumber;
+    use bitcoin::secp256k1::Secp256k1;
+    use bitcoin::{Address, Amount, Network, OutPoint};
+    use node::ScanObject;
+
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+
+    // Pay to the child at index 3 of a ranged descriptor the wallet does not know about.
+    let xpub = integration_test::test_keys().xpub;
+    let child =
+        xpub.derive_pub(&Secp256k1::new(), &[ChildNumber::from_normal_idx(3).unwrap()]).unwrap();
+    let address = Address::p2pkh(child.to_pub(), Network::Regtest);
+    let amount = Amount::from_sat(100_000);
+    let txid =
+        node.client.send_to_address(&address, amount).expect("sendtoaddress").txid().unwrap();
+    node.mine_a_block();
+
+    let descriptor = format!("pkh({}/*)", xpub);
+    // Core v17 only accepts ranges starting at 0.
+    let scan = ScanObject::new(&descriptor).with_range(0, 5);
+    let json: ScanTxOutSetStart =
+        node.client.scan_tx_out_set_start_with_objects(&[scan]).expect("scantxoutset start");
+    let model: Result<mtype::ScanTxOutSetStart, ScanTxOutSetError> = json.into_model();
+    let model = model.unwrap();
+    assert!(model.success);
+    assert_eq!(model.unspents.len(), 1);
+    assert_eq!(model.total_amount, amount);
+
+    let unspent = &model.unspents[0];
+    assert_eq!(unspent.outpoint(), OutPoint { txid, vout: unspent.vout });
+    assert_eq!(unspent.script_pubkey, address.script_pubkey());
+    assert_eq!(unspent.amount, amount);
+
+    // The child at index 3 is outside of the range.
+    let scan = ScanObject::new(&descriptor).with_range(0, 2);
+    let json = node.client.scan_tx_out_set_start_with_objects(&[scan]).expect("scantxoutset start");
+    assert!(json.into_model().unwrap().unspents.is_empty());
+
+    #[cfg(not(feature = "v17"))]
+    {
+        let scan = ScanObject::new(&descriptor).with_range(2, 5);
+        let json =
+            node.client.scan_tx_out_set_start_with_objects(&[scan]).expect("scantxoutset start");
+        assert_eq!(json.into_model().unwrap().unspents.len(), 1);
+
+        let scan = ScanObject::new(&descriptor).with_range(4, 5);
+        let json =
+            node.client.scan_tx_out_set_start_with_objects(&[scan]).expect("scantxoutset start");
+        assert!(json.into_model().unwrap().unspents.is_empty());
+    }
+}
+
 #[test]
 fn blockchain__verify_chain() {
     let node = Node::with_wallet(Wallet::None, &[]);
@@ -708,6 +1108,34 @@ fn blockchain__wait_for_new_block__modelled() {
     assert_ne!(block.hash, prev_hash);
 }
 
+#[test]
+fn blockchain__wait_for_new_block_with_timeout() {
+    use std::time::Instant;
+
+    let node = Node::with_wallet(Wallet::None, &[]);
+    let tip = node.client.best_block_hash().expect("bestblockhash");
+
+    // Waiting longer than the timeout of regular calls does not time out the call.
+    let client = Client::builder()
+        .url(&node.rpc_url())
+        .auth(Auth::CookieFile(node.params.cookie_file.clone()))
+        .timeout(Duration::from_secs(1))
+        .build()
+        .expect("build");
+    let start = Instant::now();
+    let json =
+        client.wait_for_new_block_with_timeout(Duration::from_secs(3)).expect("waitfornewblock");
+    assert!(start.elapsed() >= Duration::from_secs(3));
+    // The current tip is returned on timeout.
+    assert_eq!(json.into_model().unwrap().hash, tip);
+
+    let json = client
+        .wait_for_block_height_with_timeout(10, Duration::from_millis(500))
+        .expect("waitforblockheight");
+    let block = json.into_model().unwrap();
+    assert_eq!((block.hash, block.height), (tip, 0));
+}
+
 /// Create and broadcast a child transaction spending vout 0 of the given parent mempool txid.
 /// Returns the child's txid.
 fn create_child_spending_parent(node: &Node, parent_txid: bitcoin::Txid) -> bitcoin::Txid {
@@ -5,7 +5,9 @@
 #![allow(non_snake_case)] // Test names intentionally use double underscore.
 
 use integration_test::{Node, NodeExt as _, Wallet};
+use node::client::client_sync::Error;
 use node::vtype::*; // All the version specific types.
+use node::Client;
 
 #[test]
 fn control__get_memory_info() {
@@ -43,3 +45,249 @@ fn control__uptime() {
     let node = Node::with_wallet(Wallet::None, &[]);
     let _ = node.client.uptime().unwrap();
 }
+
+#[test]
+fn control__wait_for_rpc() {
+    use std::time::{Duration, Instant};
+
+    let node = Node::with_wallet(Wallet::None, &[]);
+    node.client.wait_for_rpc(Duration::from_secs(5)).expect("wait_for_rpc");
+
+    // Nothing listens on port 1.
+    let client = Client::new("http://127.0.0.1:1");
+    let start = Instant::now();
+    let err = client.wait_for_rpc(Duration::from_millis(500)).unwrap_err();
+    assert!(start.elapsed() >= Duration::from_millis(500));
+    assert!(matches!(err, Error::JsonRpc(_)), "{}", err);
+}
+
+#[test]
+fn control__builder() {
+    use std::time::{Duration, Instant};
+
+    use node::client::client_sync::Auth;
+
+    let node = Node::with_wallet(Wallet::None, &[]);
+    for keep_alive in [false, true] {
+        let client = Client::builder()
+            .url(&node.rpc_url())
+            .auth(Auth::CookieFile(node.params.cookie_file.clone()))
+            .timeout(Duration::from_secs(5))
+            .retries(3)
+            .keep_alive(keep_alive)
+            .build()
+            .expect("build");
+        for _ in 0..3 {
+            let _ = client.uptime().expect("uptime");
+        }
+    }
+
+    // Nothing listens on port 1, the call is retried with backoff.
+    let client = Client::builder()
+        .url("http://127.0.0.1:1")
+        .retries(2)
+        .retry_backoff(Duration::from_millis(100))
+        .build()
+        .expect("build");
+    let start = Instant::now();
+    let err = client.uptime().unwrap_err();
+    assert!(start.elapsed() >= Duration::from_millis(300));
+    assert!(matches!(err, Error::JsonRpc(_)), "{}", err);
+
+    // Errors returned by the node are not retried.
+    let client = Client::builder()
+        .url(&node.rpc_url())
+        .auth(Auth::CookieFile(node.params.cookie_file.clone()))
+        .retries(5)
+        .retry_backoff(Duration::from_secs(10))
+        .build()
+        .expect("build");
+    let start = Instant::now();
+    assert!(client.call::<String>("nosuchmethod", &[]).unwrap_err().is_method_not_found());
+    assert!(start.elapsed() < Duration::from_secs(10));
+}
+
+#[test]
+fn control__builder_proxy() {
+    use std::io::{self, Read, Write};
+    use std::net::{Ipv4Addr, Shutdown, TcpListener, TcpStream};
+    use std::thread;
+    use std::time::Duration;
+
+    use node::client::client_sync::Auth;
+
+    // A SOCKS5 proxy without authentication that relays each connection to the requested address.
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+    let proxy = listener.local_addr().expect("local_addr").to_string();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut client = stream.expect("accept");
+            thread::spawn(move || -> io::Result<()> {
+                let mut buf = [0; 10];
+                client.read_exact(&mut buf[..3])?; // Version 5, one method, no authentication.
+                client.write_all(&[5, 0])?;
+                client.read_exact(&mut buf)?; // Connect to an IPv4 address.
+                let ip = Ipv4Addr::new(buf[4], buf[5], buf[6], buf[7]);
+                let mut node = TcpStream::connect((ip, u16::from_be_bytes([buf[8], buf[9]])))?;
+                client.write_all(&[5, 0, 0, 1, 0, 0, 0, 0, 0, 0])?;
+
+                let (mut from, mut to) = (client.try_clone()?, node.try_clone()?);
+                thread::spawn(move || {
+                    let _ = io::copy(&mut from, &mut to);
+                    to.shutdown(Shutdown::Write)
+                });
+                io::copy(&mut node, &mut client)?;
+                Ok(())
+            });
+        }
+    });
+
+    let node = Node::with_wallet(Wallet::None, &[]);
+    let client = Client::builder()
+        .url(&node.rpc_url())
+        .auth(Auth::CookieFile(node.params.cookie_file.clone()))
+        .proxy(&proxy)
+        .keep_alive(true) // Ignored, the proxy needs a connection per call.
+        .build()
+        .expect("build");
+    for _ in 0..3 {
+        let _ = client.uptime().expect("uptime");
+    }
+    // Long polls go through the proxy too.
+    let json = client.wait_for_new_block_with_timeout(Duration::from_millis(100)).expect("wait");
+    assert_eq!(json.into_model().unwrap().hash, node.client.best_block_hash().expect("best"));
+
+    // Nothing listens on port 1.
+    let client = Client::builder()
+        .url(&node.rpc_url())
+        .auth(Auth::CookieFile(node.params.cookie_file.clone()))
+        .proxy("127.0.0.1:1")
+        .build()
+        .expect("build");
+    assert!(matches!(client.uptime().unwrap_err(), Error::JsonRpc(_)));
+}
+
+#[test]
+fn control__rpc_error_code() {
+    use node::client::client_sync::RpcErrorCode;
+
+    let node = Node::with_wallet(Wallet::None, &[]);
+    let err = node.client.call::<String>("nosuchmethod", &[]).unwrap_err();
+    assert!(err.is_method_not_found(), "unexpected error: {}", err);
+    assert_eq!(err.rpc_error_code(), Some(RpcErrorCode::MethodNotFound));
+    assert_eq!(err.rpc_error().map(|e| e.code), Some(RpcErrorCode::MethodNotFound.code()));
+    assert!(!err.is_warmup());
+}
+
+#[test]
+fn control__slow_call_threshold() {
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    use log::{Level, LevelFilter, Log, Metadata, Record};
+    use node::client::client_sync::Auth;
+
+    /// Records the warnings logged by the client.
+    struct Warnings(Mutex<Vec<String>>);
+
+    impl Log for Warnings {
+        fn enabled(&self, meta: &Metadata) -> bool {
+            meta.target() == "corepc" && meta.level() <= Level::Warn
+        }
+
+        fn log(&self, record: &Record) {
+            if self.enabled(record.metadata()) {
+                self.0.lock().unwrap().push(record.args().to_string());
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    static WARNINGS: Warnings = Warnings(Mutex::new(Vec::new()));
+    log::set_logger(&WARNINGS).expect("no other test sets a logger");
+    log::set_max_level(LevelFilter::Warn);
+
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    let auth = Auth::CookieFile(node.params.cookie_file.clone());
+    // Every call is logged as slow.
+    let client = Client::new_with_auth(&node.rpc_url_with_wallet("default"), auth)
+        .expect("client")
+        .slow_call_threshold(Duration::ZERO);
+    let _: GetBalance = client.get_balance().expect("getbalance");
+    // Calls are not slow with the default threshold.
+    let _ = node.client.get_block_count().expect("getblockcount");
+
+    let warnings = WARNINGS.0.lock().unwrap();
+    let slow = |method: &str| {
+        let prefix = format!("slow call: {} ", method);
+        warnings.iter().filter(|w| w.starts_with(&prefix)).cloned().collect::<Vec<_>>()
+    };
+    let getbalance = slow("getbalance");
+    assert_eq!(getbalance.len(), 1, "{:?}", warnings);
+    assert!(getbalance[0].contains("wallet=default duration="), "{}", getbalance[0]);
+    assert!(getbalance[0].ends_with("outcome=ok"), "{}", getbalance[0]);
+    assert!(slow("getblockcount").is_empty(), "{:?}", warnings);
+}
+
+#[test]
+fn control__with_observer() {
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use node::client::client_sync::observer::{Outcome, RpcCall};
+    use node::client::client_sync::Auth;
+
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    let calls = Arc::new(Mutex::new(vec![]));
+    let observed = Arc::clone(&calls);
+    let auth = Auth::CookieFile(node.params.cookie_file.clone());
+    let client = Client::new_with_auth(&node.rpc_url_with_wallet("default"), auth)
+        .expect("client")
+        .with_observer(move |call: &RpcCall| {
+            let wallet = call.wallet.map(str::to_owned);
+            let call = (call.method.to_owned(), wallet, call.request_size, call.outcome);
+            observed.lock().unwrap().push(call);
+        });
+
+    let _: GetBalance = client.get_balance().expect("getbalance");
+    let _ = client.call::<String>("nosuchmethod", &["argument".into()]).unwrap_err();
+    let _: Vec<GetBlockHash> = client.call_batch("getblockhash", &[vec![0.into()]]).expect("batch");
+
+    let calls = calls.lock().unwrap();
+    let wallet = Some("default".to_owned());
+    assert_eq!(calls.len(), 3);
+    assert_eq!(calls[0], ("getbalance".to_owned(), wallet.clone(), 2, Outcome::Success));
+    assert_eq!(
+        calls[1],
+        ("nosuchmethod".to_owned(), wallet.clone(), 12, Outcome::RpcError(-32601))
+    );
+    assert_eq!(calls[2], ("getblockhash".to_owned(), wallet, 3, Outcome::Success));
+
+    // Calls that get no response are observed as transport errors.
+    let calls = Arc::new(Mutex::new(vec![]));
+    let observed = Arc::clone(&calls);
+    let client = Client::builder()
+        .url("http://127.0.0.1:1")
+        .timeout(Duration::from_secs(1))
+        .build()
+        .expect("build")
+        .with_observer(move |call: &RpcCall| observed.lock().unwrap().push(call.outcome));
+    assert!(client.uptime().is_err());
+    assert_eq!(*calls.lock().unwrap(), vec![Outcome::TransportError]);
+}
+
+#[test]
+fn control__from_bitcoin_conf() {
+    let node = Node::with_wallet(Wallet::None, &[]);
+    let datadir = node.workdir();
+    let conf = datadir.join("bitcoin.conf");
+    let port = node.params.rpc_socket.port();
+    std::fs::write(&conf, format!("regtest=1\n[regtest]\nrpcport={}\n", port)).expect("write");
+
+    let client = Client::from_bitcoin_conf(&conf).expect("from_bitcoin_conf");
+    client.get_block_count().expect("getblockcount");
+
+    let client = Client::from_datadir(&datadir, bitcoin::Network::Regtest).expect("from_datadir");
+    client.get_block_count().expect("getblockcount");
+}
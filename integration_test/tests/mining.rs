@@ -36,6 +36,45 @@ fn mining__get_block_template__modelled() {
     model.unwrap();
 }
 
+#[test]
+fn mining__block_templates() {
+    // Requires connected nodes otherwise the RPC call errors.
+    let (node1, node2, node3) = integration_test::three_node_network();
+
+    // Use the nodes otherwise they get dropped.
+    node1.mine_a_block();
+    node2.mine_a_block();
+    node3.mine_a_block();
+
+    let options = match () {
+        #[cfg(feature = "v28_and_below")]
+        () => TemplateRequest { rules: vec![TemplateRules::Segwit] },
+        #[cfg(not(feature = "v28_and_below"))]
+        () => TemplateRequest {
+            rules: vec![TemplateRules::Segwit],
+            mode: Some("template".to_string()),
+            ..Default::default()
+        },
+    };
+
+    let mut templates = node1.client.block_templates(&options).expect("block_templates failed");
+    let first = templates.next().unwrap().expect("first template");
+    assert!(first.long_poll_id.is_some());
+    assert_eq!(templates.long_poll_id(), first.long_poll_id.as_deref());
+
+    // The long poll blocks until the tip changes, so mine from another thread.
+    let second = std::thread::scope(|s| {
+        s.spawn(|| {
+            std::thread::sleep(std::time::Duration::from_secs(1));
+            node2.mine_a_block();
+        });
+        templates.next().unwrap().expect("second template")
+    });
+
+    assert_eq!(second.height, first.height + 1);
+    assert_ne!(second.previous_block_hash, first.previous_block_hash);
+}
+
 #[test]
 fn mining__get_mining_info() {
     let node = Node::with_wallet(Wallet::Default, &[]);
@@ -43,17 +82,11 @@ fn mining__get_mining_info() {
 
     let json: GetMiningInfo = node.client.get_mining_info().expect("rpc");
 
-    // Up to v28 (i.e., not 29_0) there is no error converting into model.
-    #[cfg(feature = "v28_and_below")]
-    let model: mtype::GetMiningInfo = json.into_model();
-
-    #[cfg(not(feature = "v28_and_below"))]
-    let model: mtype::GetMiningInfo = {
-        let result: Result<mtype::GetMiningInfo, GetMiningInfoError> = json.into_model();
-        result.unwrap()
-    };
+    let model: Result<mtype::GetMiningInfo, GetMiningInfoError> = json.into_model();
+    let model = model.unwrap();
 
     assert!(model.network_hash_ps > 0.0);
+    assert_eq!(model.chain, bitcoin::Network::Regtest);
 }
 
 #[test]
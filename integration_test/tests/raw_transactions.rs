@@ -5,14 +5,20 @@
 #![allow(non_snake_case)] // Test names intentionally use double underscore.
 #![allow(unused_imports)] // Because of feature gated tests.
 
+use std::time::Duration;
+
 use bitcoin::bip32::DerivationPath;
 use bitcoin::consensus::encode;
 use bitcoin::hex::FromHex as _;
 use bitcoin::opcodes::all::*;
 use bitcoin::{
-    absolute, consensus, hex, psbt, script, transaction, Amount, ScriptBuf, Transaction, TxOut,
+    absolute, consensus, hex, psbt, script, transaction, Amount, FeeRate, ScriptBuf, Sequence,
+    Transaction, TxOut,
 };
 use integration_test::{test_keys, Node, NodeExt as _, Wallet};
+use node::client::client_sync::broadcast::{BroadcastError, RejectCategory};
+use node::client::client_sync::cpfp::Submission;
+use node::client::client_sync::{Error, RpcErrorCode};
 use node::vtype::*;
 use node::{mtype, Input, Output}; // All the version specific types.
 
@@ -28,6 +34,73 @@ fn raw_transactions__analyze_psbt__modelled() {
     model.unwrap();
 }
 
+#[test]
+fn raw_transactions__broadcast_checked() {
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+
+    // Not yet final, the lock time is far in the future.
+    let mut tx = create_a_raw_transaction(&node);
+    tx.lock_time = absolute::LockTime::from_height(10_000).unwrap();
+    tx.input[0].sequence = Sequence::ENABLE_LOCKTIME_NO_RBF;
+    let non_final = sign(&node, &tx);
+    match node.client.broadcast_checked(&non_final) {
+        Err(BroadcastError::Rejected(r)) => assert_eq!(r.category, RejectCategory::NonFinal),
+        res => panic!("expected rejection, got: {:?}", res),
+    }
+
+    tx.lock_time = absolute::LockTime::ZERO;
+    let tx = sign(&node, &tx);
+    let txid = node.client.broadcast_checked(&tx).expect("broadcast_checked");
+    assert_eq!(txid, tx.compute_txid());
+
+    // Broadcasting again is rejected as the transaction is already in the mempool.
+    match node.client.broadcast_checked(&tx) {
+        Err(BroadcastError::Rejected(r)) => assert_eq!(r.category, RejectCategory::AlreadyKnown),
+        res => panic!("expected rejection, got: {:?}", res),
+    }
+
+    fn sign(node: &Node, tx: &Transaction) -> Transaction {
+        node.client
+            .sign_raw_transaction_with_wallet(tx)
+            .expect("signrawtransactionwithwallet")
+            .into_model()
+            .expect("SignRawTransactionWithWallet into model")
+            .tx
+    }
+}
+
+#[test]
+fn raw_transactions__cpfp() {
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+
+    let (addr, txid) = node.create_mempool_transaction();
+    let parent = node
+        .client
+        .get_raw_transaction(txid)
+        .expect("getrawtransaction")
+        .transaction()
+        .expect("GetRawTransaction into transaction");
+    let vout = parent
+        .output
+        .iter()
+        .position(|txout| txout.script_pubkey == addr.script_pubkey())
+        .expect("output to addr") as u32;
+
+    let package_fee_rate = FeeRate::from_sat_per_vb(50).unwrap();
+    let package = node.client.cpfp(&parent, vout, package_fee_rate).expect("cpfp");
+    assert_eq!(package.parent_txid, txid);
+    assert!(package.package_fee_rate >= package_fee_rate);
+    #[cfg(feature = "v25_and_below")]
+    assert_eq!(package.submission, Submission::Sequential);
+    #[cfg(not(feature = "v25_and_below"))]
+    assert_eq!(package.submission, Submission::Package);
+
+    let child_txid = package.child.compute_txid();
+    node.client.get_mempool_entry(child_txid).expect("child in mempool");
+}
+
 #[test]
 fn raw_transactions__combine_psbt__modelled() {
     let node = Node::with_wallet(Wallet::Default, &[]);
@@ -269,6 +342,66 @@ fn raw_transactions__fund_raw_transaction__modelled() {
     create_fund_sign_send(&node);
 }
 
+#[test]
+fn raw_transactions__fund_raw_transaction_with_options__modelled() {
+    use node::FundRawTransactionOptions;
+
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+    let (_, _, txid, _, vout) = create_utxo(&node);
+
+    // Spend more than the input so that the wallet has to select another one.
+    let inputs = vec![Input { txid, vout, sequence: None }];
+    let address = node.client.new_address().expect("newaddress");
+    let outputs = vec![Output::new(address, Amount::from_sat(2_000_000))];
+    let tx = node
+        .client
+        .create_raw_transaction(&inputs, &outputs)
+        .expect("createrawtransaction")
+        .transaction()
+        .unwrap();
+
+    let change = node.client.new_address().expect("newaddress");
+    let options = FundRawTransactionOptions {
+        change_address: Some(change.clone()),
+        change_position: Some(0),
+        fee_rate: Some(FeeRate::from_sat_per_vb(10).unwrap()),
+        lock_unspents: Some(true),
+        ..Default::default()
+    };
+    let json: FundRawTransaction =
+        node.client.fund_raw_transaction_with_options(&tx, &options).expect("fundrawtransaction");
+    let model: Result<mtype::FundRawTransaction, FundRawTransactionError> = json.into_model();
+    let funded = model.unwrap();
+
+    assert_eq!(funded.change_position, 0);
+    assert_eq!(funded.tx.output[0].script_pubkey, change.script_pubkey());
+    assert_eq!(funded.tx.output[1].value, Amount::from_sat(2_000_000));
+
+    // The selected inputs are locked.
+    let locked = node.client.list_lock_unspent().expect("listlockunspent").0;
+    assert!(!locked.is_empty());
+    for utxo in locked {
+        assert!(funded
+            .tx
+            .input
+            .iter()
+            .any(|input| input.previous_output.txid.to_string() == utxo.txid
+                && i64::from(input.previous_output.vout) == utxo.vout));
+    }
+
+    // The fee is estimated for the signed transaction.
+    let signed = node
+        .client
+        .sign_raw_transaction_with_wallet(&funded.tx)
+        .expect("signrawtransactionwithwallet")
+        .into_model()
+        .unwrap()
+        .tx;
+    let fee_rate = funded.fee.to_sat() as f64 / signed.vsize() as f64;
+    assert!((10.0..10.5).contains(&fee_rate), "fee rate: {}", fee_rate);
+}
+
 #[test]
 fn raw_transactions__send_raw_transaction__modelled() {
     let node = Node::with_wallet(Wallet::Default, &[]);
@@ -276,6 +409,17 @@ fn raw_transactions__send_raw_transaction__modelled() {
     create_sign_send(&node); // Calls `sendrawtransaction`.
 }
 
+#[test]
+fn raw_transactions__send_raw_transaction__already_in_chain() {
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+    let (_, tx) = node.create_mined_transaction();
+
+    let err = node.client.send_raw_transaction(&tx).unwrap_err();
+    assert!(err.is_tx_already_in_chain(), "unexpected error: {}", err);
+    assert_eq!(err.rpc_error_code(), Some(RpcErrorCode::VerifyAlreadyInChain));
+}
+
 #[test]
 fn raw_transactions__get_raw_transaction__modelled() {
     let node = Node::with_wallet(Wallet::Default, &["-txindex"]);
@@ -422,6 +566,59 @@ fn raw_transactions__test_mempool_accept__modelled() {
     assert!(res.allowed, "fresh signed tx should be allowed");
 }
 
+#[test]
+#[cfg(not(feature = "v21_and_below"))]
+fn raw_transactions__test_mempool_accept__package() {
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+    let tx = create_a_raw_transaction(&node);
+    let signed: SignRawTransactionWithWallet =
+        node.client.sign_raw_transaction_with_wallet(&tx).expect("signrawtransactionwithwallet");
+    let signed_tx = signed.into_model().expect("SignRawTransaction into model").tx;
+
+    // The transactions of a package must not conflict with each other.
+    let json: TestMempoolAccept = node
+        .client
+        .test_mempool_accept(&[signed_tx.clone(), signed_tx.clone()])
+        .expect("testmempoolaccept");
+    let model: Result<mtype::TestMempoolAccept, TestMempoolAcceptError> = json.into_model();
+    let test_mempool = model.unwrap();
+
+    assert_eq!(test_mempool.results.len(), 2);
+    for res in &test_mempool.results {
+        assert_eq!(res.txid, signed_tx.compute_txid());
+        assert!(!res.allowed);
+        assert!(res.package_error.is_some());
+    }
+}
+
+#[test]
+#[cfg(not(feature = "v18_and_below"))]
+fn raw_transactions__test_mempool_accept_with_max_fee_rate() {
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+    let tx = create_a_raw_transaction(&node); // Pays more than 1 sat/vB.
+    let signed: SignRawTransactionWithWallet =
+        node.client.sign_raw_transaction_with_wallet(&tx).expect("signrawtransactionwithwallet");
+    let signed_tx = signed.into_model().expect("SignRawTransaction into model").tx;
+    let txs = std::slice::from_ref(&signed_tx);
+
+    let max_fee_rate = FeeRate::from_sat_per_vb(1).unwrap();
+    let json: TestMempoolAccept = node
+        .client
+        .test_mempool_accept_with_max_fee_rate(txs, max_fee_rate)
+        .expect("testmempoolaccept");
+    assert!(!json.0[0].allowed);
+    assert!(json.0[0].reject_reason.is_some());
+
+    // A zero fee rate accepts any fee rate.
+    let json: TestMempoolAccept = node
+        .client
+        .test_mempool_accept_with_max_fee_rate(txs, FeeRate::ZERO)
+        .expect("testmempoolaccept");
+    assert!(json.0[0].allowed);
+}
+
 #[test]
 #[cfg(not(feature = "v17"))]
 fn raw_transactions__utxo_update_psbt__modelled() {
@@ -436,6 +633,34 @@ fn raw_transactions__utxo_update_psbt__modelled() {
     assert!(update_psbts.0.inputs.len() >= psbt.inputs.len());
 }
 
+#[test]
+fn raw_transactions__wait_for_confirmations() {
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+    let (_, txid) = node.create_mempool_transaction();
+    let timeout = Duration::from_secs(1);
+
+    let res = node.client.wait_for_confirmations(txid, 1, timeout);
+    assert!(matches!(res, Err(Error::Timeout)));
+
+    node.mine_a_block();
+    let (hash, height) = node.client.wait_for_confirmations(txid, 1, timeout).expect("confirmed");
+    assert_eq!(hash, node.client.best_block_hash().expect("best_block_hash"));
+    assert_eq!(height, 102);
+
+    // Reorg the confirming block out, the transaction goes back to the mempool.
+    node.client.invalidate_block(hash).expect("invalidateblock");
+    let res = node.client.wait_for_confirmations(txid, 1, timeout);
+    assert!(matches!(res, Err(Error::Timeout)));
+
+    node.mine_a_block();
+    node.mine_a_block();
+    let (new_hash, new_height) =
+        node.client.wait_for_confirmations(txid, 2, timeout).expect("confirmed");
+    assert_ne!(new_hash, hash);
+    assert_eq!(new_height, 102);
+}
+
 // Manipulates raw transactions.
 //
 // Calls the following RPC methods:
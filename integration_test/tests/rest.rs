@@ -0,0 +1,79 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Tests for the REST interface client.
+
+#![allow(non_snake_case)] // Test names intentionally use double underscore.
+#![allow(unused_imports)] // Because of feature gated tests.
+
+use bitcoin::hashes::Hash as _;
+use client::rest;
+use integration_test::{Node, NodeExt as _, Wallet};
+use node::vtype::*; // All the version specific types.
+
+fn rest_client(node: &Node) -> rest::Client { rest::Client::new(&node.rpc_url()) }
+
+#[test]
+fn rest__get_block() {
+    let node = Node::with_wallet(Wallet::Default, &["-rest"]);
+    node.mine_a_block();
+    let rest = rest_client(&node);
+
+    let hash = node.client.best_block_hash().expect("best_block_hash");
+    let block = rest.get_block(hash).expect("rest block").0;
+    assert_eq!(block, node.client.get_block(hash).expect("getblock"));
+
+    let missing = bitcoin::BlockHash::from_byte_array([0xff; 32]);
+    let err = rest.get_block(missing).unwrap_err();
+    assert!(err.is_not_found(), "unexpected error: {}", err);
+}
+
+#[test]
+fn rest__get_raw_transaction() {
+    let node = Node::with_wallet(Wallet::Default, &["-rest"]);
+    node.fund_wallet();
+    let (_, txid) = node.create_mempool_transaction();
+
+    let tx = rest_client(&node).get_raw_transaction(txid).expect("rest tx").0;
+    assert_eq!(tx.compute_txid(), txid);
+}
+
+#[test]
+fn rest__get_block_headers() {
+    let node = Node::with_wallet(Wallet::Default, &["-rest"]);
+    node.fund_wallet();
+    let rest = rest_client(&node);
+
+    let genesis = node.client.get_block_hash(0).expect("getblockhash").block_hash().unwrap();
+    let headers = rest.get_block_headers(genesis, 10).expect("rest headers");
+    assert_eq!(headers.len(), 10);
+    assert_eq!(headers[0].0.block_hash(), genesis);
+    for pair in headers.windows(2) {
+        assert_eq!(pair[1].0.prev_blockhash, pair[0].0.block_hash());
+    }
+
+    // Only the headers up to the tip are returned.
+    let tip = node.client.best_block_hash().expect("best_block_hash");
+    let headers = rest.get_block_headers(tip, 10).expect("rest headers");
+    assert_eq!(headers.len(), 1);
+}
+
+#[test]
+#[cfg(not(feature = "v18_and_below"))]
+fn rest__get_block_hash() {
+    let node = Node::with_wallet(Wallet::Default, &["-rest"]);
+    node.mine_a_block();
+
+    let hash = rest_client(&node).get_block_hash(1).expect("rest blockhashbyheight").0;
+    let want = node.client.get_block_hash(1).expect("getblockhash").block_hash().unwrap();
+    assert_eq!(hash, want);
+}
+
+#[test]
+fn rest__get_blockchain_info() {
+    let node = Node::with_wallet(Wallet::None, &["-rest"]);
+
+    let json: GetBlockchainInfo = rest_client(&node).get_blockchain_info().expect("rest chaininfo");
+    let model = json.into_model().expect("into_model");
+    assert_eq!(model.chain, bitcoin::Network::Regtest);
+    assert_eq!(model.best_block_hash, node.client.best_block_hash().expect("best_block_hash"));
+}
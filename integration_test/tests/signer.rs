@@ -15,7 +15,7 @@ use node::{mtype, Input, Output}; // All the version specific types.
 fn signer__enumerate_signers() {
     use std::os::unix::fs::PermissionsExt;
 
-    let script_path = integration_test::random_tmp_file();
+    let script_path = integration_test::tmp_file();
     // `script_body` is minimal JSON array expected by `enumeratesigners` RPC: an array
     // of signer objects with at least a fingerprint and name. Using a hard-coded
     // dummy signer (fingerprint "deadbeef").
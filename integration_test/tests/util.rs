@@ -75,13 +75,21 @@ fn util__estimate_smart_fee__modelled() {
 
 #[test]
 #[cfg(not(feature = "v17"))]
-fn util__get_descriptor_info() {
+fn util__get_descriptor_info__modelled() {
     let node = Node::with_wallet(Wallet::Default, &[]);
 
     // Use a valid, deterministic public key from the pubkey_sort test vectors
     let descriptor = "pkh(02ff12471208c14bd580709cb2358d98975247d8765f92bc25eab3b2763ed605f8)";
-    let _: GetDescriptorInfo =
+    let json: GetDescriptorInfo =
         node.client.get_descriptor_info(descriptor).expect("getdescriptorinfo");
+    let model: mtype::GetDescriptorInfo = json.into_model();
+
+    assert!(model.descriptor.starts_with(descriptor));
+    assert!(!model.is_range);
+    assert!(model.is_solvable);
+    assert!(!model.has_private_keys);
+    #[cfg(not(feature = "v18_and_below"))]
+    assert!(model.checksum.is_some());
 }
 
 #[test]
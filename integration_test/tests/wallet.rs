@@ -13,15 +13,21 @@ use bitcoin::address::{self, Address, KnownHrp, NetworkChecked};
 use bitcoin::bip32::{Xpriv, Xpub};
 use bitcoin::{
     amount, hex, key, psbt, secp256k1, sign_message, Amount, CompressedPublicKey, FeeRate, Network,
-    PrivateKey, PublicKey,
+    PrivateKey, PublicKey, SignedAmount,
 };
 use integration_test::{Node, NodeExt as _, Wallet};
+#[cfg(unix)]
+use node::client::client_sync::external_signer::ExternalSigner;
+use node::client::client_sync::psbt::{PsbtPipelineError, PsbtStage};
+use node::client::client_sync::rbf::{ReplaceByFeeError, Replacement};
+use node::client::client_sync::wallet_sync::{WalletEvent, WalletSync};
+use node::client::client_sync::{Auth, RpcErrorCode};
 use node::vtype::*; // All the version specific types.
 #[cfg(not(feature = "v20_and_below"))]
 use node::ImportDescriptorsRequest;
 use node::{
-    mtype, AddressType, ImportMultiRequest, ImportMultiScriptPubKey, ImportMultiTimestamp,
-    WalletCreateFundedPsbtInput,
+    mtype, AddressType, Client, ImportMultiRequest, ImportMultiScriptPubKey, ImportMultiTimestamp,
+    Input, Output, SighashType, WalletCreateFundedPsbtInput, WalletCreateFundedPsbtOptions,
 };
 
 #[test]
@@ -91,7 +97,7 @@ fn wallet__backup_wallet() { backup_and_restore_wallet() }
 
 fn backup_and_restore_wallet() {
     let node = Node::with_wallet(Wallet::Default, &[]);
-    let file_path = integration_test::random_tmp_file();
+    let file_path = integration_test::tmp_file();
 
     let _: () = node.client.backup_wallet(&file_path).expect("backupwallet");
     assert!(file_path.exists(), "Backup file should exist at destination");
@@ -128,6 +134,68 @@ fn wallet__bump_fee__modelled() {
     model.unwrap();
 }
 
+#[test]
+#[cfg(feature = "v20_and_below")]
+fn wallet__bump_fee__fee_rate_string() {
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+    let address = node.client.new_address().expect("failed to create new address");
+
+    let txid = node
+        .client
+        .send_to_address_rbf(&address, Amount::from_sat(10_000))
+        .expect("sendtoaddress")
+        .txid()
+        .unwrap();
+
+    // Up to v20 `feeRate` is in BTC/kvB, sent as a decimal string.
+    let options = node::serde_json::json!({ "feeRate": "0.00050000" });
+    let json: BumpFee =
+        node.client.call("bumpfee", &[txid.to_string().into(), options]).expect("bumpfee");
+    let model: mtype::BumpFee = json.into_model().expect("BumpFee into model");
+
+    let tx = node
+        .client
+        .get_raw_transaction(model.txid)
+        .expect("getrawtransaction")
+        .transaction()
+        .expect("GetRawTransaction into transaction");
+    let fee_rate = model.fee.to_sat() / tx.vsize() as u64;
+    assert!((50..55).contains(&fee_rate), "fee rate {} sat/vB", fee_rate);
+}
+
+#[test]
+fn wallet__replace_by_fee() {
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+    let address = node.client.new_address().expect("failed to create new address");
+
+    let txid = node
+        .client
+        .send_to_address_rbf(&address, Amount::from_sat(10_000))
+        .expect("sendtoaddress")
+        .txid()
+        .unwrap();
+
+    let fee_rate = FeeRate::from_sat_per_vb(50).unwrap();
+    let replacement = node.client.replace_by_fee(txid, fee_rate).expect("replace_by_fee");
+    let new_txid = match replacement {
+        Replacement::Txid(txid) => txid,
+        Replacement::Psbt(_) => panic!("expected the wallet to sign the replacement"),
+    };
+    assert_ne!(new_txid, txid);
+
+    node.mine_a_block();
+    match node.client.replace_by_fee(new_txid, fee_rate) {
+        Err(ReplaceByFeeError::AlreadyConfirmed { confirmations }) => assert_eq!(confirmations, 1),
+        res => panic!("expected already confirmed error, got: {:?}", res),
+    }
+    match node.client.replace_by_fee(txid, fee_rate) {
+        Err(ReplaceByFeeError::Conflicted) => {}
+        res => panic!("expected conflicted error, got: {:?}", res),
+    }
+}
+
 #[test]
 fn wallet__create_wallet__modelled() {
     // Implicitly tests `createwallet` because we create the default wallet.
@@ -201,6 +269,13 @@ fn wallet__dump_priv_key__modelled() {
     }
 }
 
+integration_test::versioned_test! {
+    26..=29 => fn wallet__deprecation__legacy_wallet() {
+        assert!(Client::deprecation("dumpwallet").is_some());
+        assert!(Client::deprecation("importdescriptors").is_none());
+    }
+}
+
 #[test]
 #[cfg(feature = "v29_and_below")]
 fn wallet__dump_wallet() {
@@ -211,7 +286,7 @@ fn wallet__dump_wallet() {
         let node = Node::with_wallet(Wallet::None, &[]);
 
         node.client.create_legacy_wallet("legacy_wallet").expect("legacy create_wallet");
-        let out = integration_test::random_tmp_file();
+        let out = integration_test::tmp_file();
 
         let _: DumpWallet = node.client.dump_wallet(&out).expect("dumpwallet");
     }
@@ -219,7 +294,7 @@ fn wallet__dump_wallet() {
     #[cfg(feature = "v22_and_below")]
     {
         let node = Node::with_wallet(Wallet::Default, &[]);
-        let out = integration_test::random_tmp_file();
+        let out = integration_test::tmp_file();
 
         let _: DumpWallet = node.client.dump_wallet(&out).expect("dumpwallet");
     }
@@ -296,6 +371,27 @@ fn wallet__get_balance__modelled() {
     model.unwrap();
 }
 
+#[test]
+fn wallet__fund_wallet_with() {
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    let balance = || node.client.get_balance().expect("getbalance").into_model().unwrap().0;
+    let height = || node.client.get_block_count().expect("getblockcount").0;
+
+    // Three block rewards of 50 BTC, the last one matured by 100 blocks on top of it.
+    node.fund_wallet_with(Amount::from_int_btc(120));
+    assert_eq!(balance(), Amount::from_int_btc(150));
+    assert_eq!(height(), 103);
+
+    // Already funded.
+    node.fund_wallet_with(Amount::from_int_btc(100));
+    assert_eq!(height(), 103);
+
+    node.mine_a_block();
+    node.mature_coinbases();
+    assert_eq!(balance(), Amount::from_int_btc(200));
+    assert_eq!(height(), 204);
+}
+
 #[test]
 #[cfg(not(feature = "v18_and_below"))]
 fn wallet__get_balances() {
@@ -333,6 +429,39 @@ fn wallet__get_new_address__modelled() {
     let _ = node.client.new_address_with_type(AddressType::Bech32).unwrap();
 }
 
+#[test]
+#[cfg(not(feature = "v22_and_below"))] // Bech32m addresses were added in v23.
+fn wallet__get_new_address__bech32m() {
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+
+    let (address, txid) = node.create_taproot_transaction();
+    assert_eq!(address.address_type(), Some(address::AddressType::P2tr));
+    node.client.get_mempool_entry(txid).expect("getmempoolentry");
+
+    // A wallet with only tr() descriptors can receive and spend.
+    let taproot = node.create_taproot_wallet("taproot");
+    let address =
+        taproot.new_address_with_type(AddressType::Bech32m).expect("getnewaddress bech32m");
+    assert_eq!(address.address_type(), Some(address::AddressType::P2tr));
+    let _ =
+        node.client.send_to_address(&address, Amount::from_sat(100_000)).expect("sendtoaddress");
+    node.mine_a_block();
+    let balance = taproot.get_balance().expect("getbalance").into_model().unwrap().0;
+    assert_eq!(balance, Amount::from_sat(100_000));
+
+    // P2TR outputs can be built without a wallet.
+    let address = integration_test::test_taproot_address();
+    let txid = taproot
+        .send_to_address(&address, Amount::from_sat(10_000))
+        .expect("sendtoaddress")
+        .txid()
+        .unwrap();
+    let tx =
+        node.client.get_raw_transaction(txid).expect("getrawtransaction").transaction().unwrap();
+    assert!(tx.output.iter().any(|txout| txout.script_pubkey == address.script_pubkey()));
+}
+
 #[test]
 fn wallet__get_raw_change_address__modelled() {
     let node = Node::with_wallet(Wallet::Default, &[]);
@@ -463,7 +592,7 @@ fn wallet__import_address() {
 
 #[test]
 #[cfg(not(feature = "v20_and_below"))]
-fn wallet__import_descriptors() {
+fn wallet__import_descriptors__modelled() {
     let node = Node::with_wallet(Wallet::None, &[]);
     let wallet_name = "desc_wallet";
 
@@ -500,10 +629,73 @@ fn wallet__import_descriptors() {
 
     // 5. Scan for the descriptor using the time from (1)
     let request = ImportDescriptorsRequest::new(descriptor, start_time);
-    let result: ImportDescriptors =
+    let json: ImportDescriptors =
         node.client.import_descriptors(&[request]).expect("importdescriptors");
+    let model: Result<mtype::ImportDescriptors, ImportDescriptorsError> = json.into_model();
+    let result = model.unwrap();
     assert_eq!(result.0.len(), 1, "should have exactly one import result");
     assert!(result.0[0].success);
+    assert!(result.0[0].error.is_none());
+
+    // An invalid descriptor fails with an error.
+    let request = ImportDescriptorsRequest::new("wpkh(invalid)", start_time);
+    let json: ImportDescriptors =
+        node.client.import_descriptors(&[request]).expect("importdescriptors");
+    let result = json.into_model().unwrap();
+    assert!(!result.0[0].success);
+    assert!(result.0[0].error.as_ref().is_some_and(|e| !e.message.is_empty()));
+}
+
+#[test]
+#[cfg(not(feature = "v20_and_below"))]
+fn wallet__import_descriptor_and_rescan() {
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+    let wallet_name = "desc_wallet";
+
+    #[cfg(feature = "v22_and_below")]
+    node.client.create_descriptor_wallet(wallet_name).expect("create descriptor wallet");
+
+    // v23 onwards uses descriptor wallets by default.
+    #[cfg(not(feature = "v22_and_below"))]
+    node.client.create_wallet(wallet_name).expect("create wallet");
+
+    let auth = Auth::CookieFile(node.params.cookie_file.clone());
+    let client =
+        Client::new_with_auth(&node.rpc_url_with_wallet(wallet_name), auth).expect("client");
+
+    let start_time =
+        SystemTime::now().duration_since(UNIX_EPOCH).expect("failed to get current time").as_secs();
+
+    // Send to an address of a known private key from the default wallet and confirm it.
+    let privkey =
+        PrivateKey::from_wif("cVt4o7BGAig1UXywgGSmARhxMdzP5qvQsxKkSsc1XEkw3tDTQFpy").unwrap();
+    let secp = secp256k1::Secp256k1::new();
+    let pubkey = privkey.public_key(&secp);
+    let address = Address::p2wpkh(&CompressedPublicKey(pubkey.inner), KnownHrp::Regtest);
+    let txid = node
+        .client
+        .send_to_address(&address, Amount::from_sat(10_000))
+        .expect("sendtoaddress")
+        .txid()
+        .unwrap();
+    node.mine_a_block();
+
+    let raw_descriptor = format!("wpkh({})", privkey.to_wif());
+    let info = client.get_descriptor_info(&raw_descriptor).expect("get_descriptor_info");
+    let descriptor = format!("{}#{}", raw_descriptor, info.checksum);
+
+    let found = client
+        .import_descriptor_and_rescan(&descriptor, start_time)
+        .expect("import_descriptor_and_rescan");
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].txid, txid.to_string());
+
+    // Nothing new is found when importing again.
+    let found = client
+        .import_descriptor_and_rescan(&descriptor, start_time)
+        .expect("import_descriptor_and_rescan");
+    assert!(found.is_empty());
 }
 
 #[test]
@@ -536,7 +728,7 @@ fn wallet__import_wallet() {
     };
 
     node.client.new_address().expect("newaddress");
-    let dump_file_path = integration_test::random_tmp_file();
+    let dump_file_path = integration_test::tmp_file();
 
     node.client.dump_wallet(&dump_file_path).expect("dumpwallet");
     assert!(dump_file_path.exists());
@@ -635,6 +827,45 @@ fn wallet__list_since_block__modelled() {
     assert_eq!(first_tx.txid.unwrap().to_string().len(), 64);
 }
 
+#[test]
+fn wallet__sync_wallet() {
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+
+    let mut sync = WalletSync::new(6);
+    let events = node.client.sync_wallet(&mut sync).expect("initial sync");
+    assert!(!events.is_empty());
+    assert!(sync.last_block().is_some());
+
+    let (_, txid) = node.create_mempool_transaction();
+    let events = node.client.sync_wallet(&mut sync).expect("sync mempool tx");
+    assert!(events.contains(&WalletEvent::Added { txid }));
+    assert!(!events
+        .iter()
+        .any(|e| matches!(e, WalletEvent::Confirmed { txid: t, .. } if *t == txid)));
+
+    // Nothing changed, nothing to report.
+    let events = node.client.sync_wallet(&mut sync).expect("sync again");
+    assert!(events.is_empty());
+
+    node.mine_a_block();
+    let block_hash = node.client.best_block_hash().expect("best_block_hash");
+    let events = node.client.sync_wallet(&mut sync).expect("sync mined tx");
+    assert!(events.contains(&WalletEvent::Confirmed { txid, block_hash }));
+    assert!(!events.contains(&WalletEvent::Added { txid }));
+
+    // Reorg the confirming block out, the transaction goes back to the mempool.
+    node.client.invalidate_block(block_hash).expect("invalidateblock");
+    let events = node.client.sync_wallet(&mut sync).expect("sync after reorg");
+    assert!(events.contains(&WalletEvent::Removed { txid, block_hash }));
+    assert!(!events.contains(&WalletEvent::Added { txid }));
+
+    node.mine_a_block();
+    let block_hash = node.client.best_block_hash().expect("best_block_hash");
+    let events = node.client.sync_wallet(&mut sync).expect("sync re-mined tx");
+    assert!(events.contains(&WalletEvent::Confirmed { txid, block_hash }));
+}
+
 #[test]
 fn wallet__list_transactions__modelled() {
     let node = Node::with_wallet(Wallet::Default, &[]);
@@ -653,6 +884,28 @@ fn wallet__list_transactions__modelled() {
     assert_eq!(first_tx.txid.unwrap().to_string().len(), 64);
 }
 
+#[test]
+fn wallet__list_transactions_paged() {
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+
+    let all: ListTransactions =
+        node.client.call("listtransactions", &["*".into(), 1000.into()]).expect("listtransactions");
+    let txids =
+        |items: &[TransactionItem]| items.iter().map(|tx| tx.txid.clone()).collect::<Vec<_>>();
+
+    let paged = node
+        .client
+        .list_transactions_paged(7)
+        .collect::<Result<Vec<_>, _>>()
+        .expect("list_transactions_paged");
+
+    let mut want = txids(&all.0);
+    want.reverse();
+    assert_eq!(txids(&paged), want);
+    assert!(paged.len() > 7);
+}
+
 #[test]
 #[cfg(feature = "v29_and_below")]
 fn wallet__import_multi() {
@@ -760,7 +1013,7 @@ fn wallet__import_pubkey() {
 
 #[test]
 #[cfg(not(feature = "v21_and_below"))]
-fn wallet__list_descriptors() {
+fn wallet__list_descriptors__modelled() {
     let node = Node::with_wallet(Wallet::None, &[]);
     let wallet_name = "desc_wallet";
 
@@ -772,8 +1025,11 @@ fn wallet__list_descriptors() {
     node.client.create_wallet(wallet_name).expect("create wallet");
 
     let json: ListDescriptors = node.client.list_descriptors().expect("listdescriptors");
+    let model: mtype::ListDescriptors = json.into_model();
 
-    let has_descriptor = json.descriptors.iter().any(|desc_info| {
+    assert_eq!(model.wallet_name, wallet_name);
+    assert!(model.descriptors.iter().all(|desc_info| desc_info.next_index.is_some()));
+    let has_descriptor = model.descriptors.iter().any(|desc_info| {
         desc_info.descriptor.starts_with("wpkh(") || desc_info.descriptor.starts_with("pkh(")
     });
     assert!(has_descriptor, "No standard descriptors found in listdescriptors result");
@@ -883,6 +1139,28 @@ fn wallet__new_keypool() {
     let _: () = node.client.new_keypool().expect("newkeypool");
 }
 
+#[test]
+fn wallet__preview_send() {
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+
+    let addr = node.client.new_address().expect("newaddress");
+    let amount = Amount::from_sat(100_000);
+    let outputs = BTreeMap::from([(addr, amount)]);
+    let preview = node.client.preview_send(vec![], vec![outputs]).expect("preview_send");
+
+    let input_total: Amount = preview.inputs.iter().map(|input| input.amount).sum();
+    let change = preview.change.as_ref().expect("change output").amount;
+    assert_eq!(input_total, amount + change + preview.fee);
+
+    #[cfg(not(feature = "v17"))]
+    assert!(preview.effective_fee_rate.expect("fee rate") > FeeRate::ZERO);
+
+    // Nothing was broadcast.
+    let mempool = node.client.get_raw_mempool().expect("getrawmempool");
+    assert!(mempool.0.is_empty());
+}
+
 #[test]
 #[cfg(not(feature = "v20_and_below"))]
 fn wallet__psbt_bump_fee__modelled() {
@@ -945,6 +1223,70 @@ fn wallet__sign_raw_transaction_with_wallet__modelled() {}
 #[test]
 fn wallet__unload_wallet() { create_load_unload_wallet(); }
 
+#[test]
+fn wallet__unload_wallet__wallet_not_loaded() {
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.client.unload_wallet("default").expect("unloadwallet");
+
+    let err = node.client.get_balance().unwrap_err();
+    assert!(err.is_wallet_not_loaded(), "unexpected error: {}", err);
+    assert_eq!(err.rpc_error_code(), Some(RpcErrorCode::WalletNotFound));
+    assert!(!err.is_warmup());
+}
+
+#[test]
+fn wallet__unload_wallet__auto_load_wallet() {
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    let client = Client::new_with_auth(
+        &node.rpc_url_with_wallet("default"),
+        Auth::CookieFile(node.params.cookie_file.clone()),
+    )
+    .expect("failed to create client")
+    .auto_load_wallet("default");
+    node.client.unload_wallet("default").expect("unloadwallet");
+
+    let _: GetBalance = client.get_balance().expect("getbalance, loading the wallet");
+
+    // The wallet is only loaded if it exists.
+    let client = Client::new_with_auth(
+        &node.rpc_url_with_wallet("missing"),
+        Auth::CookieFile(node.params.cookie_file.clone()),
+    )
+    .expect("failed to create client")
+    .auto_load_wallet("missing");
+    let err = client.get_balance().unwrap_err();
+    assert!(err.is_wallet_not_loaded(), "unexpected error: {}", err);
+}
+
+#[test]
+fn wallet__wallet_manager() {
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.client.create_wallet("other").expect("createwallet");
+    node.client.unload_wallet("other").expect("unloadwallet");
+
+    let auth = Auth::CookieFile(node.params.cookie_file.clone());
+    let manager = Client::wallet_manager(&node.rpc_url(), auth).expect("wallet_manager");
+    assert_eq!(manager.list_wallets().expect("listwallets"), vec!["default".to_owned()]);
+
+    // The wallet is loaded the first time it is used.
+    let _: GetBalance = manager.wallet("other").expect("wallet").get_balance().expect("getbalance");
+    let mut wallets = manager.list_wallets().expect("listwallets");
+    wallets.sort();
+    assert_eq!(wallets, vec!["default".to_owned(), "other".to_owned()]);
+
+    // And loaded again if it is unloaded, e.g. by a node restart.
+    node.client.unload_wallet("other").expect("unloadwallet");
+    let _: GetBalance = manager.with_wallet("other", Client::get_balance).expect("getbalance");
+
+    let address = manager.wallet("default").expect("wallet").new_address().expect("address");
+    let info: node::serde_json::Value =
+        manager.call("default", "getaddressinfo", &[address.to_string().into()]).expect("call");
+    assert_eq!(info["ismine"], true);
+    let info: node::serde_json::Value =
+        manager.call("other", "getaddressinfo", &[address.to_string().into()]).expect("call");
+    assert_eq!(info["ismine"], false);
+}
+
 #[test]
 fn wallet__send_many__modelled() {
     let node = Node::with_wallet(Wallet::Default, &[]);
@@ -981,13 +1323,49 @@ fn wallet__send__modelled() {
     let address = node.client.new_address().expect("failed to create new address");
 
     let mut outputs = BTreeMap::new();
-    outputs.insert(address.to_string(), 0.001);
+    outputs.insert(address, Amount::from_sat(100_000));
 
     let json: Send = node.client.send(&outputs).expect("send");
     let model: Result<mtype::Send, SendError> = json.into_model();
     model.unwrap();
 }
 
+#[test]
+#[cfg(not(feature = "v20_and_below"))]
+fn wallet__send_with_options__modelled() {
+    use std::collections::BTreeMap;
+
+    use node::SendOptions;
+
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+    let address = node.client.new_address().expect("failed to create new address");
+    let change_address = node.client.new_address().expect("failed to create change address");
+
+    let mut outputs = BTreeMap::new();
+    outputs.insert(address.clone(), Amount::from_sat(100_000));
+    let options = SendOptions {
+        add_to_wallet: Some(false),
+        change_address: Some(change_address.clone()),
+        fee_rate: Some(FeeRate::from_sat_per_vb(5).unwrap()),
+        subtract_fee_from_outputs: Some(vec![0]),
+        ..Default::default()
+    };
+
+    let json: Send = node.client.send_with_options(&outputs, &options).expect("send");
+    let model: mtype::Send = json.into_model().unwrap();
+    assert!(model.complete);
+    let tx = model.hex.expect("add_to_wallet is false");
+
+    // The fee is paid by the recipient and the change goes to the change address.
+    let payment = tx.output.iter().find(|out| out.script_pubkey == address.script_pubkey());
+    assert!(payment.expect("payment output").value < Amount::from_sat(100_000));
+    assert!(tx.output.iter().any(|out| out.script_pubkey == change_address.script_pubkey()));
+
+    let mempool = node.client.get_raw_mempool().expect("getrawmempool").into_model().unwrap();
+    assert!(mempool.0.is_empty());
+}
+
 #[test]
 #[cfg(not(feature = "v23_and_below"))]
 fn wallet__send_all__modelled() {
@@ -1000,6 +1378,42 @@ fn wallet__send_all__modelled() {
     model.unwrap();
 }
 
+#[test]
+#[cfg(not(feature = "v23_and_below"))]
+fn wallet__send_all_with_options__modelled() {
+    use node::{Input, SendAllOptions};
+
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+    let address = node.client.new_address().expect("failed to create new address");
+
+    let unspent = node.client.list_unspent().expect("listunspent").into_model().unwrap();
+    let utxo = &unspent.0[0];
+    let options = SendAllOptions {
+        add_to_wallet: Some(false),
+        fee_rate: Some(FeeRate::from_sat_per_vb(2).unwrap()),
+        inputs: Some(vec![Input { txid: utxo.txid, vout: utxo.vout.into(), sequence: None }]),
+        ..Default::default()
+    };
+
+    let script_pubkey = address.script_pubkey();
+    let json: SendAll = node.client.send_all_with_options(&[address], &options).expect("sendall");
+    let model: mtype::SendAll = json.into_model().unwrap();
+    assert!(model.complete);
+    let tx = model.hex.expect("add_to_wallet is false");
+
+    // Only the given input is spent, with no change.
+    assert_eq!(tx.input.len(), 1);
+    assert_eq!(tx.input[0].previous_output.txid, utxo.txid);
+    assert_eq!(tx.input[0].previous_output.vout, utxo.vout);
+    assert_eq!(tx.output.len(), 1);
+    assert_eq!(tx.output[0].script_pubkey, script_pubkey);
+    assert!(tx.output[0].value < utxo.amount);
+
+    let mempool = node.client.get_raw_mempool().expect("getrawmempool").into_model().unwrap();
+    assert!(mempool.0.is_empty());
+}
+
 #[test]
 fn wallet__send_to_address__modelled() {
     let node = Node::with_wallet(Wallet::Default, &[]);
@@ -1012,6 +1426,16 @@ fn wallet__send_to_address__modelled() {
     model.unwrap();
 }
 
+#[test]
+fn wallet__send_to_address__insufficient_funds() {
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    let address = node.client.new_address().expect("failed to create new address");
+
+    let err = node.client.send_to_address(&address, Amount::from_sat(10_000)).unwrap_err();
+    assert!(err.is_insufficient_funds(), "unexpected error: {}", err);
+    assert!(!err.is_wallet_not_loaded());
+}
+
 #[test]
 #[cfg(feature = "v30_and_below")]
 fn wallet__set_tx_fee() {
@@ -1117,6 +1541,71 @@ fn wallet__wallet_create_funded_psbt__modelled() {
     assert!(!psbt.psbt.inputs.is_empty());
 }
 
+#[test]
+fn wallet__wallet_create_funded_psbt_with_options() {
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+
+    let addr = node.client.new_address().expect("newaddress");
+    let change = node.client.new_address().expect("newaddress");
+    let outputs = BTreeMap::from([(addr, Amount::from_sat(100_000))]);
+    let options = WalletCreateFundedPsbtOptions {
+        change_address: Some(change.clone()),
+        change_position: Some(0),
+        fee_rate: Some(FeeRate::from_sat_per_vb(10).unwrap()),
+        subtract_fee_from_outputs: Some(vec![0]),
+        ..Default::default()
+    };
+    let lock_time = bitcoin::absolute::LockTime::from_height(100).unwrap();
+    let json: WalletCreateFundedPsbt = node
+        .client
+        .wallet_create_funded_psbt_with_options(vec![], vec![outputs], lock_time, &options, true)
+        .expect("walletcreatefundedpsbt");
+    let model: Result<mtype::WalletCreateFundedPsbt, WalletCreateFundedPsbtError> =
+        json.into_model();
+    let funded = model.unwrap();
+
+    let tx = &funded.psbt.unsigned_tx;
+    assert_eq!(funded.change_position, 0);
+    assert_eq!(tx.output[0].script_pubkey, change.script_pubkey());
+    // The fee is taken from the payment instead of the change.
+    assert_eq!(tx.output[1].value, Amount::from_sat(100_000) - funded.fee.to_unsigned().unwrap());
+    assert_eq!(tx.lock_time, lock_time);
+}
+
+#[test]
+fn wallet__wallet_process_psbt_with_options() {
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+
+    let addr = node.client.new_address().expect("newaddress");
+    let outputs = BTreeMap::from([(addr, Amount::from_sat(50_000))]);
+    let funded: WalletCreateFundedPsbt = node
+        .client
+        .wallet_create_funded_psbt(vec![], vec![outputs])
+        .expect("walletcreatefundedpsbt");
+    let psbt = funded.into_model().unwrap().psbt;
+
+    #[cfg(feature = "v25_and_below")]
+    type WalletProcessPsbtError = psbt::PsbtParseError;
+
+    // Only updating does not sign.
+    let json: WalletProcessPsbt = node
+        .client
+        .wallet_process_psbt_with_options(&psbt, false, SighashType::All, true)
+        .expect("walletprocesspsbt");
+    let model: Result<mtype::WalletProcessPsbt, WalletProcessPsbtError> = json.into_model();
+    let updated = model.unwrap();
+    assert!(!updated.complete);
+
+    let json: WalletProcessPsbt = node
+        .client
+        .wallet_process_psbt_with_options(&updated.psbt, true, SighashType::All, false)
+        .expect("walletprocesspsbt");
+    let model: Result<mtype::WalletProcessPsbt, WalletProcessPsbtError> = json.into_model();
+    assert!(model.unwrap().complete);
+}
+
 #[test]
 fn wallet__wallet_process_psbt__modelled() {
     let node = Node::with_wallet(Wallet::Default, &[]);
@@ -1143,6 +1632,237 @@ fn wallet__wallet_process_psbt__modelled() {
     assert_eq!(processed.psbt.inputs.len(), funded_psbt_model.psbt.inputs.len());
 }
 
+#[test]
+fn wallet__psbt_pipeline() {
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+
+    let addr = node.client.new_address().expect("newaddress");
+    let outputs = BTreeMap::from([(addr, Amount::from_sat(50_000))]);
+
+    let funded = node.client.psbt_pipeline(vec![], vec![outputs]).expect("fund");
+    assert!(funded.state().fee.unwrap() > SignedAmount::ZERO);
+    let processed = funded.process().expect("process");
+    assert!(processed.state().complete);
+    let finalized = processed.finalize().expect("finalize");
+    let tx = finalized.state().tx.clone();
+    let txid = finalized.broadcast().expect("broadcast");
+
+    assert_eq!(txid, tx.compute_txid());
+    let mempool = node.client.get_raw_mempool().expect("getrawmempool");
+    assert!(mempool.0.contains(&txid.to_string()));
+}
+
+#[test]
+fn wallet__psbt_pipeline__incomplete() {
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+
+    let addr = node.client.new_address().expect("newaddress");
+    let outputs = BTreeMap::from([(addr, Amount::from_sat(50_000))]);
+    let psbt = node.client.psbt_pipeline(vec![], vec![outputs]).expect("fund").into_state().psbt;
+
+    // A wallet that does not own the inputs cannot sign the PSBT.
+    node.client.create_wallet("other").expect("createwallet");
+    let auth = Auth::CookieFile(node.params.cookie_file.clone());
+    let other = Client::new_with_auth(&node.rpc_url_with_wallet("other"), auth).expect("client");
+
+    let processed = other.psbt_pipeline_from_psbt(psbt).process().expect("process");
+    assert!(!processed.state().complete);
+    match processed.finalize() {
+        Err(e @ PsbtPipelineError::Incomplete(_)) => assert_eq!(e.stage(), PsbtStage::Finalize),
+        res => panic!("expected incomplete PSBT error, got: {:?}", res),
+    }
+}
+
+#[test]
+fn wallet__psbt_pipeline__multi_party() {
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+
+    // Give two other wallets a single UTXO each.
+    let auth = Auth::CookieFile(node.params.cookie_file.clone());
+    let parties = ["alice", "bob"].map(|name| {
+        node.client.create_wallet(name).expect("createwallet");
+        let client =
+            Client::new_with_auth(&node.rpc_url_with_wallet(name), auth.clone()).expect("client");
+        let addr = client.new_address().expect("newaddress");
+        node.client.send_to_address(&addr, Amount::from_btc(1.0).unwrap()).expect("sendtoaddress");
+        client
+    });
+    node.mine_a_block();
+
+    // A transaction spending from both wallets.
+    let inputs = parties
+        .iter()
+        .map(|client| {
+            let utxo = client.list_unspent().expect("listunspent").0.remove(0);
+            Input { txid: utxo.txid.parse().unwrap(), vout: utxo.vout as u64, sequence: None }
+        })
+        .collect::<Vec<_>>();
+    let addr = node.client.new_address().expect("newaddress");
+    let outputs = vec![Output::new(addr, Amount::from_btc(1.999).unwrap())];
+    let psbt = node
+        .client
+        .create_psbt(&inputs, &outputs)
+        .expect("createpsbt")
+        .into_model()
+        .expect("CreatePsbt into model")
+        .0;
+
+    // Neither party can sign alone.
+    let processed = parties[0].psbt_pipeline_from_psbt(psbt.clone()).process().expect("process");
+    assert!(!processed.state().complete);
+
+    let [alice, bob] = &parties;
+    let processed = node
+        .client
+        .psbt_pipeline_from_psbt(psbt)
+        .process_multi_party(&[alice, bob], vec![])
+        .expect("process_multi_party");
+    assert!(processed.state().complete);
+    let txid = processed.finalize().expect("finalize").broadcast().expect("broadcast");
+
+    let mempool = node.client.get_raw_mempool().expect("getrawmempool");
+    assert!(mempool.0.contains(&txid.to_string()));
+}
+
+#[test]
+#[cfg(not(feature = "v20_and_below"))] // `importdescriptors` was added in v0.21.
+fn wallet__psbt_pipeline__multi_party_multisig() {
+    use bitcoin::opcodes::all::{OP_CHECKMULTISIG, OP_PUSHNUM_2, OP_PUSHNUM_3};
+    use bitcoin::script::Builder;
+
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+
+    let secp = secp256k1::Secp256k1::new();
+    let keys = [1u8, 2, 3].map(|i| {
+        let secret = secp256k1::SecretKey::from_slice(&[i; 32]).unwrap();
+        PrivateKey::new(secret, Network::Regtest)
+    });
+    let pubkeys = keys.map(|key| key.public_key(&secp));
+
+    // A 2-of-3 multisig, each party's wallet holds one of the private keys.
+    let auth = Auth::CookieFile(node.params.cookie_file.clone());
+    let [alice, bob, carol] = [(0, "alice"), (1, "bob"), (2, "carol")].map(|(i, name)| {
+        #[cfg(feature = "v22_and_below")]
+        node.client.create_descriptor_wallet(name).expect("create descriptor wallet");
+        #[cfg(not(feature = "v22_and_below"))]
+        node.client.create_wallet(name).expect("createwallet");
+        let client =
+            Client::new_with_auth(&node.rpc_url_with_wallet(name), auth.clone()).expect("client");
+
+        let keys = (0..3)
+            .map(|j| if i == j { keys[j].to_wif() } else { pubkeys[j].to_string() })
+            .collect::<Vec<_>>();
+        let raw_descriptor = format!("wsh(multi(2,{}))", keys.join(","));
+        let info = node.client.get_descriptor_info(&raw_descriptor).expect("getdescriptorinfo");
+        let descriptor = format!("{}#{}", raw_descriptor, info.checksum);
+        let json = client
+            .import_descriptors(&[ImportDescriptorsRequest::new(descriptor, "now")])
+            .expect("importdescriptors");
+        assert!(json.0[0].success, "{:?}", json.0[0].error);
+        client
+    });
+
+    let script = Builder::new()
+        .push_opcode(OP_PUSHNUM_2)
+        .push_key(&pubkeys[0])
+        .push_key(&pubkeys[1])
+        .push_key(&pubkeys[2])
+        .push_opcode(OP_PUSHNUM_3)
+        .push_opcode(OP_CHECKMULTISIG)
+        .into_script();
+    let multisig = Address::p2wsh(&script, KnownHrp::Regtest);
+    node.client.send_to_address(&multisig, Amount::from_btc(1.0).unwrap()).expect("sendtoaddress");
+    node.mine_a_block();
+
+    let utxo = alice.list_unspent().expect("listunspent").0.remove(0);
+    let inputs =
+        [Input { txid: utxo.txid.parse().unwrap(), vout: utxo.vout as u64, sequence: None }];
+    let addr = node.client.new_address().expect("newaddress");
+    let outputs = vec![Output::new(addr, Amount::from_btc(0.999).unwrap())];
+    let psbt = node
+        .client
+        .create_psbt(&inputs, &outputs)
+        .expect("createpsbt")
+        .into_model()
+        .expect("CreatePsbt into model")
+        .0;
+
+    // A single signature is not enough to finalize.
+    for party in [&alice, &bob, &carol] {
+        let processed = party.psbt_pipeline_from_psbt(psbt.clone()).process().expect("process");
+        assert!(!processed.state().complete);
+        match processed.finalize() {
+            Err(e @ PsbtPipelineError::Incomplete(_)) => assert_eq!(e.stage(), PsbtStage::Finalize),
+            res => panic!("expected incomplete PSBT error, got: {:?}", res),
+        }
+    }
+
+    // The PSBTs signed by two of the parties are combined into a complete one.
+    let processed = node
+        .client
+        .psbt_pipeline_from_psbt(psbt)
+        .process_multi_party(&[&alice, &carol], vec![])
+        .expect("process_multi_party");
+    assert!(processed.state().complete);
+    let txid = processed.finalize().expect("finalize").broadcast().expect("broadcast");
+
+    let mempool = node.client.get_raw_mempool().expect("getrawmempool");
+    assert!(mempool.0.contains(&txid.to_string()));
+}
+
+#[test]
+#[cfg(unix)] // The mock signer is a shell script.
+#[cfg(not(feature = "v19_and_below"))] // `bip32derivs` defaults to true from v0.20.
+fn wallet__psbt_pipeline__external_signer() {
+    use std::os::unix::fs::PermissionsExt as _;
+
+    let node = Node::with_wallet(Wallet::Default, &[]);
+    node.fund_wallet();
+
+    let addr = node.client.new_address().expect("newaddress");
+    let outputs = BTreeMap::from([(addr.clone(), Amount::from_sat(50_000))]);
+    let processed =
+        node.client.psbt_pipeline(vec![], vec![outputs]).expect("fund").process().expect("process");
+    let (fingerprint, _) =
+        processed.state().psbt.inputs[0].bip32_derivation.values().next().unwrap();
+
+    // A mock signer for the wallet's keys, `signtx` returns the (already signed) PSBT unchanged.
+    let script = format!(
+        r#"#!/bin/sh
+case "$*" in
+    *enumerate*) echo '[{{"fingerprint": "{}", "model": "mock"}}]' ;;
+    *displayaddress*) echo '{{"address": "{}"}}' ;;
+    *--stdin*) read -r _ psbt; echo "{{\"psbt\": \"$psbt\"}}" ;;
+    *) echo '{{"error": "unknown command"}}' ;;
+esac
+"#,
+        fingerprint, addr
+    );
+    let path = integration_test::tmp_file();
+    fs::write(&path, script).expect("write mock signer");
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).expect("chmod mock signer");
+
+    let signers =
+        ExternalSigner::enumerate(path.to_str().unwrap(), Network::Regtest).expect("enumerate");
+    assert_eq!(signers.len(), 1);
+    let signer = &signers[0];
+    assert_eq!(signer.fingerprint(), *fingerprint);
+    assert_eq!(signer.name(), "mock");
+
+    let displayed = signer.display_address(&format!("addr({})", addr)).expect("displayaddress");
+    assert_eq!(displayed.assume_checked(), addr);
+
+    let signed = processed.sign_external(signer).expect("signtx");
+    assert!(signed.state().complete);
+    let txid = signed.finalize().expect("finalize").broadcast().expect("broadcast");
+    let mempool = node.client.get_raw_mempool().expect("getrawmempool");
+    assert!(mempool.0.contains(&txid.to_string()));
+}
+
 #[test]
 fn wallet__wallet_lock() {
     let node = Node::with_wallet(Wallet::Default, &[]);
@@ -21,3 +21,42 @@ fn zmq__get_zmq_notifications__modelled() {
     assert_eq!(zmq_notification.type_, "pubhashblock");
     assert_eq!(zmq_notification.address, "tcp://127.0.0.1:29000");
 }
+
+#[test]
+fn zmq__subscriber() {
+    use std::thread;
+    use std::time::Duration;
+
+    use node::zmq::{ZmqEvent, ZmqSubscriber};
+
+    let exe = node::exe_path().expect("failed to get bitcoind executable");
+    let mut conf = node::Conf::default();
+    conf.enable_zmq = true;
+    let node = Node::with_conf(exe, &conf).expect("failed to create node");
+
+    let params = &node.params;
+    let connect = |socket: Option<std::net::SocketAddrV4>| {
+        let subscriber = ZmqSubscriber::connect(socket.expect("zmq endpoint")).expect("connect");
+        subscriber.set_timeout(Some(Duration::from_secs(10))).expect("set_timeout");
+        subscriber
+    };
+    let mut raw_blocks = connect(params.zmq_pub_raw_block_socket);
+    let mut hash_blocks = connect(params.zmq_pub_hash_block_socket);
+    let mut raw_txs = connect(params.zmq_pub_raw_tx_socket);
+    // Give the node time to process the subscriptions, earlier notifications are dropped.
+    thread::sleep(Duration::from_millis(500));
+
+    let address = node.client.new_address().expect("failed to get new address");
+    let json = node.client.generate_to_address(1, &address).expect("generatetoaddress");
+    let hash = json.into_model().unwrap().0[0];
+
+    match raw_blocks.recv().expect("rawblock") {
+        ZmqEvent::Block(block) => assert_eq!(block.block_hash(), hash),
+        event => panic!("unexpected event: {:?}", event),
+    }
+    assert_eq!(hash_blocks.recv().expect("hashblock"), ZmqEvent::BlockHash(hash));
+    // The coinbase transaction of the block.
+    assert!(
+        matches!(raw_txs.recv().expect("rawtx"), ZmqEvent::Transaction(tx) if tx.is_coinbase())
+    );
+}
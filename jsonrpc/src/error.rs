@@ -65,8 +65,7 @@ impl error::Error for Error {
         use self::Error::*;
 
         match *self {
-            Rpc(_)
-            | NonceMismatch
+            NonceMismatch
             | VersionMismatch
             | EmptyBatch
             | WrongBatchResponseSize
@@ -74,6 +73,7 @@ impl error::Error for Error {
             | WrongBatchResponseId(_) => None,
             Transport(ref e) => Some(&**e),
             Json(ref e) => Some(e),
+            Rpc(ref e) => Some(e),
         }
     }
 }
@@ -126,6 +126,14 @@ pub struct RpcError {
     pub data: Option<Box<serde_json::value::RawValue>>,
 }
 
+impl fmt::Display for RpcError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (code {})", self.message, self.code)
+    }
+}
+
+impl error::Error for RpcError {}
+
 /// Create a standard error responses
 pub fn standard_error(
     code: StandardError,
@@ -218,4 +226,13 @@ mod tests {
         assert_eq!(resp.id, serde_json::Value::from(-1));
         assert_eq!(resp.error.unwrap().code, -32603);
     }
+
+    #[test]
+    fn test_rpc_error_source() {
+        use std::error::Error as _;
+
+        let err = super::Error::Rpc(standard_error(MethodNotFound, None));
+        let source = err.source().expect("RPC error is the source");
+        assert_eq!(source.to_string(), "Method not found (code -32601)");
+    }
 }
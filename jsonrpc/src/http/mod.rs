@@ -15,3 +15,44 @@ pub const DEFAULT_PORT: u16 = 8332;
 // Currently only used by `simple_http` module, here for consistency.
 #[cfg(feature = "proxy")]
 pub const DEFAULT_PROXY_PORT: u16 = 9050;
+
+/// Overwrites `secret` with zeros before dropping it.
+///
+/// The spare capacity of a `String` or `Vec` is overwritten too.
+#[cfg(feature = "zeroize")]
+pub(crate) fn zeroize<Z: zeroize::Zeroize>(mut secret: Z) { secret.zeroize() }
+
+/// A buffer that is overwritten with zeros when dropped.
+#[cfg(feature = "zeroize")]
+pub(crate) type ZeroizeOnDrop = zeroize::Zeroizing<Vec<u8>>;
+
+#[cfg(all(test, feature = "zeroize"))]
+mod tests {
+    use zeroize::Zeroize;
+
+    use super::*;
+
+    /// Borrows a secret so that it can be inspected once `zeroize` has dropped the borrow.
+    struct Borrowed<'a, Z: ?Sized>(&'a mut Z);
+
+    impl<Z: Zeroize + ?Sized> Zeroize for Borrowed<'_, Z> {
+        fn zeroize(&mut self) { self.0.zeroize() }
+    }
+
+    #[test]
+    fn zeroize_overwrites_bytes() {
+        let mut auth = *b"Basic dXNlcjpwYXNz";
+        zeroize(Borrowed(&mut auth[..]));
+        assert_eq!(auth, [0; 18]);
+    }
+
+    #[test]
+    fn zeroize_clears_string() {
+        let mut auth = String::from("Basic dXNlcjpwYXNz");
+        let capacity = auth.capacity();
+        zeroize(Borrowed(&mut auth));
+        assert!(auth.is_empty());
+        // The scrubbed allocation is kept, it is freed when `auth` is dropped.
+        assert_eq!(auth.capacity(), capacity);
+    }
+}
@@ -36,7 +36,7 @@ const DEFAULT_TIMEOUT: Duration = Duration::from_millis(1);
 
 /// Simple HTTP transport that implements the necessary subset of HTTP for
 /// running a bitcoind RPC client.
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct SimpleHttpTransport {
     addr: net::SocketAddr,
     path: String,
@@ -142,6 +142,8 @@ impl SimpleHttpTransport {
 
         // Serialize the body first so we can set the Content-Length header.
         let body = serde_json::to_vec(&req)?;
+        #[cfg(feature = "zeroize")]
+        let body = super::ZeroizeOnDrop::new(body);
 
         let mut request_bytes = Vec::new();
 
@@ -164,6 +166,8 @@ impl SimpleHttpTransport {
         // Write body
         request_bytes.write_all(b"\r\n")?;
         request_bytes.write_all(&body)?;
+        #[cfg(feature = "zeroize")]
+        let request_bytes = super::ZeroizeOnDrop::new(request_bytes);
 
         // Send HTTP request
         let write_success = sock.get_mut().write_all(request_bytes.as_slice()).is_ok()
@@ -350,6 +354,33 @@ impl Transport for SimpleHttpTransport {
     }
 }
 
+#[cfg(feature = "zeroize")]
+impl Drop for SimpleHttpTransport {
+    fn drop(&mut self) {
+        if let Some(auth) = self.basic_auth.take() {
+            super::zeroize(auth);
+        }
+        #[cfg(feature = "proxy")]
+        if let Some((_, pass)) = self.proxy_auth.take() {
+            super::zeroize(pass);
+        }
+    }
+}
+
+impl fmt::Debug for SimpleHttpTransport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut s = f.debug_struct("SimpleHttpTransport");
+        s.field("addr", &self.addr)
+            .field("path", &self.path)
+            .field("timeout", &self.timeout)
+            .field("basic_auth", &self.basic_auth.as_ref().map(|_| "[redacted]"));
+        #[cfg(feature = "proxy")]
+        s.field("proxy_addr", &self.proxy_addr)
+            .field("proxy_auth", &self.proxy_auth.as_ref().map(|(user, _)| (user, "[redacted]")));
+        s.finish_non_exhaustive()
+    }
+}
+
 /// Builder for simple bitcoind [`SimpleHttpTransport`].
 #[derive(Clone, Debug)]
 pub struct Builder {
@@ -379,13 +410,22 @@ impl Builder {
         if let Some(ref pass) = pass {
             auth.push_str(pass.as_ref());
         }
-        self.tp.basic_auth = Some(format!("Basic {}", &BASE64.encode(auth.as_bytes())));
+        let encoded = BASE64.encode(auth.as_bytes());
+        self.tp.basic_auth = Some(format!("Basic {}", &encoded));
+        #[cfg(feature = "zeroize")]
+        {
+            super::zeroize(auth);
+            super::zeroize(encoded);
+        }
         self
     }
 
     /// Adds authentication information to the transport using a cookie string ('user:pass').
     pub fn cookie_auth<S: AsRef<str>>(mut self, cookie: S) -> Self {
-        self.tp.basic_auth = Some(format!("Basic {}", &BASE64.encode(cookie.as_ref().as_bytes())));
+        let encoded = BASE64.encode(cookie.as_ref().as_bytes());
+        self.tp.basic_auth = Some(format!("Basic {}", &encoded));
+        #[cfg(feature = "zeroize")]
+        super::zeroize(encoded);
         self
     }
 
@@ -560,12 +600,12 @@ impl error::Error for Error {
             | HttpResponseTooShort { .. }
             | HttpResponseNonAsciiHello(..)
             | HttpResponseBadHello { .. }
-            | HttpResponseBadStatus(..)
-            | HttpResponseBadContentLength(..)
             | HttpResponseContentLengthTooLarge { .. }
             | HttpErrorCode(_)
             | IncompleteResponse { .. }
             | HttpResponseChunked => None,
+            HttpResponseBadStatus(_, ref e) => Some(e),
+            HttpResponseBadContentLength(_, ref e) => Some(e),
             SocketError(ref e) => Some(e),
             Json(ref e) => Some(e),
         }
@@ -703,6 +743,14 @@ mod tests {
         let _ = Client::simple_http("localhost:22", None, None).unwrap();
     }
 
+    #[test]
+    fn debug_redacts_credentials() {
+        let tp = Builder::new().url("localhost:22").unwrap().auth("user", Some("secret")).build();
+        let debug = format!("{:?}", tp);
+        assert!(!debug.contains("secret"), "{}", debug);
+        assert!(debug.contains("[redacted]"), "{}", debug);
+    }
+
     #[cfg(feature = "proxy")]
     #[test]
     fn construct_with_proxy() {
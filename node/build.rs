@@ -19,38 +19,55 @@ mod download {
 
     include!("src/versions.rs");
 
-    fn download_filename() -> String {
+    /// The env vars read by the build script, it is rerun if any of them changes.
+    const ENV_VARS: [&str; 6] = [
+        "BITCOIND_SKIP_DOWNLOAD",
+        "BITCOIND_DOWNLOAD_DIR",
+        "BITCOIND_DOWNLOAD_ENDPOINT",
+        "BITCOIND_TARBALL_FILE",
+        "BITCOIND_VERSION",
+        "BITCOIND_VERSIONS",
+    ];
+
+    /// Returns the major version number of `version` e.g., 0 for "0.21.2" and 28 for "28.2".
+    fn major(version: &str) -> u32 {
+        version.split('.').next().and_then(|major| major.parse().ok()).unwrap_or(0)
+    }
+
+    fn download_filename(version: &str) -> String {
         if cfg!(all(target_os = "macos", target_arch = "x86_64")) {
-            if cfg!(not(feature = "23_2")) {
-                return format!("bitcoin-{}-osx64.tar.gz", &VERSION);
+            if major(version) < 23 {
+                return format!("bitcoin-{}-osx64.tar.gz", version);
             }
-            return format!("bitcoin-{}-x86_64-apple-darwin.tar.gz", &VERSION);
+            return format!("bitcoin-{}-x86_64-apple-darwin.tar.gz", version);
         }
 
         if cfg!(all(target_os = "macos", target_arch = "aarch64")) {
-            return format!("bitcoin-{}-arm64-apple-darwin.tar.gz", &VERSION);
+            return format!("bitcoin-{}-arm64-apple-darwin.tar.gz", version);
         }
 
         if cfg!(all(target_os = "linux", target_arch = "x86_64")) {
-            return format!("bitcoin-{}-x86_64-linux-gnu.tar.gz", &VERSION);
+            return format!("bitcoin-{}-x86_64-linux-gnu.tar.gz", version);
         }
 
         if cfg!(all(target_os = "linux", target_arch = "aarch64")) {
-            return format!("bitcoin-{}-aarch64-linux-gnu.tar.gz", &VERSION);
+            return format!("bitcoin-{}-aarch64-linux-gnu.tar.gz", version);
         }
 
         if cfg!(all(target_os = "windows", target_arch = "x86_64")) {
-            return format!("bitcoin-{}-win64.zip", &VERSION);
+            return format!("bitcoin-{}-win64.zip", version);
         }
 
         panic!("No download file for this os/arch");
     }
 
     #[allow(clippy::lines_filter_map_ok)] // clippy doesn't like the `lines` call below and the suggested fix is incorrect.
-    fn get_expected_sha256(filename: &str) -> anyhow::Result<sha256::Hash> {
-        let sha256sums_filename = format!("sha256/bitcoin-core-{}-SHA256SUMS", &VERSION);
-        #[cfg(not(feature = "22_1"))]
-        let sha256sums_filename = format!("{}.asc", sha256sums_filename);
+    fn get_expected_sha256(version: &str, filename: &str) -> anyhow::Result<sha256::Hash> {
+        let mut sha256sums_filename = format!("sha256/bitcoin-core-{}-SHA256SUMS", version);
+        // The sums of the versions before v22 are in the signed file.
+        if major(version) < 22 {
+            sha256sums_filename.push_str(".asc");
+        }
         let file = File::open(&sha256sums_filename)
             .with_context(|| format!("cannot find {:?}", sha256sums_filename))?;
         for line in BufReader::new(file).lines().flatten() {
@@ -74,7 +91,32 @@ mod download {
         out_dir.join("bitcoin")
     }
 
+    /// Returns the versions to download, the one of the version feature then the ones selected at
+    /// runtime with the `BITCOIND_VERSION` and `BITCOIND_VERSIONS` env vars.
+    fn versions() -> Vec<String> {
+        let mut versions = vec![VERSION.to_owned()];
+        let selected = ["BITCOIND_VERSION", "BITCOIND_VERSIONS"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .collect::<Vec<_>>();
+        for version in selected.iter().flat_map(|list| list.split(',')) {
+            // Same formats as accepted by `Version::from_str` e.g., "28.2", "v28.2" or "28_2".
+            let version = version.trim().trim_start_matches('v').replace('_', ".");
+            if !version.is_empty() && !versions.contains(&version) {
+                versions.push(version);
+            }
+        }
+        versions
+    }
+
     pub(crate) fn start() -> anyhow::Result<()> {
+        for var in ENV_VARS {
+            println!("cargo:rerun-if-env-changed={}", var);
+        }
+        println!("cargo:rerun-if-changed=build.rs");
+        println!("cargo:rerun-if-changed=src/versions.rs");
+        println!("cargo:rerun-if-changed=sha256");
+
         if std::env::var_os("BITCOIND_SKIP_DOWNLOAD").is_some() {
             return Ok(());
         }
@@ -84,8 +126,17 @@ mod download {
         std::fs::create_dir_all(&bitcoin_exe_home)
             .with_context(|| format!("cannot create dir {:?}", bitcoin_exe_home))?;
 
+        for version in versions() {
+            download(&version, &bitcoin_exe_home)
+                .with_context(|| format!("cannot download Bitcoin Core {}", version))?;
+        }
+        Ok(())
+    }
+
+    /// Downloads `bitcoind` of `version` into `bitcoin_exe_home`, unless it is already there.
+    fn download(version: &str, bitcoin_exe_home: &Path) -> anyhow::Result<()> {
         let mut existing_filename =
-            bitcoin_exe_home.join(format!("bitcoin-{}", VERSION)).join("bin");
+            bitcoin_exe_home.join(format!("bitcoin-{}", version)).join("bin");
         if cfg!(target_os = "windows") {
             existing_filename.push("bitcoind.exe");
         } else {
@@ -93,20 +144,23 @@ mod download {
         }
 
         if !existing_filename.exists() {
-            let download_filename = download_filename();
+            let download_filename = download_filename(version);
             println!("download_filename: {}", download_filename);
-            let expected_hash = get_expected_sha256(&download_filename)?;
+            let expected_hash = get_expected_sha256(version, &download_filename)?;
 
-            println!("filename:{} version:{} hash:{}", download_filename, VERSION, expected_hash);
+            println!("filename:{} version:{} hash:{}", download_filename, version, expected_hash);
 
-            let (file_or_url, tarball_bytes) = match std::env::var("BITCOIND_TARBALL_FILE") {
-                Err(_) => {
+            // The tarball file is the one of the version feature.
+            let tarball_file =
+                std::env::var("BITCOIND_TARBALL_FILE").ok().filter(|_| version == VERSION);
+            let (file_or_url, tarball_bytes) = match tarball_file {
+                None => {
                     let download_endpoint = std::env::var("BITCOIND_DOWNLOAD_ENDPOINT")
                         .unwrap_or("https://bitcoincore.org/bin".to_owned());
 
                     let url = format!(
                         "{}/bitcoin-core-{}/{}",
-                        download_endpoint, VERSION, download_filename
+                        download_endpoint, version, download_filename
                     );
                     let resp = bitreq::get(&url)
                         .send()
@@ -115,7 +169,7 @@ mod download {
 
                     (url, resp.as_bytes().to_vec())
                 }
-                Ok(path) => {
+                Some(path) => {
                     let f = File::open(&path).with_context(|| {
                         format!(
                             "Cannot find {:?} specified with env var BITCOIND_TARBALL_FILE",
@@ -143,7 +197,7 @@ mod download {
                 for mut entry in archive.entries().unwrap().flatten() {
                     if let Ok(file) = entry.path() {
                         if file.ends_with("bitcoind") {
-                            entry.unpack_in(&bitcoin_exe_home).unwrap();
+                            entry.unpack_in(bitcoin_exe_home).unwrap();
                         }
                     }
                 }
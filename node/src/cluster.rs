@@ -0,0 +1,223 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! A regtest network of connected nodes.
+//!
+//! See [`Cluster`].
+
+use std::ffi::OsStr;
+use std::net::SocketAddrV4;
+use std::ops::Index;
+use std::time::{Duration, Instant};
+use std::{slice, thread};
+
+use anyhow::Context;
+use corepc_client::client_sync::RpcErrorCode;
+
+#[cfg(feature = "download")]
+use crate::{downloaded_exe_path, downloaded_version_exe_path, Version};
+use crate::{Conf, Node, P2P};
+
+/// How long `connect`, `disconnect` and the `sync_*` methods wait before giving up.
+const TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How often the nodes are polled while waiting.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// A network of nodes connected to each other over p2p.
+///
+/// The nodes are connected in a line, each node to the one before, using `addnode` so that
+/// connections can be dropped with [`Cluster::disconnect`] without the nodes reconnecting.
+///
+/// ```no_run
+/// # fn main() -> anyhow::Result<()> {
+/// let cluster = corepc_node::Cluster::new(corepc_node::exe_path()?, 3)?;
+/// let address = cluster[0].client.new_address()?;
+/// cluster[0].client.generate_to_address(1, &address)?;
+/// cluster.sync_all()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Cluster {
+    nodes: Vec<Node>,
+}
+
+impl Cluster {
+    /// Launches `n` connected nodes from the given `exe` executable with default args.
+    pub fn new<S: AsRef<OsStr>>(exe: S, n: usize) -> anyhow::Result<Cluster> {
+        Cluster::with_conf(exe, n, &Conf::default())
+    }
+
+    /// Launches `n` connected nodes from the given `exe` executable with the given [`Conf`].
+    ///
+    /// `conf.p2p` is ignored, all the nodes listen for p2p connections.
+    pub fn with_conf<S: AsRef<OsStr>>(exe: S, n: usize, conf: &Conf) -> anyhow::Result<Cluster> {
+        let conf = Conf { p2p: P2P::Yes, ..conf.clone() };
+        let nodes =
+            (0..n).map(|_| Node::with_conf(exe.as_ref(), &conf)).collect::<Result<_, _>>()?;
+        let cluster = Cluster { nodes };
+        for i in 1..n {
+            cluster.connect(i, i - 1)?;
+        }
+        Ok(cluster)
+    }
+
+    /// Returns the nodes, in the order they were launched.
+    pub fn nodes(&self) -> &[Node] { &self.nodes }
+
+    /// Returns the number of nodes.
+    pub fn len(&self) -> usize { self.nodes.len() }
+
+    /// Returns true if there are no nodes.
+    pub fn is_empty(&self) -> bool { self.nodes.is_empty() }
+
+    /// Returns an iterator over the nodes.
+    pub fn iter(&self) -> slice::Iter<'_, Node> { self.nodes.iter() }
+
+    /// Connects node `a` to node `b`, waiting for the handshake to complete.
+    pub fn connect(&self, a: usize, b: usize) -> anyhow::Result<()> {
+        let (node_a, node_b) = self.pair(a, b)?;
+        let addr = p2p_socket(node_b)?.to_string();
+        node_a.client.call::<serde_json::Value>("addnode", &[addr.into(), "onetry".into()])?;
+        wait_for(|| self.is_connected(a, b), || format!("connecting node {} to node {}", a, b))
+    }
+
+    /// Disconnects nodes `a` and `b`, whichever of them made the connection.
+    ///
+    /// Returns an error if the nodes are not connected.
+    pub fn disconnect(&self, a: usize, b: usize) -> anyhow::Result<()> {
+        let (node_a, node_b) = self.pair(a, b)?;
+        let mut disconnected = false;
+        for (from, to) in [(node_a, node_b), (node_b, node_a)] {
+            let addr = p2p_socket(to)?.to_string();
+            match from.client.call::<serde_json::Value>("disconnectnode", &[addr.into()]) {
+                Ok(_) => disconnected = true,
+                Err(e) if e.is_rpc_code(RpcErrorCode::ClientNodeNotConnected) => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+        if !disconnected {
+            anyhow::bail!("node {} is not connected to node {}", a, b);
+        }
+        wait_for(
+            || Ok(peer(node_a, node_b)?.is_none() && peer(node_b, node_a)?.is_none()),
+            || format!("disconnecting node {} from node {}", a, b),
+        )
+    }
+
+    /// Returns true if nodes `a` and `b` are connected, whichever of them made the connection.
+    pub fn is_connected(&self, a: usize, b: usize) -> anyhow::Result<bool> {
+        let (node_a, node_b) = self.pair(a, b)?;
+        let handshaked = |peer: Option<serde_json::Value>| {
+            peer.is_some_and(|peer| peer["version"].as_u64().is_some_and(|v| v > 0))
+        };
+        Ok(handshaked(peer(node_a, node_b)?) || handshaked(peer(node_b, node_a)?))
+    }
+
+    /// Waits until all the nodes have the same best block and the same mempool.
+    pub fn sync_all(&self) -> anyhow::Result<()> {
+        self.sync_blocks()?;
+        self.sync_mempools()
+    }
+
+    /// Waits until all the nodes have the same best block.
+    pub fn sync_blocks(&self) -> anyhow::Result<()> {
+        wait_for(
+            || self.all_equal(|node| node.client.call::<String>("getbestblockhash", &[])),
+            || "syncing the best block".to_owned(),
+        )
+    }
+
+    /// Waits until all the nodes have the same transactions in their mempool.
+    pub fn sync_mempools(&self) -> anyhow::Result<()> {
+        wait_for(
+            || {
+                self.all_equal(|node| {
+                    let mut txids = node.client.call::<Vec<String>>("getrawmempool", &[])?;
+                    txids.sort();
+                    Ok(txids)
+                })
+            },
+            || "syncing the mempools".to_owned(),
+        )
+    }
+
+    /// Returns true if `f` returns the same value for all the nodes.
+    fn all_equal<T, F>(&self, f: F) -> anyhow::Result<bool>
+    where
+        T: PartialEq,
+        F: Fn(&Node) -> Result<T, corepc_client::client_sync::Error>,
+    {
+        let values = self.nodes.iter().map(f).collect::<Result<Vec<_>, _>>()?;
+        Ok(values.windows(2).all(|pair| pair[0] == pair[1]))
+    }
+
+    /// Returns nodes `a` and `b`, which must be different nodes of the cluster.
+    fn pair(&self, a: usize, b: usize) -> anyhow::Result<(&Node, &Node)> {
+        if a == b {
+            anyhow::bail!("can not connect node {} to itself", a);
+        }
+        let node = |i| self.nodes.get(i).with_context(|| format!("no node {} in cluster", i));
+        Ok((node(a)?, node(b)?))
+    }
+}
+
+#[cfg(feature = "download")]
+impl Cluster {
+    /// Create a cluster of `n` nodes with the downloaded executable.
+    pub fn from_downloaded(n: usize) -> anyhow::Result<Cluster> {
+        Cluster::new(downloaded_exe_path()?, n)
+    }
+
+    /// Create a cluster of `n` nodes with the downloaded executable and given Conf.
+    pub fn from_downloaded_with_conf(n: usize, conf: &Conf) -> anyhow::Result<Cluster> {
+        Cluster::with_conf(downloaded_exe_path()?, n, conf)
+    }
+
+    /// Create a cluster of `n` nodes with the downloaded executable of `version` and given Conf.
+    pub fn with_version(version: Version, n: usize, conf: &Conf) -> anyhow::Result<Cluster> {
+        Cluster::with_conf(downloaded_version_exe_path(version)?, n, conf)
+    }
+}
+
+impl Index<usize> for Cluster {
+    type Output = Node;
+
+    fn index(&self, index: usize) -> &Node { &self.nodes[index] }
+}
+
+impl<'a> IntoIterator for &'a Cluster {
+    type Item = &'a Node;
+    type IntoIter = slice::Iter<'a, Node>;
+
+    fn into_iter(self) -> Self::IntoIter { self.iter() }
+}
+
+/// Returns the p2p socket of `node`, nodes in a cluster always listen for p2p connections.
+fn p2p_socket(node: &Node) -> anyhow::Result<SocketAddrV4> {
+    node.params.p2p_socket.context("node does not listen for p2p connections")
+}
+
+/// Returns the `getpeerinfo` entry of `from` for its outbound connection to `to`, if any.
+fn peer(from: &Node, to: &Node) -> anyhow::Result<Option<serde_json::Value>> {
+    // Use serde value to be resilient to the peer info changing between versions.
+    let addr = p2p_socket(to)?.to_string();
+    let peers = from.client.call::<Vec<serde_json::Value>>("getpeerinfo", &[])?;
+    Ok(peers.into_iter().find(|peer| peer["addr"] == addr.as_str()))
+}
+
+/// Polls `done` until it returns true, or returns an error describing `what` on timeout.
+fn wait_for<F, W>(done: F, what: W) -> anyhow::Result<()>
+where
+    F: Fn() -> anyhow::Result<bool>,
+    W: Fn() -> String,
+{
+    let start = Instant::now();
+    while !done()? {
+        if start.elapsed() > TIMEOUT {
+            anyhow::bail!("timeout {}", what());
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+    Ok(())
+}
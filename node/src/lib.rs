@@ -8,11 +8,18 @@
 
 pub extern crate corepc_client as client;
 
+mod cluster;
 #[rustfmt::skip]
 mod client_versions;
+#[cfg(feature = "26_2")]
+mod snapshot;
+mod version;
 mod versions;
+#[cfg(feature = "zmq")]
+pub mod zmq;
 
-use std::ffi::OsStr;
+use std::collections::BTreeMap;
+use std::ffi::{OsStr, OsString};
 use std::net::{Ipv4Addr, SocketAddrV4, TcpListener};
 use std::path::{Path, PathBuf};
 use std::process::{Child, Command, ExitStatus, Stdio};
@@ -20,6 +27,11 @@ use std::time::Duration;
 use std::{env, fmt, fs, thread};
 
 use anyhow::Context;
+use corepc_client::bitcoin::consensus::encode;
+use corepc_client::bitcoin::{
+    absolute, transaction, Address, Amount, Network, OutPoint, ScriptBuf, Sequence, Transaction,
+    TxIn, TxOut, Txid, Witness,
+};
 use corepc_client::client_sync::{self, Auth};
 use tempfile::TempDir;
 pub use {anyhow, serde_json, tempfile, which};
@@ -34,6 +46,8 @@ pub use self::{
     // Re-export the model types as `mtype` to differentiate it from `vtype`.
     client::types::model as mtype, // `types` is the `corepc-types` crate.
 };
+pub use self::cluster::Cluster;
+pub use self::version::Version;
 
 #[derive(Debug)]
 /// Struct representing the bitcoind process with related information.
@@ -44,6 +58,8 @@ pub struct Node {
     pub client: Client,
     /// Work directory, where the node store blocks and other stuff.
     work_dir: DataDir,
+    /// The executable the process was started from, used to restart the node.
+    exe: OsString,
 
     /// Contains information to connect to this node.
     pub params: ConnectParams,
@@ -82,6 +98,8 @@ pub struct ConnectParams {
     pub zmq_pub_raw_block_socket: Option<SocketAddrV4>,
     /// zmq pub raw tx connection Url.
     pub zmq_pub_raw_tx_socket: Option<SocketAddrV4>,
+    /// zmq pub hash block connection url.
+    pub zmq_pub_hash_block_socket: Option<SocketAddrV4>,
 }
 
 pub struct CookieValues {
@@ -89,6 +107,15 @@ pub struct CookieValues {
     pub password: String,
 }
 
+impl fmt::Debug for CookieValues {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CookieValues")
+            .field("user", &self.user)
+            .field("password", &"[redacted]")
+            .finish()
+    }
+}
+
 impl ConnectParams {
     /// Parses the cookie file content.
     fn parse_cookie(content: String) -> Option<CookieValues> {
@@ -141,8 +168,19 @@ pub enum Error {
     /// Returned when expecting an auto-downloaded executable but `BITCOIND_SKIP_DOWNLOAD` env var is set.
     SkipDownload,
     /// Returned when bitcoind could not be reached after multiple attempts.
-    /// The attached string, if present, contains the error encountered when trying to connect.
+    /// The attached string describes what failed, the error encountered, if any, is its source.
     NoBitcoindInstance(String),
+    /// Returned when the chain selected in `Conf` args is not `Conf::network`.
+    NetworkMismatch {
+        /// The chain selected in `Conf` args.
+        args: Network,
+        /// The `Conf::network`.
+        network: Network,
+    },
+    /// Returned when parsing a version of Bitcoin Core not supported by this crate.
+    UnknownVersion(String),
+    /// Returned when the executable of a version selected at runtime was not downloaded.
+    VersionNotDownloaded(Version),
 }
 
 impl fmt::Debug for Error {
@@ -160,6 +198,9 @@ impl fmt::Debug for Error {
             RpcUserAndPasswordUsed => write!(f, "`-rpcuser` and `-rpcpassword` cannot be used, it will be deprecated soon and it's recommended to use `-rpcauth` instead which works alongside with the default cookie authentication"),
             SkipDownload => write!(f, "expecting an auto-downloaded executable but `BITCOIND_SKIP_DOWNLOAD` env var is set"),
             NoBitcoindInstance(msg) => write!(f, "it appears that bitcoind is not reachable: {}", msg),
+            NetworkMismatch { args, network } => write!(f, "the chain selected in args ({}) is not the configured network ({})", args, network),
+            UnknownVersion(v) => write!(f, "unknown Bitcoin Core version `{}`, the supported versions are the version features", v),
+            VersionNotDownloaded(v) => write!(f, "Bitcoin Core {} was not downloaded, add it to the `BITCOIND_VERSIONS` env var when building", v),
         }
     }
 }
@@ -182,7 +223,10 @@ impl std::error::Error for Error {
             | BothDirsSpecified
             | RpcUserAndPasswordUsed
             | SkipDownload
-            | NoBitcoindInstance(_) => None,
+            | NoBitcoindInstance(_)
+            | NetworkMismatch { .. }
+            | UnknownVersion(_)
+            | VersionNotDownloaded(_) => None,
         }
     }
 }
@@ -204,7 +248,7 @@ const INVALID_ARGS: [&str; 2] = ["-rpcuser", "-rpcpassword"];
 /// conf.args = vec!["-regtest", "-fallbackfee=0.0001"];
 /// conf.view_stdout = false;
 /// conf.p2p = bitcoind::P2P::No;
-/// conf.network = "regtest";
+/// conf.network = bitcoind::client::bitcoin::Network::Regtest;
 /// conf.tmpdir = None;
 /// conf.staticdir = None;
 /// conf.attempts = 5;
@@ -225,9 +269,8 @@ pub struct Conf<'a> {
     /// Allows to specify options to open p2p port or connect to the another node.
     pub p2p: P2P,
 
-    /// Must match what specified in args without dashes, needed to locate the cookie file
-    /// directory with different/esoteric networks.
-    pub network: &'a str,
+    /// Must match the chain selected in args (e.g. `-regtest`), needed to locate the cookie file.
+    pub network: Network,
 
     /// Temporary directory path.
     ///
@@ -245,6 +288,9 @@ pub struct Conf<'a> {
     pub tmpdir: Option<PathBuf>,
 
     /// Persistent directory path.
+    ///
+    /// May point at an existing data directory, e.g. one left by a previous node, in which case
+    /// its chain and wallets are used. The directory is not removed when the node is dropped.
     pub staticdir: Option<PathBuf>,
 
     /// Try to spawn the process `attempt` time.
@@ -255,6 +301,9 @@ pub struct Conf<'a> {
     pub attempts: u8,
 
     /// Enable the ZMQ interface to be accessible.
+    ///
+    /// The `rawtx`, `rawblock` and `hashblock` notifications are published on free ports, see
+    /// [`ConnectParams`].
     pub enable_zmq: bool,
 
     /// Load `wallet` after initialization.
@@ -267,7 +316,7 @@ impl Default for Conf<'_> {
             args: vec!["-regtest", "-fallbackfee=0.0001"],
             view_stdout: false,
             p2p: P2P::No,
-            network: "regtest",
+            network: Network::Regtest,
             tmpdir: None,
             staticdir: None,
             attempts: 5,
@@ -303,90 +352,153 @@ impl Node {
     ///
     /// If the node fails to start after the specified number of attempts.
     pub fn with_conf<S: AsRef<OsStr>>(exe: S, conf: &Conf) -> anyhow::Result<Node> {
+        check_network(&conf.args, conf.network)?;
         for attempt in 0..conf.attempts {
             let work_dir = Self::init_work_dir(conf)?;
-            let cookie_file = work_dir.path().join(conf.network).join(".cookie");
-
-            let rpc_port = get_available_port()?;
-            let rpc_socket = SocketAddrV4::new(LOCAL_IP, rpc_port);
-            let rpc_url = format!("http://{}", rpc_socket);
-
-            let (p2p_args, p2p_socket) = Self::p2p_args(&conf.p2p)?;
-            let (zmq_args, zmq_pub_raw_tx_socket, zmq_pub_raw_block_socket) =
-                Self::zmq_args(conf.enable_zmq)?;
-
-            let stdout = if conf.view_stdout { Stdio::inherit() } else { Stdio::null() };
-
-            let datadir_arg = format!("-datadir={}", work_dir.path().display());
-            let rpc_arg = format!("-rpcport={}", rpc_port);
-            let default_args = [&datadir_arg, &rpc_arg];
-            let conf_args = validate_args(conf.args.clone())?;
-
-            let mut process = Command::new(exe.as_ref())
-                .args(default_args)
-                .args(&p2p_args)
-                .args(&conf_args)
-                .args(&zmq_args)
-                .stdout(stdout)
-                .spawn()
-                .with_context(|| format!("Error while executing {:?}", exe.as_ref()))?;
-            match process.try_wait() {
-                Ok(Some(_)) | Err(_) => {
-                    // Process has exited or an error occurred, kill and retry
-                    let _ = process.kill();
-                    continue;
-                }
-                Ok(None) => {
-                    // Process is still running, proceed
-                }
+            let last_attempt = attempt == conf.attempts - 1;
+            if let Some((process, client, params)) =
+                Self::spawn(exe.as_ref(), conf, &work_dir.path(), last_attempt)?
+            {
+                return Ok(Node {
+                    process,
+                    client,
+                    work_dir,
+                    exe: exe.as_ref().to_owned(),
+                    params,
+                });
             }
+        }
+        Err(anyhow::anyhow!("Failed to start the node after {} attempts", conf.attempts))
+    }
 
-            if Self::wait_for_cookie_file(cookie_file.as_path(), Duration::from_secs(5)).is_err() {
-                // If the cookie file is not accessible a new work_dir is needed and therefore a new
-                // process. Kill the process and retry.
-                let _ = process.kill();
-                continue;
+    /// Stops the node and starts it again with the same executable and the given [`Conf`].
+    ///
+    /// See [`Node::restart_with`].
+    pub fn restart(&mut self, conf: &Conf) -> anyhow::Result<()> {
+        let exe = self.exe.clone();
+        self.restart_with(exe, conf)
+    }
+
+    /// Stops the node and starts it again from the given `exe` executable and [`Conf`].
+    ///
+    /// The node keeps its data directory, the `tmpdir` and `staticdir` of `conf` are ignored, so
+    /// the chain and the wallets are kept. Starting a different Core version on the same data
+    /// directory can be used to test upgrades. The node gets new ports and a new client, which
+    /// is connected to `conf.wallet` as in [`Node::with_conf`].
+    ///
+    /// # Errors
+    ///
+    /// If the node fails to stop, or to start again after the specified number of attempts.
+    pub fn restart_with<S: AsRef<OsStr>>(&mut self, exe: S, conf: &Conf) -> anyhow::Result<()> {
+        check_network(&conf.args, conf.network)?;
+        let _ = self.stop()?;
+        let work_dir = self.work_dir.path();
+        for attempt in 0..conf.attempts {
+            let last_attempt = attempt == conf.attempts - 1;
+            if let Some((process, client, params)) =
+                Self::spawn(exe.as_ref(), conf, &work_dir, last_attempt)?
+            {
+                self.process = process;
+                self.client = client;
+                self.exe = exe.as_ref().to_owned();
+                self.params = params;
+                return Ok(());
             }
-            let auth = Auth::CookieFile(cookie_file.clone());
-
-            let client_base = Self::create_client_base(&rpc_url, &auth)?;
-            let client = match &conf.wallet {
-                Some(wallet) =>
-                    match Self::create_client_wallet(&client_base, &rpc_url, &auth, wallet) {
-                        Ok(client) => client,
-                        Err(e) =>
-                            if attempt == conf.attempts - 1 {
-                                return Err(e);
-                            } else {
-                                // If the wallet cannot be created or loaded, there might be an issue
-                                // with the work_dir or process. Kill the process and retry.
-                                let _ = process.kill();
-                                continue;
-                            },
-                    },
-                None => client_base,
-            };
-            if Self::wait_for_client(&client, Duration::from_secs(5)).is_err() {
-                // If the client times out there might be an issue with the work_dir or process. Kill
-                // the process and retry.
+        }
+        Err(anyhow::anyhow!("Failed to restart the node after {} attempts", conf.attempts))
+    }
+
+    /// Spawns the bitcoind process on `work_dir` and waits for it to be ready.
+    ///
+    /// Returns `None` if the attempt failed and should be retried, the error of the wallet
+    /// creation is returned instead on the `last_attempt`.
+    fn spawn(
+        exe: &OsStr,
+        conf: &Conf,
+        work_dir: &Path,
+        last_attempt: bool,
+    ) -> anyhow::Result<Option<(Child, Client, ConnectParams)>> {
+        let cookie_file = work_dir.join(network_dir(conf.network)).join(".cookie");
+        // A cookie file left by a previous process on the same work_dir is not valid anymore.
+        let _ = fs::remove_file(&cookie_file);
+
+        let rpc_port = get_available_port()?;
+        let rpc_socket = SocketAddrV4::new(LOCAL_IP, rpc_port);
+        let rpc_url = format!("http://{}", rpc_socket);
+
+        let (p2p_args, p2p_socket) = Self::p2p_args(&conf.p2p)?;
+        let (
+            zmq_args,
+            [zmq_pub_raw_tx_socket, zmq_pub_raw_block_socket, zmq_pub_hash_block_socket],
+        ) = Self::zmq_args(conf.enable_zmq)?;
+
+        let stdout = if conf.view_stdout { Stdio::inherit() } else { Stdio::null() };
+
+        let datadir_arg = format!("-datadir={}", work_dir.display());
+        let rpc_arg = format!("-rpcport={}", rpc_port);
+        let default_args = [&datadir_arg, &rpc_arg];
+        let conf_args = validate_args(conf.args.clone())?;
+
+        let mut process = Command::new(exe)
+            .args(default_args)
+            .args(&p2p_args)
+            .args(&conf_args)
+            .args(&zmq_args)
+            .stdout(stdout)
+            .spawn()
+            .with_context(|| format!("Error while executing {:?}", exe))?;
+        match process.try_wait() {
+            Ok(Some(_)) | Err(_) => {
+                // Process has exited or an error occurred, kill and retry
                 let _ = process.kill();
-                continue;
+                return Ok(None);
+            }
+            Ok(None) => {
+                // Process is still running, proceed
             }
+        }
 
-            return Ok(Node {
-                process,
-                client,
-                work_dir,
-                params: ConnectParams {
-                    cookie_file,
-                    rpc_socket,
-                    p2p_socket,
-                    zmq_pub_raw_block_socket,
-                    zmq_pub_raw_tx_socket,
-                },
-            });
+        if Self::wait_for_cookie_file(cookie_file.as_path(), Duration::from_secs(5)).is_err() {
+            // If the cookie file is not accessible a new work_dir is needed and therefore a new
+            // process. Kill the process and retry.
+            let _ = process.kill();
+            return Ok(None);
         }
-        Err(anyhow::anyhow!("Failed to start the node after {} attempts", conf.attempts))
+        let auth = Auth::CookieFile(cookie_file.clone());
+
+        let client_base = Self::create_client_base(&rpc_url, &auth)?;
+        let client = match &conf.wallet {
+            Some(wallet) => match Self::create_client_wallet(&client_base, &rpc_url, &auth, wallet)
+            {
+                Ok(client) => client,
+                Err(e) => {
+                    // If the wallet cannot be created or loaded, there might be an issue with the
+                    // work_dir or process. Kill the process and retry.
+                    let _ = process.kill();
+                    if last_attempt {
+                        return Err(e);
+                    }
+                    return Ok(None);
+                }
+            },
+            None => client_base,
+        };
+        if Self::wait_for_client(&client, Duration::from_secs(5)).is_err() {
+            // If the client times out there might be an issue with the work_dir or process. Kill
+            // the process and retry.
+            let _ = process.kill();
+            return Ok(None);
+        }
+
+        let params = ConnectParams {
+            cookie_file,
+            rpc_socket,
+            p2p_socket,
+            zmq_pub_raw_block_socket,
+            zmq_pub_raw_tx_socket,
+            zmq_pub_hash_block_socket,
+        };
+        Ok(Some((process, client, params)))
     }
 
     /// Initialize the work directory based on the provided configuration in [`Conf`].
@@ -439,28 +551,22 @@ impl Node {
     /// Returns the zmq args and the zmq socket addresses if any.
     ///
     /// # Parameters
-    /// * `enable_zmq` - If `true`, creates two ZMQ sockets:
+    /// * `enable_zmq` - If `true`, creates three ZMQ sockets, returned in this order:
     ///     - `zmq_pub_raw_tx_socket`: for raw transaction publishing.
     ///     - `zmq_pub_raw_block_socket`: for raw block publishing.
-    fn zmq_args(
-        enable_zmq: bool,
-    ) -> anyhow::Result<(Vec<String>, Option<SocketAddrV4>, Option<SocketAddrV4>)> {
-        if enable_zmq {
-            let zmq_pub_raw_tx_port = get_available_port()?;
-            let zmq_pub_raw_tx_socket = SocketAddrV4::new(LOCAL_IP, zmq_pub_raw_tx_port);
-            let zmq_pub_raw_block_port = get_available_port()?;
-            let zmq_pub_raw_block_socket = SocketAddrV4::new(LOCAL_IP, zmq_pub_raw_block_port);
-            let zmqpubrawblock_arg =
-                format!("-zmqpubrawblock=tcp://0.0.0.0:{}", zmq_pub_raw_block_port);
-            let zmqpubrawtx_arg = format!("-zmqpubrawtx=tcp://0.0.0.0:{}", zmq_pub_raw_tx_port);
-            Ok((
-                vec![zmqpubrawtx_arg, zmqpubrawblock_arg],
-                Some(zmq_pub_raw_tx_socket),
-                Some(zmq_pub_raw_block_socket),
-            ))
-        } else {
-            Ok((vec![], None, None))
+    ///     - `zmq_pub_hash_block_socket`: for block hash publishing.
+    fn zmq_args(enable_zmq: bool) -> anyhow::Result<(Vec<String>, [Option<SocketAddrV4>; 3])> {
+        if !enable_zmq {
+            return Ok((vec![], [None; 3]));
         }
+        let mut args = vec![];
+        let mut sockets = [None; 3];
+        for (socket, topic) in sockets.iter_mut().zip(["rawtx", "rawblock", "hashblock"]) {
+            let port = get_available_port()?;
+            args.push(format!("-zmqpub{}=tcp://0.0.0.0:{}", topic, port));
+            *socket = Some(SocketAddrV4::new(LOCAL_IP, port));
+        }
+        Ok((args, sockets))
     }
 
     /// Returns `Ok` once the cookie file is accessible, or an error if it times out.
@@ -498,8 +604,10 @@ impl Node {
             }
             thread::sleep(Duration::from_millis(200));
         }
-        Client::new_with_auth(rpc_url, auth.clone())
-            .map_err(|e| Error::NoBitcoindInstance(e.to_string()).into())
+        Client::new_with_auth(rpc_url, auth.clone()).map_err(|e| {
+            let msg = format!("could not create a client for {}", rpc_url);
+            anyhow::Error::new(e).context(Error::NoBitcoindInstance(msg))
+        })
     }
 
     /// Create a new RPC client connected to the given `wallet`.
@@ -519,8 +627,10 @@ impl Node {
             if client_base.create_wallet(wallet).is_ok() || client_base.load_wallet(wallet).is_ok()
             {
                 let url = format!("{}/wallet/{}", rpc_url, wallet);
-                return Client::new_with_auth(&url, auth.clone())
-                    .map_err(|e| Error::NoBitcoindInstance(e.to_string()).into());
+                return Client::new_with_auth(&url, auth.clone()).map_err(|e| {
+                    let msg = format!("could not create a client for {}", url);
+                    anyhow::Error::new(e).context(Error::NoBitcoindInstance(msg))
+                });
             }
             thread::sleep(Duration::from_millis(200));
         }
@@ -545,7 +655,12 @@ impl Node {
     }
 
     /// Stop the node, waiting correct process termination.
+    ///
+    /// Returns the exit status without calling `stop` again if the process already exited.
     pub fn stop(&mut self) -> anyhow::Result<ExitStatus> {
+        if let Some(status) = self.process.try_wait()? {
+            return Ok(status);
+        }
         self.client.stop()?;
         Ok(self.process.wait()?)
     }
@@ -559,6 +674,111 @@ impl Node {
             Auth::CookieFile(self.params.cookie_file.clone()),
         )?)
     }
+
+    /// Creates `n` confirmed outputs of `amount` each, paying to new addresses of the wallet.
+    ///
+    /// The outputs are created by a single transaction from the wallet loaded on `self.client`,
+    /// which needs to be funded, then a block is mined to confirm it. Nothing is sent or mined if
+    /// `n` is zero.
+    pub fn create_utxos(&self, n: usize, amount: Amount) -> anyhow::Result<Vec<OutPoint>> {
+        if n == 0 {
+            return Ok(vec![]);
+        }
+        let addresses = (0..n).map(|_| self.client.new_address()).collect::<Result<Vec<_>, _>>()?;
+        let outputs = addresses.iter().map(|address| (address.clone(), amount)).collect::<Vec<_>>();
+        let txid = self.send_to_many(&outputs)?;
+        let tx = self.mempool_transaction(txid)?;
+
+        let address = self.client.new_address()?;
+        let _ = self.client.generate_to_address(1, &address)?;
+
+        addresses
+            .iter()
+            .map(|address| {
+                let vout = output_index(&tx, address)?;
+                Ok(OutPoint { txid, vout })
+            })
+            .collect()
+    }
+
+    /// Sends to all `outputs` in a single, unconfirmed, transaction from the wallet loaded on
+    /// `self.client`.
+    pub fn send_to_many(&self, outputs: &[(Address, Amount)]) -> anyhow::Result<Txid> {
+        let amounts = outputs.iter().cloned().collect::<BTreeMap<_, _>>();
+        if amounts.len() != outputs.len() {
+            anyhow::bail!("duplicate address in outputs");
+        }
+        let json = self.client.send_many(amounts)?;
+        Ok(json.0.parse()?)
+    }
+
+    /// Creates a chain of `n` unconfirmed transactions, each spending an output of the one before.
+    ///
+    /// The first transaction is funded by the wallet loaded on `self.client`, which needs to be
+    /// funded. Transactions are returned in the order they were broadcast. Note that by default
+    /// Core does not accept chains of more than 25 unconfirmed transactions.
+    pub fn create_chain_of_unconfirmed(&self, n: usize) -> anyhow::Result<Vec<Transaction>> {
+        const AMOUNT: Amount = Amount::from_sat(1_000_000);
+        const FEE: Amount = Amount::from_sat(1_000);
+
+        let mut chain = Vec::with_capacity(n);
+        // The output of the previous transaction paying to the wallet, and its value.
+        let mut prev: Option<(OutPoint, Amount)> = None;
+        for _ in 0..n {
+            let address = self.client.new_address()?;
+            let tx = match prev {
+                None => {
+                    let txid = self.client.send_to_address(&address, AMOUNT)?.txid()?;
+                    self.mempool_transaction(txid)?
+                }
+                Some((previous_output, value)) => {
+                    let unsigned = Transaction {
+                        version: transaction::Version::TWO,
+                        lock_time: absolute::LockTime::ZERO,
+                        input: vec![TxIn {
+                            previous_output,
+                            script_sig: ScriptBuf::new(),
+                            sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+                            witness: Witness::new(),
+                        }],
+                        output: vec![TxOut {
+                            value: value.checked_sub(FEE).context("chain ran out of value")?,
+                            script_pubkey: address.script_pubkey(),
+                        }],
+                    };
+                    let signed = self.client.sign_raw_transaction_with_wallet(&unsigned)?;
+                    if !signed.complete {
+                        anyhow::bail!("wallet could not sign transaction {}", chain.len());
+                    }
+                    let tx: Transaction = encode::deserialize_hex(&signed.hex)?;
+                    let _ = self.client.send_raw_transaction(&tx)?;
+                    tx
+                }
+            };
+            let vout = output_index(&tx, &address)?;
+            prev =
+                Some((OutPoint { txid: tx.compute_txid(), vout }, tx.output[vout as usize].value));
+            chain.push(tx);
+        }
+        Ok(chain)
+    }
+
+    /// Gets a transaction from the mempool.
+    fn mempool_transaction(&self, txid: Txid) -> anyhow::Result<Transaction> {
+        let json = self.client.get_raw_transaction(txid)?;
+        Ok(encode::deserialize_hex(&json.0)?)
+    }
+}
+
+/// Returns the index of the output of `tx` paying to `address`.
+fn output_index(tx: &Transaction, address: &Address) -> anyhow::Result<u32> {
+    let script_pubkey = address.script_pubkey();
+    let vout = tx
+        .output
+        .iter()
+        .position(|output| output.script_pubkey == script_pubkey)
+        .context("no output pays to address")?;
+    Ok(vout as u32)
 }
 
 #[cfg(feature = "download")]
@@ -570,6 +790,20 @@ impl Node {
     pub fn from_downloaded_with_conf(conf: &Conf) -> anyhow::Result<Node> {
         Node::with_conf(downloaded_exe_path()?, conf)
     }
+
+    /// create Node struct with the downloaded executable of `version` and given Conf.
+    ///
+    /// See [`Version`] for how to download the executables of several versions.
+    pub fn with_version(version: Version, conf: &Conf) -> anyhow::Result<Node> {
+        Node::with_conf(downloaded_version_exe_path(version)?, conf)
+    }
+
+    /// Restarts the node with the downloaded executable of `version` and given Conf.
+    ///
+    /// See [`Node::restart_with`].
+    pub fn restart_with_version(&mut self, version: Version, conf: &Conf) -> anyhow::Result<()> {
+        self.restart_with(downloaded_version_exe_path(version)?, conf)
+    }
 }
 
 impl Drop for Node {
@@ -581,6 +815,68 @@ impl Drop for Node {
     }
 }
 
+/// A path to a temporary file that is deleted, if created, when the handle is dropped.
+///
+/// The file is not created, so it can be passed to RPCs (e.g. `dumpwallet`) that refuse to
+/// overwrite an existing file. It is placed in a new directory in the OS default temporary
+/// directory, or `TEMPDIR_ROOT` if set, that is removed along with its content on drop.
+///
+/// Set the `TEMPDIR_KEEP` env variable, or use [`TmpFile::keep`], to keep the file for debugging.
+#[derive(Debug)]
+pub struct TmpFile {
+    /// The directory containing the file, `None` once kept.
+    dir: Option<TempDir>,
+    /// The path to the file.
+    path: PathBuf,
+}
+
+impl TmpFile {
+    /// Creates a new temporary file path.
+    pub fn new() -> anyhow::Result<TmpFile> {
+        let dir = match env::var("TEMPDIR_ROOT") {
+            Ok(root) => TempDir::new_in(root)?,
+            Err(_) => TempDir::new()?,
+        };
+        let path = dir.path().join("file");
+        Ok(TmpFile { dir: Some(dir), path })
+    }
+
+    /// Returns the path to the file.
+    pub fn path(&self) -> &Path { &self.path }
+
+    /// Keeps the file, and its directory, after the handle is dropped and returns its path.
+    pub fn keep(mut self) -> PathBuf {
+        if let Some(dir) = self.dir.take() {
+            keep_dir(dir);
+        }
+        self.path.clone()
+    }
+}
+
+impl Drop for TmpFile {
+    fn drop(&mut self) {
+        if let Some(dir) = self.dir.take() {
+            if env::var_os("TEMPDIR_KEEP").is_some() {
+                log::info!("keeping temporary file {}", self.path.display());
+                keep_dir(dir);
+            }
+        }
+    }
+}
+
+impl std::ops::Deref for TmpFile {
+    type Target = Path;
+    fn deref(&self) -> &Path { &self.path }
+}
+
+impl AsRef<Path> for TmpFile {
+    fn as_ref(&self) -> &Path { &self.path }
+}
+
+/// Keeps `dir` after it is dropped.
+#[allow(deprecated)] // `TempDir::keep` requires tempfile v3.20.
+fn keep_dir(dir: TempDir) { let _ = dir.into_path(); }
+
 /// Returns a non-used local port if available.
 ///
 /// Note there is a race condition during the time the method check availability and the caller.
@@ -603,8 +899,32 @@ impl From<client_sync::Error> for Error {
 pub fn downloaded_exe_path() -> anyhow::Result<String> { Err(Error::NoFeature.into()) }
 
 /// Provide the bitcoind executable path if a version feature has been specified.
+///
+/// The version is the one selected with the `BITCOIND_VERSION` env var, if set, otherwise the one
+/// of the version feature.
 #[cfg(feature = "download")]
 pub fn downloaded_exe_path() -> anyhow::Result<String> {
+    match Version::from_env()? {
+        Some(version) => downloaded_version_exe_path(version),
+        None => download_path(VERSION),
+    }
+}
+
+/// Provide the path of the downloaded bitcoind executable of `version`.
+///
+/// Returns an error if the executable was not downloaded, see [`Version`].
+#[cfg(feature = "download")]
+pub fn downloaded_version_exe_path(version: Version) -> anyhow::Result<String> {
+    let path = download_path(version.as_str())?;
+    if !Path::new(&path).exists() {
+        return Err(Error::VersionNotDownloaded(version).into());
+    }
+    Ok(path)
+}
+
+/// Returns the path of the bitcoind executable of `version` in the download directory.
+#[cfg(feature = "download")]
+fn download_path(version: &str) -> anyhow::Result<String> {
     if std::env::var_os("BITCOIND_SKIP_DOWNLOAD").is_some() {
         return Err(Error::SkipDownload.into());
     }
@@ -616,7 +936,7 @@ pub fn downloaded_exe_path() -> anyhow::Result<String> {
         p.push("bitcoin");
         p
     };
-    path.push(format!("bitcoin-{}", VERSION));
+    path.push(format!("bitcoin-{}", version));
     path.push("bin");
 
     if cfg!(target_os = "windows") {
@@ -633,20 +953,58 @@ pub fn downloaded_exe_path() -> anyhow::Result<String> {
 ///
 /// 1) If it's specified in the `BITCOIND_EXE` env var.
 /// 2) If there is no env var but the auto-download feature is enabled, returns the
-///    path of the downloaded executable.
+///    path of the downloaded executable, of the version in the `BITCOIND_VERSION` env var if set.
+///    Returns an error if `BITCOIND_VERSION` is set but the version was not downloaded.
 /// 3) If neither of the precedent are available, the `bitcoind` executable is searched in the `PATH`.
 pub fn exe_path() -> anyhow::Result<String> {
     if let Ok(path) = std::env::var("BITCOIND_EXE") {
         return Ok(path);
     }
-    if let Ok(path) = downloaded_exe_path() {
-        return Ok(path);
+    match downloaded_exe_path() {
+        Ok(path) => return Ok(path),
+        // Don't fall back to an executable of another version than the one selected.
+        Err(e) if cfg!(feature = "download") && env::var_os("BITCOIND_VERSION").is_some() =>
+            return Err(e),
+        Err(_) => {}
     }
     which::which("bitcoind")
         .map_err(|_| Error::NoBitcoindExecutableFound.into())
         .map(|p| p.display().to_string())
 }
 
+/// Returns the network specific directory in the data directory, relative to the data directory.
+fn network_dir(network: Network) -> &'static str {
+    match network.to_core_arg() {
+        "main" => "",
+        "test" => "testnet3",
+        // The other networks use their Core name.
+        chain => chain,
+    }
+}
+
+/// Returns an error if the chain selected by `args` is not `network`.
+fn check_network(args: &[&str], network: Network) -> anyhow::Result<()> {
+    let mut selected = Network::Bitcoin;
+    for arg in args {
+        let chain = match arg.trim_start_matches('-') {
+            "testnet" | "testnet=1" => "test",
+            "testnet4" | "testnet4=1" => "testnet4",
+            "signet" | "signet=1" => "signet",
+            "regtest" | "regtest=1" => "regtest",
+            arg => match arg.strip_prefix("chain=") {
+                Some(chain) => chain,
+                None => continue,
+            },
+        };
+        selected = Network::from_core_arg(chain)
+            .with_context(|| format!("unknown chain in args: {}", chain))?;
+    }
+    if selected != network {
+        return Err(Error::NetworkMismatch { args: selected, network }.into());
+    }
+    Ok(())
+}
+
 /// Validate the specified arg if there is any unavailable or deprecated one.
 pub fn validate_args(args: Vec<&str>) -> anyhow::Result<Vec<&str>> {
     args.iter().try_for_each(|arg| {
@@ -677,6 +1035,39 @@ mod test {
         assert_eq!(format!("127.0.0.1:{}", port), format!("{}", socket));
     }
 
+    #[test]
+    fn test_check_network() {
+        check_network(&["-regtest", "-fallbackfee=0.0001"], Network::Regtest).unwrap();
+        check_network(&["-chain=signet"], Network::Signet).unwrap();
+        check_network(&["-testnet"], Network::Testnet).unwrap();
+        check_network(&[], Network::Bitcoin).unwrap();
+
+        let err = check_network(&["-regtest"], Network::Signet).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<Error>(),
+            Some(Error::NetworkMismatch { args: Network::Regtest, network: Network::Signet })
+        ));
+        assert!(check_network(&["-chain=moon"], Network::Regtest).is_err());
+        assert_eq!(network_dir(Network::Testnet), "testnet3");
+    }
+
+    #[test]
+    fn test_tmp_file() {
+        let tmp = TmpFile::new().unwrap();
+        let path = tmp.path().to_path_buf();
+        assert!(!path.exists());
+        fs::write(&tmp, "test").unwrap();
+        drop(tmp);
+        assert!(!path.exists());
+        assert!(!path.parent().unwrap().exists());
+
+        let tmp = TmpFile::new().unwrap();
+        fs::write(&tmp, "test").unwrap();
+        let path = tmp.keep();
+        assert!(path.exists());
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
     #[test]
     fn test_node_get_blockchain_info() {
         let exe = init();
@@ -698,6 +1089,54 @@ mod test {
         assert_eq!(1, info.blocks);
     }
 
+    #[test]
+    fn test_create_utxos() {
+        let exe = init();
+        let node = Node::new(exe).unwrap();
+        let address = node.client.new_address().unwrap();
+        let _ = node.client.generate_to_address(101, &address).unwrap();
+
+        let amount = Amount::from_sat(100_000);
+        let outpoints = node.create_utxos(3, amount).unwrap();
+        assert_eq!(outpoints.len(), 3);
+        for outpoint in outpoints {
+            let txout = node.client.get_tx_out(outpoint.txid, outpoint.vout.into()).unwrap();
+            assert_eq!(txout.confirmations, 1);
+        }
+    }
+
+    #[test]
+    fn test_send_to_many() {
+        let exe = init();
+        let node = Node::new(exe).unwrap();
+        let address = node.client.new_address().unwrap();
+        let _ = node.client.generate_to_address(101, &address).unwrap();
+
+        let amount = Amount::from_sat(100_000);
+        let outputs =
+            (0..2).map(|_| (node.client.new_address().unwrap(), amount)).collect::<Vec<_>>();
+        let _ = node.send_to_many(&outputs).unwrap();
+
+        let duplicate = [(address.clone(), amount), (address, amount)];
+        assert!(node.send_to_many(&duplicate).is_err());
+    }
+
+    #[test]
+    fn test_create_chain_of_unconfirmed() {
+        let exe = init();
+        let node = Node::new(exe).unwrap();
+        let address = node.client.new_address().unwrap();
+        let _ = node.client.generate_to_address(101, &address).unwrap();
+
+        let chain = node.create_chain_of_unconfirmed(5).unwrap();
+        assert_eq!(chain.len(), 5);
+        for pair in chain.windows(2) {
+            assert_eq!(pair[1].input[0].previous_output.txid, pair[0].compute_txid());
+        }
+        let mempool = node.client.get_raw_mempool().unwrap();
+        assert_eq!(mempool.0.len(), 5);
+    }
+
     #[test]
     #[cfg(feature = "0_21_2")]
     fn test_getindexinfo() {
@@ -762,6 +1201,53 @@ mod test {
         assert_eq!(wallet_balance_1, wallet_balance_2);
     }
 
+    #[test]
+    fn test_restart() {
+        let exe = init();
+        let mut node = Node::new(&exe).unwrap();
+        let address = node.client.new_address().unwrap();
+        node.client.generate_to_address(101, &address).unwrap();
+        let best_block = node.client.get_best_block_hash().unwrap();
+        let balance = node.client.get_balance().unwrap();
+        let workdir = node.workdir();
+
+        assert!(node.stop().unwrap().success());
+        assert!(node.client.get_best_block_hash().is_err());
+        // Stopping again returns the exit status of the stopped process.
+        assert!(node.stop().unwrap().success());
+
+        let conf = Conf::<'_> { p2p: P2P::Yes, ..Default::default() };
+        node.restart(&conf).unwrap();
+        assert_eq!(node.workdir(), workdir);
+        assert!(node.params.p2p_socket.is_some());
+        assert_eq!(node.client.get_best_block_hash().unwrap(), best_block);
+        assert_eq!(node.client.get_balance().unwrap(), balance);
+
+        // Restarting a running node stops it first.
+        node.restart(&Conf::default()).unwrap();
+        assert_eq!(node.client.get_best_block_hash().unwrap(), best_block);
+    }
+
+    #[test]
+    #[cfg(feature = "26_2")]
+    fn test_snapshot() {
+        let exe = init();
+        let node = Node::new(&exe).unwrap();
+        let address = node.client.new_address().unwrap();
+        node.client.generate_to_address(110, &address).unwrap();
+
+        let snapshot = TmpFile::new().unwrap();
+        let dump = node.dump_snapshot(&snapshot).unwrap();
+        assert_eq!(dump.base_height, 110);
+        assert!(dump.coins_written > 0);
+
+        // The headers are copied, but the regtest assumeutxo data is for the chain of the Core
+        // functional tests so the snapshot itself is rejected.
+        let other = Node::new(&exe).unwrap();
+        assert!(other.load_snapshot(&node, &dump).is_err());
+        assert_eq!(other.client.get_chain_states().unwrap().headers, 110);
+    }
+
     #[test]
     fn test_multi_p2p() {
         let exe = init();
@@ -790,6 +1276,32 @@ mod test {
         assert_eq!(node3_peers, 1, "listen false but more than 1 peer");
     }
 
+    #[test]
+    fn test_cluster() {
+        let exe = init();
+        let cluster = Cluster::new(&exe, 3).unwrap();
+        assert!(cluster.is_connected(1, 0).unwrap());
+        assert!(cluster.is_connected(1, 2).unwrap());
+        assert!(!cluster.is_connected(0, 2).unwrap());
+
+        let address = cluster[0].client.new_address().unwrap();
+        cluster[0].client.generate_to_address(1, &address).unwrap();
+        cluster.sync_all().unwrap();
+        assert!(cluster.iter().all(|node| node.client.get_block_count().unwrap().0 == 1));
+
+        // Node 2 mines on its own while disconnected.
+        cluster.disconnect(1, 2).unwrap();
+        assert!(!cluster.is_connected(1, 2).unwrap());
+        assert!(cluster.disconnect(1, 2).is_err());
+        let address = cluster[2].client.new_address().unwrap();
+        cluster[2].client.generate_to_address(2, &address).unwrap();
+        assert_eq!(cluster[0].client.get_block_count().unwrap().0, 1);
+
+        cluster.connect(2, 0).unwrap();
+        cluster.sync_all().unwrap();
+        assert_eq!(cluster[1].client.get_block_count().unwrap().0, 3);
+    }
+
     #[cfg(feature = "0_19_1")]
     #[test]
     fn test_multi_wallet() {
@@ -878,7 +1390,7 @@ mod test {
 
         let node = Node::with_conf(exe, &conf).unwrap();
 
-        let auth = Auth::UserPass("bitcoind".to_string(), "bitcoind".to_string());
+        let auth = Auth::UserPass("bitcoind".to_string(), "bitcoind".into());
         let client = Client::new_with_auth(
             format!("{}/wallet/default", node.rpc_url().as_str()).as_str(),
             auth,
@@ -907,6 +1419,7 @@ mod test {
 
         assert_eq!(user, result_values.user);
         assert_eq!(password, result_values.password);
+        assert!(!format!("{:?}", result_values).contains(password));
     }
 
     #[test]
@@ -916,6 +1429,7 @@ mod test {
 
         assert!(node.params.zmq_pub_raw_tx_socket.is_some());
         assert!(node.params.zmq_pub_raw_block_socket.is_some());
+        assert!(node.params.zmq_pub_hash_block_socket.is_some());
     }
 
     #[test]
@@ -925,6 +1439,7 @@ mod test {
 
         assert!(node.params.zmq_pub_raw_tx_socket.is_none());
         assert!(node.params.zmq_pub_raw_block_socket.is_none());
+        assert!(node.params.zmq_pub_hash_block_socket.is_none());
     }
 
     fn peers_connected(client: &Client) -> usize {
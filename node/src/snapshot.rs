@@ -0,0 +1,52 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Helpers for the assumeutxo snapshots of Core v26 and later.
+//!
+//! Note that a node only loads a snapshot whose base block is in the assumeutxo data of its
+//! chain parameters. On regtest these are fixed heights of the chain built by the Core functional
+//! tests, so a snapshot of an arbitrary regtest chain is rejected by `loadtxoutset`.
+
+use std::path::Path;
+
+use anyhow::Context;
+
+use crate::{mtype, Node};
+
+impl Node {
+    /// Dumps the UTXO set as of the tip of the node to `path`.
+    ///
+    /// `path` must not exist, Core refuses to overwrite a file.
+    pub fn dump_snapshot(&self, path: &Path) -> anyhow::Result<mtype::DumpTxOutSet> {
+        let path = path.to_str().context("snapshot path is not valid UTF-8")?;
+        #[cfg(not(feature = "29_0"))]
+        let json = self.client.dump_tx_out_set(path)?;
+        #[cfg(feature = "29_0")]
+        let json = self.client.dump_tx_out_set(path, "latest")?;
+        Ok(json.into_model()?)
+    }
+
+    /// Loads the snapshot `dump` of the `source` node into this node.
+    ///
+    /// The node needs the headers up to the base block of the snapshot, the ones it is missing
+    /// are copied from `source` with `submitheader` first. The node must not have synced past
+    /// the base block.
+    ///
+    /// A snapshot of an arbitrary regtest chain can not be loaded, only one whose base block is in
+    /// the assumeutxo data of the node (see the module docs).
+    pub fn load_snapshot(
+        &self,
+        source: &Node,
+        dump: &mtype::DumpTxOutSet,
+    ) -> anyhow::Result<mtype::LoadTxOutSet> {
+        let headers = self.client.get_chain_states()?.into_model()?.headers;
+        for (height, header) in source.client.block_headers_from(headers + 1)? {
+            if height > dump.base_height {
+                break;
+            }
+            self.client.submit_header(&header)?;
+        }
+
+        let json = self.client.load_tx_out_set(&dump.path)?;
+        Ok(json.into_model()?)
+    }
+}
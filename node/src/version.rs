@@ -0,0 +1,164 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! The versions of Bitcoin Core that can be selected at runtime.
+//!
+//! See [`Version`].
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Error;
+
+/// A version of Bitcoin Core supported by this crate, one for each version feature.
+///
+/// Selecting the version at runtime allows testing against several versions without compiling
+/// once per version feature. The executables are downloaded by the build script, when the
+/// `download` feature is enabled, for the version feature and for the versions listed in the
+/// `BITCOIND_VERSION` and the comma separated `BITCOIND_VERSIONS` env vars e.g.,
+/// `BITCOIND_VERSIONS=27.2,28.2,29.0 cargo test --features=29_0,download`.
+///
+/// Note that `Node::client` is always the client of the version feature, the calls whose JSON
+/// changed between the versions may fail to deserialize for a node of another version. Use
+/// `Client::call` with `serde_json::Value` for those.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Version {
+    /// Bitcoin Core v0.17.2.
+    V0_17_2,
+    /// Bitcoin Core v0.18.1.
+    V0_18_1,
+    /// Bitcoin Core v0.19.1.
+    V0_19_1,
+    /// Bitcoin Core v0.20.2.
+    V0_20_2,
+    /// Bitcoin Core v0.21.2.
+    V0_21_2,
+    /// Bitcoin Core v22.1.
+    V22_1,
+    /// Bitcoin Core v23.2.
+    V23_2,
+    /// Bitcoin Core v24.2.
+    V24_2,
+    /// Bitcoin Core v25.2.
+    V25_2,
+    /// Bitcoin Core v26.2.
+    V26_2,
+    /// Bitcoin Core v27.0.
+    V27_0,
+    /// Bitcoin Core v27.1.
+    V27_1,
+    /// Bitcoin Core v27.2.
+    V27_2,
+    /// Bitcoin Core v28.0.
+    V28_0,
+    /// Bitcoin Core v28.1.
+    V28_1,
+    /// Bitcoin Core v28.2.
+    V28_2,
+    /// Bitcoin Core v29.0.
+    V29_0,
+    /// Bitcoin Core v30.0.
+    V30_0,
+    /// Bitcoin Core v30.2.
+    V30_2,
+}
+
+impl Version {
+    /// All the supported versions, from the oldest to the latest.
+    pub const ALL: [Version; 19] = [
+        Version::V0_17_2,
+        Version::V0_18_1,
+        Version::V0_19_1,
+        Version::V0_20_2,
+        Version::V0_21_2,
+        Version::V22_1,
+        Version::V23_2,
+        Version::V24_2,
+        Version::V25_2,
+        Version::V26_2,
+        Version::V27_0,
+        Version::V27_1,
+        Version::V27_2,
+        Version::V28_0,
+        Version::V28_1,
+        Version::V28_2,
+        Version::V29_0,
+        Version::V30_0,
+        Version::V30_2,
+    ];
+
+    /// Returns the version string e.g., "28.2", same format as [`VERSION`](crate::VERSION).
+    pub fn as_str(self) -> &'static str {
+        use Version::*;
+
+        match self {
+            V0_17_2 => "0.17.2",
+            V0_18_1 => "0.18.1",
+            V0_19_1 => "0.19.1",
+            V0_20_2 => "0.20.2",
+            V0_21_2 => "0.21.2",
+            V22_1 => "22.1",
+            V23_2 => "23.2",
+            V24_2 => "24.2",
+            V25_2 => "25.2",
+            V26_2 => "26.2",
+            V27_0 => "27.0",
+            V27_1 => "27.1",
+            V27_2 => "27.2",
+            V28_0 => "28.0",
+            V28_1 => "28.1",
+            V28_2 => "28.2",
+            V29_0 => "29.0",
+            V30_0 => "30.0",
+            V30_2 => "30.2",
+        }
+    }
+
+    /// Returns the version selected with the `BITCOIND_VERSION` env var, if set.
+    pub fn from_env() -> anyhow::Result<Option<Version>> {
+        match std::env::var("BITCOIND_VERSION") {
+            Ok(version) => Ok(Some(version.parse()?)),
+            Err(_) => Ok(None),
+        }
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+impl FromStr for Version {
+    type Err = Error;
+
+    /// Parses a version string like "28.2", "v28.2" or the name of the feature "28_2".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let version = s.trim().trim_start_matches('v').replace('_', ".");
+        Version::ALL
+            .into_iter()
+            .find(|v| v.as_str() == version)
+            .ok_or_else(|| Error::UnknownVersion(s.to_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_from_str() {
+        for version in Version::ALL {
+            assert_eq!(version.as_str().parse::<Version>().unwrap(), version);
+            assert_eq!(version.to_string().parse::<Version>().unwrap(), version);
+        }
+        assert_eq!("v28.2".parse::<Version>().unwrap(), Version::V28_2);
+        assert_eq!("0_21_2".parse::<Version>().unwrap(), Version::V0_21_2);
+        assert!(matches!("28.3".parse::<Version>(), Err(Error::UnknownVersion(_))));
+        assert!("28".parse::<Version>().is_err());
+    }
+
+    #[test]
+    fn version_ordering() {
+        assert!(Version::ALL.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(Version::ALL.last().unwrap().as_str(), "30.2");
+    }
+}
@@ -0,0 +1,280 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! A subscriber to the ZMQ notifications published by a node.
+//!
+//! Implements just enough of ZMTP 3.0, the ZMQ wire protocol, to subscribe to the `-zmqpub*`
+//! notifications (the `NULL` security mechanism and the `SUB` socket type) so that `libzmq` is not
+//! needed. See [`ZmqSubscriber`].
+
+use std::io::{BufReader, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+use anyhow::Context;
+use corepc_client::bitcoin::consensus::encode;
+use corepc_client::bitcoin::hashes::Hash;
+use corepc_client::bitcoin::{Block, BlockHash, Transaction};
+
+/// How long the handshake with the node is waited for.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The largest frame read, a block is at most 4 MB.
+const MAX_FRAME_LEN: u64 = 8_000_000;
+
+/// The frame flag set if more frames of the message follow.
+const FLAG_MORE: u8 = 0x01;
+/// The frame flag set if the frame size is 8 bytes long, instead of 1.
+const FLAG_LONG: u8 = 0x02;
+/// The frame flag set if the frame is a command, instead of a message.
+const FLAG_COMMAND: u8 = 0x04;
+
+/// A notification published by the node.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ZmqEvent {
+    /// A block connected to the chain, published with `-zmqpubrawblock`.
+    Block(Block),
+    /// A transaction added to the mempool or in a connected block, published with `-zmqpubrawtx`.
+    Transaction(Transaction),
+    /// The hash of a block connected to the chain, published with `-zmqpubhashblock`.
+    BlockHash(BlockHash),
+}
+
+/// A subscriber to all the notifications published on a ZMQ endpoint of the node.
+///
+/// The endpoints are in the [`ConnectParams`](crate::ConnectParams) of a node spawned with
+/// `Conf::enable_zmq`. Like with any ZMQ subscriber, the notifications published before the
+/// subscription reaches the node are not received.
+///
+/// ```no_run
+/// # fn main() -> anyhow::Result<()> {
+/// use corepc_node::zmq::{ZmqEvent, ZmqSubscriber};
+///
+/// let mut conf = corepc_node::Conf::default();
+/// conf.enable_zmq = true;
+/// let node = corepc_node::Node::with_conf(corepc_node::exe_path()?, &conf)?;
+/// let mut blocks = ZmqSubscriber::connect(node.params.zmq_pub_raw_block_socket.unwrap())?;
+///
+/// let address = node.client.new_address()?;
+/// node.client.generate_to_address(1, &address)?;
+/// if let ZmqEvent::Block(block) = blocks.recv()? {
+///     println!("block {}", block.block_hash());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ZmqSubscriber {
+    stream: BufReader<TcpStream>,
+}
+
+impl ZmqSubscriber {
+    /// Connects to the ZMQ endpoint at `addr` and subscribes to all its notifications.
+    pub fn connect<A: ToSocketAddrs>(addr: A) -> anyhow::Result<ZmqSubscriber> {
+        let stream = TcpStream::connect(addr).context("failed to connect to the zmq endpoint")?;
+        stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+        let mut subscriber = ZmqSubscriber { stream: BufReader::new(stream) };
+        subscriber.handshake().context("zmq handshake failed")?;
+        subscriber.stream.get_ref().set_read_timeout(None)?;
+        Ok(subscriber)
+    }
+
+    /// Sets how long `recv` waits for a notification, `None` waits forever.
+    pub fn set_timeout(&self, timeout: Option<Duration>) -> anyhow::Result<()> {
+        Ok(self.stream.get_ref().set_read_timeout(timeout)?)
+    }
+
+    /// Waits for the next notification.
+    ///
+    /// Notifications for other topics than the ones in [`ZmqEvent`] are skipped.
+    pub fn recv(&mut self) -> anyhow::Result<ZmqEvent> {
+        loop {
+            // The topic, the body and the sequence number of the notification.
+            let frames = self.read_message()?;
+            let (topic, body) = match frames.as_slice() {
+                [topic, body, ..] => (topic.as_slice(), body),
+                _ => anyhow::bail!("notification with {} frames", frames.len()),
+            };
+            let event = match topic {
+                b"rawblock" => ZmqEvent::Block(encode::deserialize(body)?),
+                b"rawtx" => ZmqEvent::Transaction(encode::deserialize(body)?),
+                b"hashblock" => {
+                    let mut hash: [u8; 32] =
+                        body.as_slice().try_into().context("block hash is not 32 bytes")?;
+                    // The hash is published in the byte order it is displayed in.
+                    hash.reverse();
+                    ZmqEvent::BlockHash(BlockHash::from_byte_array(hash))
+                }
+                _ => continue,
+            };
+            return Ok(event);
+        }
+    }
+
+    /// Exchanges the greeting and the `READY` commands with the node, then subscribes.
+    fn handshake(&mut self) -> anyhow::Result<()> {
+        self.stream.get_mut().write_all(&greeting())?;
+        let mut greeting = [0; 64];
+        self.stream.read_exact(&mut greeting)?;
+        if greeting[0] != 0xFF || greeting[9] & 0x01 != 0x01 || greeting[10] < 3 {
+            anyhow::bail!("the endpoint does not speak ZMTP 3");
+        }
+        if &greeting[12..32] != mechanism().as_slice() {
+            anyhow::bail!("the endpoint does not use the NULL security mechanism");
+        }
+
+        self.write_frame(FLAG_COMMAND, &ready_command())?;
+        let (flags, command) = self.read_frame()?;
+        if flags & FLAG_COMMAND == 0 || command.get(..6) != Some(b"\x05READY".as_slice()) {
+            anyhow::bail!("the endpoint did not send READY");
+        }
+
+        // In ZMTP 3.0 a subscription is a message starting with 1, the empty topic matches all.
+        self.write_frame(0, &[0x01])
+    }
+
+    /// Reads the frames of the next message, skipping commands.
+    fn read_message(&mut self) -> anyhow::Result<Vec<Vec<u8>>> {
+        let mut frames = vec![];
+        loop {
+            let (flags, frame) = self.read_frame()?;
+            if flags & FLAG_COMMAND != 0 {
+                continue;
+            }
+            frames.push(frame);
+            if flags & FLAG_MORE == 0 {
+                return Ok(frames);
+            }
+        }
+    }
+
+    /// Reads a frame, returning its flags and body.
+    fn read_frame(&mut self) -> anyhow::Result<(u8, Vec<u8>)> {
+        let mut flags = [0; 1];
+        self.stream.read_exact(&mut flags)?;
+        let len = if flags[0] & FLAG_LONG != 0 {
+            let mut len = [0; 8];
+            self.stream.read_exact(&mut len)?;
+            u64::from_be_bytes(len)
+        } else {
+            let mut len = [0; 1];
+            self.stream.read_exact(&mut len)?;
+            u64::from(len[0])
+        };
+        if len > MAX_FRAME_LEN {
+            anyhow::bail!("frame of {} bytes is too large", len);
+        }
+        let mut body = vec![0; len as usize];
+        self.stream.read_exact(&mut body)?;
+        Ok((flags[0], body))
+    }
+
+    /// Writes a single frame with `flags` and `body`, which must be shorter than 256 bytes.
+    fn write_frame(&mut self, flags: u8, body: &[u8]) -> anyhow::Result<()> {
+        let mut frame = vec![flags, body.len() as u8];
+        frame.extend_from_slice(body);
+        Ok(self.stream.get_mut().write_all(&frame)?)
+    }
+}
+
+/// Returns the ZMTP 3.0 greeting of a client using the `NULL` security mechanism.
+fn greeting() -> Vec<u8> {
+    // The signature, then the version.
+    let mut greeting = vec![0xFF, 0, 0, 0, 0, 0, 0, 0, 0, 0x7F, 3, 0];
+    greeting.extend_from_slice(&mechanism());
+    // Not the server, then the filler.
+    greeting.extend_from_slice(&[0; 32]);
+    greeting
+}
+
+/// Returns the name of the `NULL` security mechanism, padded as in the greeting.
+fn mechanism() -> [u8; 20] {
+    let mut mechanism = [0; 20];
+    mechanism[..4].copy_from_slice(b"NULL");
+    mechanism
+}
+
+/// Returns the body of the `READY` command of a `SUB` socket.
+fn ready_command() -> Vec<u8> {
+    let mut command = b"\x05READY\x0bSocket-Type".to_vec();
+    command.extend_from_slice(&3u32.to_be_bytes());
+    command.extend_from_slice(b"SUB");
+    command
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::TcpListener;
+    use std::thread;
+
+    use corepc_client::bitcoin::constants::genesis_block;
+    use corepc_client::bitcoin::Network;
+
+    use super::*;
+
+    /// Writes a frame, using a long size if needed like the node.
+    fn write_frame(stream: &mut TcpStream, flags: u8, body: &[u8]) {
+        let mut frame = vec![];
+        if body.len() > 255 {
+            frame.push(flags | FLAG_LONG);
+            frame.extend_from_slice(&(body.len() as u64).to_be_bytes());
+        } else {
+            frame.extend_from_slice(&[flags, body.len() as u8]);
+        }
+        frame.extend_from_slice(body);
+        stream.write_all(&frame).unwrap();
+    }
+
+    /// Writes a notification like the node does, the topic, the body and the sequence number.
+    fn publish(stream: &mut TcpStream, topic: &[u8], body: &[u8]) {
+        write_frame(stream, FLAG_MORE, topic);
+        write_frame(stream, FLAG_MORE, body);
+        write_frame(stream, 0, &0u32.to_le_bytes());
+    }
+
+    #[test]
+    fn subscribe_and_recv() {
+        let block = genesis_block(Network::Regtest);
+        let tx = block.txdata[0].clone();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let publisher = {
+            let block = block.clone();
+            thread::spawn(move || {
+                let (mut stream, _) = listener.accept().unwrap();
+                stream.write_all(&greeting()).unwrap();
+                let mut greeting = [0; 64];
+                stream.read_exact(&mut greeting).unwrap();
+                assert_eq!(&greeting[12..16], b"NULL");
+
+                let mut ready = [0; 2 + 25];
+                stream.read_exact(&mut ready).unwrap();
+                assert_eq!(ready[..2], [FLAG_COMMAND, 25]);
+                assert!(ready.ends_with(b"SUB"));
+                let mut command = b"\x05READY\x0bSocket-Type".to_vec();
+                command.extend_from_slice(&3u32.to_be_bytes());
+                command.extend_from_slice(b"PUB");
+                write_frame(&mut stream, FLAG_COMMAND, &command);
+
+                let mut subscribe = [0; 3];
+                stream.read_exact(&mut subscribe).unwrap();
+                assert_eq!(subscribe, [0, 1, 1]);
+
+                publish(&mut stream, b"rawtx", &encode::serialize(&block.txdata[0]));
+                publish(&mut stream, b"sequence", &[0; 33]);
+                publish(&mut stream, b"rawblock", &encode::serialize(&block));
+                let mut hash = block.block_hash().to_byte_array();
+                hash.reverse();
+                publish(&mut stream, b"hashblock", &hash);
+            })
+        };
+
+        let mut subscriber = ZmqSubscriber::connect(addr).unwrap();
+        subscriber.set_timeout(Some(Duration::from_secs(10))).unwrap();
+        assert_eq!(subscriber.recv().unwrap(), ZmqEvent::Transaction(tx));
+        assert_eq!(subscriber.recv().unwrap(), ZmqEvent::Block(block.clone()));
+        assert_eq!(subscriber.recv().unwrap(), ZmqEvent::BlockHash(block.block_hash()));
+        publisher.join().unwrap();
+        assert!(subscriber.recv().is_err());
+    }
+}
@@ -5,7 +5,7 @@
 # shellcheck disable=SC2034
 
 # Test all these features with "std" enabled.
-FEATURES_WITH_STD=""
+FEATURES_WITH_STD="bitcoin-0-31"
 
 # Test all these features without "std" enabled.
 FEATURES_WITHOUT_STD=""
@@ -0,0 +1,212 @@
+// SPDX-License-Identifier: CC0-1.0
+
+//! Conversions to the types of `rust-bitcoin` v0.31, the previous major release.
+//!
+//! The `model` types use the release re-exported as `corepc_types::bitcoin`. A crate that can not
+//! bump its own `bitcoin` dependency at the same time as this crate enables the `bitcoin-0-31`
+//! feature and converts the modelled fields it uses with [`ToBitcoin031`].
+//!
+//! ```ignore
+//! use corepc_types::compat::ToBitcoin031 as _;
+//!
+//! let model = json.into_model()?;
+//! let block: bitcoin_0_31::Block = model.0.to_bitcoin_0_31();
+//! ```
+
+/// Re-export of `rust-bitcoin` v0.31.
+pub extern crate bitcoin_0_31;
+
+use bitcoin::address::NetworkUnchecked;
+use bitcoin::consensus::encode;
+use bitcoin::hashes::Hash as _;
+use bitcoin_0_31::hashes::Hash as _;
+
+/// Converts a `rust-bitcoin` type to the same type of `rust-bitcoin` v0.31.
+pub trait ToBitcoin031 {
+    /// The type in `rust-bitcoin` v0.31.
+    type Target;
+
+    /// Converts `self` to the type in `rust-bitcoin` v0.31.
+    fn to_bitcoin_0_31(&self) -> Self::Target;
+}
+
+impl<T: ToBitcoin031> ToBitcoin031 for Option<T> {
+    type Target = Option<T::Target>;
+
+    fn to_bitcoin_0_31(&self) -> Self::Target { self.as_ref().map(T::to_bitcoin_0_31) }
+}
+
+impl<T: ToBitcoin031> ToBitcoin031 for Vec<T> {
+    type Target = Vec<T::Target>;
+
+    fn to_bitcoin_0_31(&self) -> Self::Target { self.iter().map(T::to_bitcoin_0_31).collect() }
+}
+
+/// Implements `ToBitcoin031` for hash types, which have the same bytes in both releases.
+macro_rules! impl_hash {
+    ($($ty:ident)::+) => {
+        impl ToBitcoin031 for bitcoin::$($ty)::+ {
+            type Target = bitcoin_0_31::$($ty)::+;
+
+            fn to_bitcoin_0_31(&self) -> Self::Target {
+                Self::Target::from_byte_array(self.to_byte_array())
+            }
+        }
+    };
+}
+impl_hash!(Txid);
+impl_hash!(Wtxid);
+impl_hash!(BlockHash);
+impl_hash!(TxMerkleNode);
+impl_hash!(PubkeyHash);
+impl_hash!(ScriptHash);
+impl_hash!(hashes::hash160::Hash);
+impl_hash!(hashes::sha256::Hash);
+
+/// Implements `ToBitcoin031` for types with the same consensus encoding in both releases.
+macro_rules! impl_consensus {
+    ($($ty:ident)::+) => {
+        impl ToBitcoin031 for bitcoin::$($ty)::+ {
+            type Target = bitcoin_0_31::$($ty)::+;
+
+            fn to_bitcoin_0_31(&self) -> Self::Target {
+                bitcoin_0_31::consensus::deserialize(&encode::serialize(self))
+                    .expect("both releases use the same consensus encoding")
+            }
+        }
+    };
+}
+impl_consensus!(Block);
+impl_consensus!(block::Header);
+impl_consensus!(OutPoint);
+impl_consensus!(Transaction);
+impl_consensus!(TxIn);
+impl_consensus!(TxOut);
+impl_consensus!(Witness);
+
+impl ToBitcoin031 for bitcoin::Amount {
+    type Target = bitcoin_0_31::Amount;
+
+    fn to_bitcoin_0_31(&self) -> Self::Target { Self::Target::from_sat(self.to_sat()) }
+}
+
+impl ToBitcoin031 for bitcoin::SignedAmount {
+    type Target = bitcoin_0_31::SignedAmount;
+
+    fn to_bitcoin_0_31(&self) -> Self::Target { Self::Target::from_sat(self.to_sat()) }
+}
+
+impl ToBitcoin031 for bitcoin::FeeRate {
+    type Target = bitcoin_0_31::FeeRate;
+
+    fn to_bitcoin_0_31(&self) -> Self::Target {
+        Self::Target::from_sat_per_kwu(self.to_sat_per_kwu())
+    }
+}
+
+impl ToBitcoin031 for bitcoin::Weight {
+    type Target = bitcoin_0_31::Weight;
+
+    fn to_bitcoin_0_31(&self) -> Self::Target { Self::Target::from_wu(self.to_wu()) }
+}
+
+impl ToBitcoin031 for bitcoin::ScriptBuf {
+    type Target = bitcoin_0_31::ScriptBuf;
+
+    fn to_bitcoin_0_31(&self) -> Self::Target { Self::Target::from_bytes(self.to_bytes()) }
+}
+
+impl ToBitcoin031 for bitcoin::Sequence {
+    type Target = bitcoin_0_31::Sequence;
+
+    fn to_bitcoin_0_31(&self) -> Self::Target { bitcoin_0_31::Sequence(self.0) }
+}
+
+impl ToBitcoin031 for bitcoin::CompactTarget {
+    type Target = bitcoin_0_31::CompactTarget;
+
+    fn to_bitcoin_0_31(&self) -> Self::Target { Self::Target::from_consensus(self.to_consensus()) }
+}
+
+impl ToBitcoin031 for bitcoin::PublicKey {
+    type Target = bitcoin_0_31::PublicKey;
+
+    fn to_bitcoin_0_31(&self) -> Self::Target {
+        Self::Target::from_slice(&self.to_bytes()).expect("a valid public key is valid in both")
+    }
+}
+
+impl ToBitcoin031 for bitcoin::bip32::Xpub {
+    type Target = bitcoin_0_31::bip32::Xpub;
+
+    fn to_bitcoin_0_31(&self) -> Self::Target {
+        Self::Target::decode(&self.encode()).expect("a valid xpub is valid in both")
+    }
+}
+
+impl ToBitcoin031 for bitcoin::Address<NetworkUnchecked> {
+    type Target = bitcoin_0_31::Address<bitcoin_0_31::address::NetworkUnchecked>;
+
+    fn to_bitcoin_0_31(&self) -> Self::Target {
+        self.assume_checked_ref().to_string().parse().expect("a valid address is valid in both")
+    }
+}
+
+impl ToBitcoin031 for bitcoin::Address {
+    type Target = bitcoin_0_31::Address;
+
+    fn to_bitcoin_0_31(&self) -> Self::Target {
+        self.as_unchecked().to_bitcoin_0_31().assume_checked()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bitcoin::{Amount, Network};
+
+    use super::*;
+
+    #[test]
+    fn genesis_block_round_trips() {
+        let block = bitcoin::constants::genesis_block(Network::Regtest);
+        let converted = block.to_bitcoin_0_31();
+        assert_eq!(converted.block_hash().to_string(), block.block_hash().to_string());
+        assert_eq!(converted.header.bits.to_consensus(), block.header.bits.to_consensus());
+
+        let tx = &block.txdata[0];
+        let converted_tx = tx.to_bitcoin_0_31();
+        assert_eq!(converted_tx.txid().to_string(), tx.compute_txid().to_string());
+        assert_eq!(tx.compute_txid().to_bitcoin_0_31(), converted_tx.txid());
+        assert_eq!(
+            tx.output[0].script_pubkey.to_bitcoin_0_31().as_bytes(),
+            tx.output[0].script_pubkey.as_bytes()
+        );
+        assert_eq!(tx.output[0].value.to_bitcoin_0_31().to_sat(), tx.output[0].value.to_sat());
+    }
+
+    #[test]
+    fn address_round_trips() {
+        let address = "bcrt1qw508d6qejxtdg4y5r3zarvary0c5xw7kygt080"
+            .parse::<bitcoin::Address<NetworkUnchecked>>()
+            .unwrap();
+        let converted = address.to_bitcoin_0_31();
+        assert_eq!(
+            converted.assume_checked_ref().to_string(),
+            address.assume_checked_ref().to_string()
+        );
+
+        let address = address.require_network(Network::Regtest).unwrap();
+        assert_eq!(address.to_bitcoin_0_31().to_string(), address.to_string());
+    }
+
+    #[test]
+    fn containers_convert_each_item() {
+        let amounts = vec![Amount::from_sat(1), Amount::from_sat(2)];
+        let converted = amounts.to_bitcoin_0_31();
+        assert_eq!(
+            converted,
+            vec![bitcoin_0_31::Amount::from_sat(1), bitcoin_0_31::Amount::from_sat(2)]
+        );
+        assert_eq!(None::<Amount>.to_bitcoin_0_31(), None);
+    }
+}
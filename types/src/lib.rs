@@ -5,6 +5,10 @@
 //! Each type has rustdocs copied from Core, bugs and all. Additional docs were only added if things
 //! really didn't make sense. Only required arguments are documented. To see what optional arguments
 //! are available run `bitcoin-cli help <method>` against the version of Core you are interested in.
+//!
+//! By default fields not in a type are ignored when deserializing (lenient mode) so the types keep
+//! working if a later release of Core adds a field. Enable the `serde-deny-unknown-fields` feature
+//! to error instead (strict mode), e.g. in CI to catch changes to Core.
 
 /// Re-export the `rust-bitcoin` crate.
 pub extern crate bitcoin;
@@ -35,6 +39,10 @@ pub mod v30;
 // JSON types that model _all_ `bitcoind` versions.
 pub mod model;
 
+// Conversions of the modelled types to the previous `rust-bitcoin` release.
+#[cfg(feature = "bitcoin-0-31")]
+pub mod compat;
+
 use core::fmt;
 
 use bitcoin::address::{self, Address, NetworkUnchecked};
@@ -300,4 +308,11 @@ mod tests {
         let got = btc_per_kb(f).unwrap();
         assert_eq!(got, Some(FeeRate::from_sat_per_kwu(25)))
     }
+
+    #[test]
+    fn unknown_fields() {
+        let json = r#"{"progress": 50.0, "current_height": 100, "added_in_later_core": true}"#;
+        let res = serde_json::from_str::<v25::ScanBlocksStatus>(json);
+        assert_eq!(res.is_err(), cfg!(feature = "serde-deny-unknown-fields"));
+    }
 }
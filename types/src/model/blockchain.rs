@@ -21,7 +21,7 @@ use super::{GetRawTransactionVerbose, ScriptPubkey};
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct DumpTxOutSet {
     /// The number of coins written in the snapshot.
-    pub coins_written: Amount,
+    pub coins_written: u64,
     /// The hash of the base of the snapshot.
     pub base_hash: BlockHash,
     /// The height of the base of the snapshot.
@@ -250,6 +250,26 @@ pub struct GetBlockchainInfo {
     pub warnings: Vec<String>,
 }
 
+impl GetBlockchainInfo {
+    /// Returns true if the node is in Initial Block Download (IBD) mode.
+    pub fn is_initial_block_download(&self) -> bool { self.initial_block_download }
+
+    /// Returns the estimated verification progress, between 0 and 1.
+    pub fn progress(&self) -> f64 { self.verification_progress.clamp(0.0, 1.0) }
+
+    /// Returns the network the node is running on.
+    pub fn chain_network(&self) -> Network { self.chain }
+
+    /// Returns the number of validated headers that have no block processed yet.
+    pub fn blocks_behind(&self) -> u32 { self.headers.saturating_sub(self.blocks) }
+
+    /// Returns true if the node is not in IBD and is at most `threshold` blocks behind the
+    /// validated headers.
+    pub fn is_synced(&self, threshold: u32) -> bool {
+        !self.initial_block_download && self.blocks_behind() <= threshold
+    }
+}
+
 /// Softfork status. Part of `getblockchaininfo`.
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct Softfork {
@@ -681,9 +701,14 @@ pub struct GetMempoolDescendantsVerbose(pub BTreeMap<Txid, MempoolEntry>);
 pub struct GetMempoolEntry(pub MempoolEntry);
 
 /// Mempool data. Part of `getmempoolentry`.
+///
+/// The virtual sizes are kept as integer vbytes, instead of `Weight`, because Core rounds the
+/// weight up to whole vbytes (and the ancestor and descendant sizes are sums of the rounded sizes),
+/// so a `Weight` made from them would not be the weight of the transactions. Use `weight` for the
+/// exact weight.
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct MempoolEntry {
-    /// Virtual transaction size as defined in BIP 141. v0.19 and later only.
+    /// Virtual transaction size as defined in BIP 141, in vbytes. v0.19 and later only.
     ///
     /// This is different from actual serialized size for witness transactions as witness data is discounted.
     pub vsize: Option<u32>,
@@ -694,7 +719,7 @@ pub struct MempoolEntry {
     ///
     /// This  was introduced with Bitcoin Core v0.19 and will hence be `None` for previous
     /// versions.
-    pub weight: Option<u32>,
+    pub weight: Option<Weight>,
     /// Local time transaction entered pool in seconds since 1 Jan 1970 GMT.
     pub time: u32,
     /// Block height when transaction entered pool.
@@ -748,9 +773,9 @@ pub struct GetMempoolInfo {
     pub bytes: u32,
     /// Total memory usage for the mempool.
     pub usage: u32,
-    /// Total fees for the mempool in BTC, ignoring modified fees through prioritisetransaction. v23
-    /// and later only.
-    pub total_fee: Option<f64>,
+    /// Total fees for the mempool, ignoring modified fees through prioritisetransaction. v22 and
+    /// later only.
+    pub total_fee: Option<Amount>,
     /// Maximum memory usage for the mempool.
     pub max_mempool: u32,
     /// Minimum fee rate in BTC/kB for a transaction to be accepted.
@@ -883,7 +908,7 @@ pub struct GetTxSpendingPrevoutItem {
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct LoadTxOutSet {
     /// The number of coins loaded from the snapshot.
-    pub coins_loaded: Amount,
+    pub coins_loaded: u64,
     /// The hash of the base of the snapshot.
     pub tip_hash: BlockHash,
     /// The height of the base of the snapshot.
@@ -975,3 +1000,8 @@ pub struct ScanTxOutSetUnspent {
     /// Number of confirmations of the unspent transaction output when the scan was done. For v28 onwards.
     pub confirmations: Option<u64>,
 }
+
+impl ScanTxOutSetUnspent {
+    /// Returns the outpoint of the unspent output.
+    pub fn outpoint(&self) -> OutPoint { OutPoint { txid: self.txid, vout: self.vout } }
+}
@@ -8,8 +8,8 @@
 use std::collections::BTreeMap;
 
 use bitcoin::{
-    block, Amount, BlockHash, CompactTarget, FeeRate, SignedAmount, Target, Transaction, Txid,
-    Weight, Wtxid,
+    block, Amount, BlockHash, CompactTarget, FeeRate, Network, SignedAmount, Target, Transaction,
+    Txid, Weight, Wtxid,
 };
 use serde::{Deserialize, Serialize};
 
@@ -119,7 +119,7 @@ pub struct GetMiningInfo {
     /// Minimum feerate of packages selected for block inclusion.
     pub block_min_tx_fee: Option<FeeRate>,
     /// Current network name as defined in BIP70 (main, test, regtest).
-    pub chain: String,
+    pub chain: Network,
     /// The block challenge (aka. block script).
     ///
     /// Only present if the current network is a signet (v29 onwards).
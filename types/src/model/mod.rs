@@ -61,7 +61,7 @@ pub use self::{
     },
     util::{
         CreateMultisig, DeriveAddresses, DeriveAddressesMultipath, EstimateSmartFee,
-        SignMessageWithPrivKey, ValidateAddress,
+        GetDescriptorInfo, SignMessageWithPrivKey, ValidateAddress,
     },
     wallet::{
         AddMultisigAddress, AddressInformation, AddressPurpose, Bip125Replaceable, BumpFee,
@@ -69,13 +69,15 @@ pub use self::{
         GetBalance, GetBalances, GetBalancesMine, GetBalancesWatchOnly, GetHdKeys, GetNewAddress,
         GetRawChangeAddress, GetReceivedByAddress, GetReceivedByLabel, GetTransaction,
         GetTransactionDetail, GetUnconfirmedBalance, GetWalletInfo, GetWalletInfoScanning, HdKey,
-        HdKeyDescriptor, LastProcessedBlock, ListAddressGroupings, ListAddressGroupingsItem,
-        ListLockUnspent, ListLockUnspentItem, ListReceivedByAddress, ListReceivedByAddressItem,
-        ListReceivedByLabel, ListReceivedByLabelItem, ListSinceBlock, ListTransactions,
-        ListUnspent, ListUnspentItem, ListWallets, LoadWallet, PsbtBumpFee, RescanBlockchain,
-        ScriptType, Send, SendAll, SendMany, SendManyVerbose, SendToAddress, SignMessage,
-        SignRawTransactionWithWallet, SimulateRawTransaction, TransactionCategory, TransactionItem,
-        UnloadWallet, WalletCreateFundedPsbt, WalletDisplayAddress, WalletProcessPsbt,
+        HdKeyDescriptor, ImportDescriptors, ImportDescriptorsFailure, ImportDescriptorsResult,
+        LastProcessedBlock, ListAddressGroupings, ListAddressGroupingsItem, ListDescriptors,
+        ListDescriptorsItem, ListLockUnspent, ListLockUnspentItem, ListReceivedByAddress,
+        ListReceivedByAddressItem, ListReceivedByLabel, ListReceivedByLabelItem, ListSinceBlock,
+        ListTransactions, ListUnspent, ListUnspentItem, ListWallets, LoadWallet, PsbtBumpFee,
+        RescanBlockchain, ScriptType, Send, SendAll, SendMany, SendManyVerbose, SendToAddress,
+        SignMessage, SignRawTransactionWithWallet, SimulateRawTransaction, TransactionCategory,
+        TransactionItem, UnloadWallet, WalletCreateFundedPsbt, WalletDisplayAddress,
+        WalletProcessPsbt,
     },
 };
 
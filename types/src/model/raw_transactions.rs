@@ -271,6 +271,8 @@ pub struct MempoolAcceptance {
     pub reject_reason: Option<String>,
     /// Rejection details (only present when 'allowed' is false and rejection details exist)
     pub reject_details: Option<String>,
+    /// Package validation error, if any (only possible if more than one transaction was tested).
+    pub package_error: Option<String>,
 }
 
 /// Models the fees field. Part of `testmempoolaccept`.
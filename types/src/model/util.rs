@@ -41,6 +41,25 @@ pub struct DeriveAddressesMultipath {
     pub addresses: Vec<DeriveAddresses>,
 }
 
+/// Models the result of JSON-RPC method `getdescriptorinfo`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct GetDescriptorInfo {
+    /// The descriptor in canonical form, without private keys. For a multipath descriptor, only the
+    /// first one.
+    pub descriptor: String,
+    /// The checksum for the input descriptor. v0.19 and later only.
+    pub checksum: Option<String>,
+    /// All descriptors produced by expanding multipath derivation elements. v29 and later only, if
+    /// the descriptor specifies multipath derivation elements.
+    pub multipath_expansion: Option<Vec<String>>,
+    /// Whether the descriptor is ranged.
+    pub is_range: bool,
+    /// Whether the descriptor is solvable.
+    pub is_solvable: bool,
+    /// Whether the input descriptor contained at least one private key.
+    pub has_private_keys: bool,
+}
+
 /// Models the result of JSON-RPC method `estimatesmartfee`.
 #[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct EstimateSmartFee {
@@ -416,6 +416,33 @@ pub struct GetTransactionDetail {
     pub parent_descriptors: Option<Vec<String>>,
 }
 
+/// Models the result of JSON-RPC method `importdescriptors`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ImportDescriptors(
+    /// The result of importing each descriptor, in the order of the request.
+    pub Vec<ImportDescriptorsResult>,
+);
+
+/// The result of importing a descriptor. Part of `importdescriptors`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ImportDescriptorsResult {
+    /// Whether the import was successful.
+    pub success: bool,
+    /// Warnings, empty if there are none.
+    pub warnings: Vec<String>,
+    /// Why the import failed, if it did.
+    pub error: Option<ImportDescriptorsFailure>,
+}
+
+/// Why the import of a descriptor failed. Part of `importdescriptors`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ImportDescriptorsFailure {
+    /// The RPC error code, e.g. -5 for an invalid descriptor.
+    pub code: i32,
+    /// The error message.
+    pub message: String,
+}
+
 /// Last processed block item. Part of of `gettransaction`.
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct LastProcessedBlock {
@@ -510,6 +537,32 @@ pub struct ListAddressGroupingsItem {
     pub label: Option<String>,
 }
 
+/// Models the result of JSON-RPC method `listdescriptors`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ListDescriptors {
+    /// The name of the wallet.
+    pub wallet_name: String,
+    /// The descriptors of the wallet.
+    pub descriptors: Vec<ListDescriptorsItem>,
+}
+
+/// A descriptor of the wallet. Part of `listdescriptors`.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ListDescriptorsItem {
+    /// The descriptor, including its checksum.
+    pub descriptor: String,
+    /// The creation time of the descriptor, in UNIX epoch time.
+    pub timestamp: u64,
+    /// Whether the descriptor is used to generate new addresses.
+    pub active: bool,
+    /// Whether the descriptor is used for change addresses, only for active descriptors.
+    pub internal: Option<bool>,
+    /// The range of indexes derived, only for ranged descriptors.
+    pub range: Option<[u64; 2]>,
+    /// The next index new addresses are derived from, only for ranged descriptors.
+    pub next_index: Option<u64>,
+}
+
 /// Models the result of JSON-RPC method `listlockunspent`.
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct ListLockUnspent(pub Vec<ListLockUnspentItem>);
@@ -406,6 +406,8 @@ pub enum GetMempoolInfoError {
     Numeric(NumericError),
     /// Conversion of a fee rate failed.
     FeeRate(ParseAmountError),
+    /// Conversion of the `total_fee` field failed.
+    TotalFee(ParseAmountError),
 }
 
 impl fmt::Display for GetMempoolInfoError {
@@ -413,6 +415,7 @@ impl fmt::Display for GetMempoolInfoError {
         match *self {
             Self::Numeric(ref e) => write_err!(f, "numeric"; e),
             Self::FeeRate(ref e) => write_err!(f, "fee rate"; e),
+            Self::TotalFee(ref e) => write_err!(f, "conversion of the `total_fee` field failed"; e),
         }
     }
 }
@@ -423,6 +426,7 @@ impl std::error::Error for GetMempoolInfoError {
         match *self {
             Self::Numeric(ref e) => Some(e),
             Self::FeeRate(ref e) => Some(e),
+            Self::TotalFee(ref e) => Some(e),
         }
     }
 }
@@ -5,7 +5,7 @@ use core::fmt;
 use bitcoin::amount::ParseAmountError;
 use bitcoin::error::UnprefixedHexError;
 use bitcoin::hex::HexToBytesError;
-use bitcoin::{consensus, hex};
+use bitcoin::{consensus, hex, network};
 
 use crate::error::write_err;
 use crate::NumericError;
@@ -99,3 +99,27 @@ impl std::error::Error for BlockTemplateTransactionError {
 impl From<NumericError> for BlockTemplateTransactionError {
     fn from(e: NumericError) -> Self { Self::Numeric(e) }
 }
+
+/// Error when converting a `GetMiningInfo` type into the model type.
+#[derive(Debug)]
+pub enum GetMiningInfoError {
+    /// Conversion of the `chain` field failed.
+    Chain(network::ParseNetworkError),
+}
+
+impl fmt::Display for GetMiningInfoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Self::Chain(ref e) => write_err!(f, "conversion of the `chain` field failed"; e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GetMiningInfoError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match *self {
+            Self::Chain(ref e) => Some(e),
+        }
+    }
+}
@@ -2,12 +2,13 @@
 
 use bitcoin::hex::FromHex as _;
 use bitcoin::{
-    block, consensus, BlockHash, CompactTarget, SignedAmount, Transaction, Txid, Weight, Wtxid,
+    block, consensus, BlockHash, CompactTarget, Network, SignedAmount, Transaction, Txid, Weight,
+    Wtxid,
 };
 
 use super::{
     BlockTemplateTransaction, BlockTemplateTransactionError, GetBlockTemplate,
-    GetBlockTemplateError, GetMiningInfo,
+    GetBlockTemplateError, GetMiningInfo, GetMiningInfoError,
 };
 use crate::model;
 
@@ -88,10 +89,13 @@ impl BlockTemplateTransaction {
 
 impl GetMiningInfo {
     /// Converts version specific type to a version nonspecific, more strongly typed type.
-    pub fn into_model(self) -> model::GetMiningInfo {
+    pub fn into_model(self) -> Result<model::GetMiningInfo, GetMiningInfoError> {
+        use GetMiningInfoError as E;
+
         let current_block_weight = self.current_block_weight.map(Weight::from_wu);
+        let chain = Network::from_core_arg(&self.chain).map_err(E::Chain)?;
 
-        model::GetMiningInfo {
+        Ok(model::GetMiningInfo {
             blocks: self.blocks,
             current_block_weight,
             current_block_tx: self.current_block_tx,
@@ -101,10 +105,10 @@ impl GetMiningInfo {
             network_hash_ps: self.network_hash_ps,
             pooled_tx: self.pooled_tx,
             block_min_tx_fee: None,
-            chain: self.chain,
+            chain,
             signet_challenge: None,
             next: None,
             warnings: vec![self.warnings],
-        }
+        })
     }
 }
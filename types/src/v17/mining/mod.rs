@@ -11,7 +11,7 @@ use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
 
-pub use self::error::{BlockTemplateTransactionError, GetBlockTemplateError};
+pub use self::error::{BlockTemplateTransactionError, GetBlockTemplateError, GetMiningInfoError};
 
 /// Result of the JSON-RPC method `getblocktemplate`.
 ///
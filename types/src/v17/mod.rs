@@ -257,7 +257,7 @@ pub use self::{
     },
     mining::{
         BlockTemplateTransaction, BlockTemplateTransactionError, GetBlockTemplate,
-        GetBlockTemplateError, GetMiningInfo,
+        GetBlockTemplateError, GetMiningInfo, GetMiningInfoError,
     },
     network::{
         AddedNode, AddedNodeAddress, Banned, GetAddedNodeInfo, GetConnectionCount, GetNetTotals,
@@ -468,6 +468,7 @@ impl MempoolAcceptance {
             fees: None,  // v21 and later only.
             reject_reason: self.reject_reason,
             reject_details: None, // v29 and later only.
+            package_error: None,  // v22 and later only.
         })
     }
 }
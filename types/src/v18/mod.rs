@@ -145,7 +145,7 @@
 //! | createmultisig                     | version + model |                                        |
 //! | deriveaddresses                    | version + model |                                        |
 //! | estimatesmartfee                   | version + model |                                        |
-//! | getdescriptorinfo                  | version         |                                        |
+//! | getdescriptorinfo                  | version + model |                                        |
 //! | signmessagewithprivkey             | version + model |                                        |
 //! | validateaddress                    | version + model |                                        |
 //! | verifymessage                      | version         |                                        |
@@ -270,18 +270,18 @@ pub use crate::v17::{
     GetBlockTemplate, GetBlockTemplateError, GetBlockVerboseOne, GetBlockVerboseOneError,
     GetBlockVerboseZero, GetBlockchainInfo, GetBlockchainInfoError, GetChainTips, GetChainTxStats,
     GetChainTxStatsError, GetConnectionCount, GetDifficulty, GetMemoryInfoStats, GetMempoolInfo,
-    GetMempoolInfoError, GetMiningInfo, GetNetTotals, GetNetworkInfo, GetNetworkInfoAddress,
-    GetNetworkInfoError, GetNetworkInfoNetwork, GetNewAddress, GetRawChangeAddress,
-    GetRawTransaction, GetRawTransactionVerbose, GetRawTransactionVerboseError,
-    GetReceivedByAddress, GetTransaction, GetTransactionDetail, GetTransactionDetailError,
-    GetTransactionError, GetTxOut, GetTxOutError, GetTxOutSetInfo, GetTxOutSetInfoError,
-    GetUnconfirmedBalance, GetWalletInfoError, ListAddressGroupings, ListAddressGroupingsError,
-    ListAddressGroupingsItem, ListBanned, ListLabels, ListLockUnspent, ListLockUnspentItem,
-    ListLockUnspentItemError, ListReceivedByAddressError, ListSinceBlock, ListSinceBlockError,
-    ListTransactions, ListUnspentItemError, ListWallets, LoadWallet, LockUnspent, Locked, Logging,
-    MapMempoolEntryError, MempoolAcceptance, MempoolEntryError, MempoolEntryFees,
-    MempoolEntryFeesError, NumericError, PartialSignatureError, PruneBlockchain, PsbtInput,
-    PsbtInputError, PsbtOutput, PsbtOutputError, PsbtScript, RawFeeDetail, RawFeeRange,
+    GetMempoolInfoError, GetMiningInfo, GetMiningInfoError, GetNetTotals, GetNetworkInfo,
+    GetNetworkInfoAddress, GetNetworkInfoError, GetNetworkInfoNetwork, GetNewAddress,
+    GetRawChangeAddress, GetRawTransaction, GetRawTransactionVerbose,
+    GetRawTransactionVerboseError, GetReceivedByAddress, GetTransaction, GetTransactionDetail,
+    GetTransactionDetailError, GetTransactionError, GetTxOut, GetTxOutError, GetTxOutSetInfo,
+    GetTxOutSetInfoError, GetUnconfirmedBalance, GetWalletInfoError, ListAddressGroupings,
+    ListAddressGroupingsError, ListAddressGroupingsItem, ListBanned, ListLabels, ListLockUnspent,
+    ListLockUnspentItem, ListLockUnspentItemError, ListReceivedByAddressError, ListSinceBlock,
+    ListSinceBlockError, ListTransactions, ListUnspentItemError, ListWallets, LoadWallet,
+    LockUnspent, Locked, Logging, MapMempoolEntryError, MempoolAcceptance, MempoolEntryError,
+    MempoolEntryFees, MempoolEntryFeesError, NumericError, PartialSignatureError, PruneBlockchain,
+    PsbtInput, PsbtInputError, PsbtOutput, PsbtOutputError, PsbtScript, RawFeeDetail, RawFeeRange,
     RawTransaction, RawTransactionError, RawTransactionInput, RawTransactionOutput,
     RescanBlockchain, ScanTxOutSetAbort, ScanTxOutSetError, ScanTxOutSetStatus, ScriptType,
     SendMany, SendRawTransaction, SendToAddress, SetNetworkActive, SetTxFee, SignFail,
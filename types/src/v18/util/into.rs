@@ -2,7 +2,7 @@
 
 use bitcoin::address;
 
-use super::DeriveAddresses;
+use super::{DeriveAddresses, GetDescriptorInfo};
 use crate::model;
 
 impl DeriveAddresses {
@@ -16,3 +16,17 @@ impl DeriveAddresses {
         Ok(model::DeriveAddresses { addresses })
     }
 }
+
+impl GetDescriptorInfo {
+    /// Converts version specific type to a version nonspecific, more strongly typed type.
+    pub fn into_model(self) -> model::GetDescriptorInfo {
+        model::GetDescriptorInfo {
+            descriptor: self.descriptor,
+            checksum: None,
+            multipath_expansion: None,
+            is_range: self.is_range,
+            is_solvable: self.is_solvable,
+            has_private_keys: self.has_private_keys,
+        }
+    }
+}
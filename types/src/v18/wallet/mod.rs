@@ -216,6 +216,7 @@ pub struct ImportMulti(pub Vec<ImportMultiEntry>);
 
 /// A single import multi entry. Part of `importmulti`.
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "serde-deny-unknown-fields", serde(deny_unknown_fields))]
 pub struct ImportMultiEntry {
     /// The success.
     pub success: bool,
@@ -227,6 +228,7 @@ pub struct ImportMultiEntry {
 
 /// A JSON-RPC error response. Part of `importmulti`.
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(feature = "serde-deny-unknown-fields", serde(deny_unknown_fields))]
 pub struct JsonRpcError {
     /// The error code.
     pub code: i32,
@@ -3,7 +3,7 @@
 use std::collections::BTreeMap;
 
 use bitcoin::hex::{self, FromHex as _};
-use bitcoin::{bip158, Amount, BlockHash, Network, Txid, Work, Wtxid};
+use bitcoin::{bip158, Amount, BlockHash, Network, Txid, Weight, Work, Wtxid};
 
 use super::error::{
     GetBlockFilterError, GetBlockchainInfoError, MapMempoolEntryError, MempoolEntryError,
@@ -155,7 +155,7 @@ impl MempoolEntry {
 
         let vsize = Some(crate::to_u32(self.vsize, "vsize")?);
         let size = None;
-        let weight = Some(crate::to_u32(self.weight, "weight")?);
+        let weight = Some(Weight::from_wu(crate::to_u32(self.weight, "weight")?.into()));
         let time = crate::to_u32(self.time, "time")?;
         let height = crate::to_u32(self.height, "height")?;
         let descendant_count = crate::to_u32(self.descendant_count, "descendant_count")?;
@@ -145,7 +145,7 @@
 //! | createmultisig                     | version + model |                                        |
 //! | deriveaddresses                    | version + model |                                        |
 //! | estimatesmartfee                   | version + model |                                        |
-//! | getdescriptorinfo                  | version         |                                        |
+//! | getdescriptorinfo                  | version + model |                                        |
 //! | signmessagewithprivkey             | version + model |                                        |
 //! | validateaddress                    | version + model |                                        |
 //! | verifymessage                      | version         |                                        |
@@ -265,26 +265,27 @@ pub use crate::v17::{
     GetBlockHeaderVerbose, GetBlockHeaderVerboseError, GetBlockStats, GetBlockStatsError,
     GetBlockTemplate, GetBlockTemplateError, GetBlockVerboseOne, GetBlockVerboseOneError,
     GetBlockVerboseZero, GetChainTips, GetChainTxStatsError, GetConnectionCount, GetDifficulty,
-    GetMemoryInfoStats, GetMempoolInfoError, GetMiningInfo, GetNetTotals, GetNetworkInfoAddress,
-    GetNetworkInfoError, GetNetworkInfoNetwork, GetNewAddress, GetRawChangeAddress,
-    GetRawTransaction, GetRawTransactionVerbose, GetRawTransactionVerboseError,
-    GetReceivedByAddress, GetTransactionDetail, GetTransactionDetailError, GetTransactionError,
-    GetTxOut, GetTxOutError, GetTxOutSetInfo, GetTxOutSetInfoError, GetUnconfirmedBalance,
-    GetWalletInfoError, ListAddressGroupings, ListAddressGroupingsError, ListAddressGroupingsItem,
-    ListBanned, ListLabels, ListLockUnspent, ListLockUnspentItem, ListLockUnspentItemError,
-    ListReceivedByAddressError, ListSinceBlock, ListSinceBlockError, ListTransactions,
-    ListUnspentItemError, ListWallets, LoadWallet, LockUnspent, Locked, Logging, MempoolAcceptance,
-    NumericError, PartialSignatureError, PruneBlockchain, PsbtInput, PsbtInputError, PsbtOutput,
-    PsbtOutputError, RawFeeDetail, RawFeeRange, RawTransactionError, RawTransactionInput,
-    RawTransactionOutput, RescanBlockchain, ScanTxOutSetAbort, ScanTxOutSetError,
-    ScanTxOutSetStatus, ScriptType, SendMany, SendRawTransaction, SendToAddress, SetNetworkActive,
-    SetTxFee, SignFail, SignFailError, SignMessage, SignMessageWithPrivKey, SignRawTransaction,
-    SignRawTransactionError, SignRawTransactionWithKey, SignRawTransactionWithWallet,
-    SoftforkReject, TestMempoolAccept, TransactionCategory, TransactionItem, TransactionItemError,
-    UploadTarget, ValidateAddress, ValidateAddressError, VerifyChain, VerifyMessage,
-    VerifyTxOutProof, WaitForBlock, WaitForBlockError, WaitForBlockHeight, WaitForBlockHeightError,
-    WaitForNewBlock, WaitForNewBlockError, WalletCreateFundedPsbt, WalletCreateFundedPsbtError,
-    WalletProcessPsbt, WitnessUtxo, WitnessUtxoError,
+    GetMemoryInfoStats, GetMempoolInfoError, GetMiningInfo, GetMiningInfoError, GetNetTotals,
+    GetNetworkInfoAddress, GetNetworkInfoError, GetNetworkInfoNetwork, GetNewAddress,
+    GetRawChangeAddress, GetRawTransaction, GetRawTransactionVerbose,
+    GetRawTransactionVerboseError, GetReceivedByAddress, GetTransactionDetail,
+    GetTransactionDetailError, GetTransactionError, GetTxOut, GetTxOutError, GetTxOutSetInfo,
+    GetTxOutSetInfoError, GetUnconfirmedBalance, GetWalletInfoError, ListAddressGroupings,
+    ListAddressGroupingsError, ListAddressGroupingsItem, ListBanned, ListLabels, ListLockUnspent,
+    ListLockUnspentItem, ListLockUnspentItemError, ListReceivedByAddressError, ListSinceBlock,
+    ListSinceBlockError, ListTransactions, ListUnspentItemError, ListWallets, LoadWallet,
+    LockUnspent, Locked, Logging, MempoolAcceptance, NumericError, PartialSignatureError,
+    PruneBlockchain, PsbtInput, PsbtInputError, PsbtOutput, PsbtOutputError, RawFeeDetail,
+    RawFeeRange, RawTransactionError, RawTransactionInput, RawTransactionOutput, RescanBlockchain,
+    ScanTxOutSetAbort, ScanTxOutSetError, ScanTxOutSetStatus, ScriptType, SendMany,
+    SendRawTransaction, SendToAddress, SetNetworkActive, SetTxFee, SignFail, SignFailError,
+    SignMessage, SignMessageWithPrivKey, SignRawTransaction, SignRawTransactionError,
+    SignRawTransactionWithKey, SignRawTransactionWithWallet, SoftforkReject, TestMempoolAccept,
+    TransactionCategory, TransactionItem, TransactionItemError, UploadTarget, ValidateAddress,
+    ValidateAddressError, VerifyChain, VerifyMessage, VerifyTxOutProof, WaitForBlock,
+    WaitForBlockError, WaitForBlockHeight, WaitForBlockHeightError, WaitForNewBlock,
+    WaitForNewBlockError, WalletCreateFundedPsbt, WalletCreateFundedPsbtError, WalletProcessPsbt,
+    WitnessUtxo, WitnessUtxoError,
 };
 #[doc(inline)]
 pub use crate::v18::{
@@ -6,6 +6,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::model;
+
 /// Result of JSON-RPC method `getdescriptorinfo`.
 ///
 /// > getdescriptorinfo "descriptor"
@@ -29,3 +31,17 @@ pub struct GetDescriptorInfo {
     #[serde(rename = "hasprivatekeys")]
     pub has_private_keys: bool,
 }
+
+impl GetDescriptorInfo {
+    /// Converts version specific type to a version nonspecific, more strongly typed type.
+    pub fn into_model(self) -> model::GetDescriptorInfo {
+        model::GetDescriptorInfo {
+            descriptor: self.descriptor,
+            checksum: Some(self.checksum),
+            multipath_expansion: None,
+            is_range: self.is_range,
+            is_solvable: self.is_solvable,
+            has_private_keys: self.has_private_keys,
+        }
+    }
+}
@@ -146,7 +146,7 @@
 //! | createmultisig                     | version + model |                                        |
 //! | deriveaddresses                    | version + model |                                        |
 //! | estimatesmartfee                   | version + model |                                        |
-//! | getdescriptorinfo                  | version         |                                        |
+//! | getdescriptorinfo                  | version + model |                                        |
 //! | signmessagewithprivkey             | version + model |                                        |
 //! | validateaddress                    | version + model |                                        |
 //! | verifymessage                      | version         |                                        |
@@ -261,25 +261,25 @@ pub use crate::{
         GetBlockStatsError, GetBlockTemplate, GetBlockTemplateError, GetBlockVerboseOne,
         GetBlockVerboseOneError, GetBlockVerboseZero, GetChainTips, GetChainTxStatsError,
         GetConnectionCount, GetDifficulty, GetMemoryInfoStats, GetMempoolInfoError, GetMiningInfo,
-        GetNetTotals, GetNetworkInfoAddress, GetNetworkInfoError, GetNetworkInfoNetwork,
-        GetNewAddress, GetRawChangeAddress, GetRawTransaction, GetRawTransactionVerbose,
-        GetRawTransactionVerboseError, GetReceivedByAddress, GetTransactionDetailError,
-        GetTransactionError, GetTxOut, GetTxOutError, GetTxOutSetInfo, GetTxOutSetInfoError,
-        GetUnconfirmedBalance, GetWalletInfoError, ListAddressGroupings, ListAddressGroupingsError,
-        ListAddressGroupingsItem, ListLabels, ListLockUnspent, ListLockUnspentItem,
-        ListLockUnspentItemError, ListReceivedByAddressError, ListUnspentItemError, ListWallets,
-        LoadWallet, LockUnspent, Locked, MempoolAcceptance, NumericError, PartialSignatureError,
-        PruneBlockchain, PsbtInput, PsbtInputError, PsbtOutput, PsbtOutputError, RawFeeDetail,
-        RawFeeRange, RawTransactionError, RawTransactionInput, RawTransactionOutput,
-        RescanBlockchain, ScanTxOutSetAbort, ScanTxOutSetError, ScanTxOutSetStatus, ScriptType,
-        SendMany, SendRawTransaction, SendToAddress, SetNetworkActive, SetTxFee, SignFail,
-        SignFailError, SignMessage, SignMessageWithPrivKey, SignRawTransaction,
-        SignRawTransactionError, SignRawTransactionWithKey, SignRawTransactionWithWallet,
-        SoftforkReject, TestMempoolAccept, TransactionCategory, UploadTarget, ValidateAddress,
-        ValidateAddressError, VerifyChain, VerifyMessage, VerifyTxOutProof, WaitForBlock,
-        WaitForBlockError, WaitForBlockHeight, WaitForBlockHeightError, WaitForNewBlock,
-        WaitForNewBlockError, WalletCreateFundedPsbt, WalletCreateFundedPsbtError,
-        WalletProcessPsbt, WitnessUtxo, WitnessUtxoError,
+        GetMiningInfoError, GetNetTotals, GetNetworkInfoAddress, GetNetworkInfoError,
+        GetNetworkInfoNetwork, GetNewAddress, GetRawChangeAddress, GetRawTransaction,
+        GetRawTransactionVerbose, GetRawTransactionVerboseError, GetReceivedByAddress,
+        GetTransactionDetailError, GetTransactionError, GetTxOut, GetTxOutError, GetTxOutSetInfo,
+        GetTxOutSetInfoError, GetUnconfirmedBalance, GetWalletInfoError, ListAddressGroupings,
+        ListAddressGroupingsError, ListAddressGroupingsItem, ListLabels, ListLockUnspent,
+        ListLockUnspentItem, ListLockUnspentItemError, ListReceivedByAddressError,
+        ListUnspentItemError, ListWallets, LoadWallet, LockUnspent, Locked, MempoolAcceptance,
+        NumericError, PartialSignatureError, PruneBlockchain, PsbtInput, PsbtInputError,
+        PsbtOutput, PsbtOutputError, RawFeeDetail, RawFeeRange, RawTransactionError,
+        RawTransactionInput, RawTransactionOutput, RescanBlockchain, ScanTxOutSetAbort,
+        ScanTxOutSetError, ScanTxOutSetStatus, ScriptType, SendMany, SendRawTransaction,
+        SendToAddress, SetNetworkActive, SetTxFee, SignFail, SignFailError, SignMessage,
+        SignMessageWithPrivKey, SignRawTransaction, SignRawTransactionError,
+        SignRawTransactionWithKey, SignRawTransactionWithWallet, SoftforkReject, TestMempoolAccept,
+        TransactionCategory, UploadTarget, ValidateAddress, ValidateAddressError, VerifyChain,
+        VerifyMessage, VerifyTxOutProof, WaitForBlock, WaitForBlockError, WaitForBlockHeight,
+        WaitForBlockHeightError, WaitForNewBlock, WaitForNewBlockError, WalletCreateFundedPsbt,
+        WalletCreateFundedPsbtError, WalletProcessPsbt, WitnessUtxo, WitnessUtxoError,
     },
     v18::{
         ActiveCommand, AnalyzePsbt, AnalyzePsbtError, AnalyzePsbtInput, AnalyzePsbtInputMissing,
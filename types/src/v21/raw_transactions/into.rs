@@ -41,6 +41,7 @@ impl MempoolAcceptance {
             fees,
             reject_reason: self.reject_reason,
             reject_details: None, // v29 and later only.
+            package_error: None,  // v22 and later only.
         })
     }
 }
@@ -43,6 +43,30 @@ impl std::error::Error for PsbtBumpFeeError {
     }
 }
 
+/// Error when converting a `ImportDescriptors` type into the model type.
+#[derive(Debug)]
+pub enum ImportDescriptorsError {
+    /// Conversion of the `error` field failed.
+    Error(serde_json::Error),
+}
+
+impl fmt::Display for ImportDescriptorsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Self::Error(ref e) => write_err!(f, "conversion of the `error` field failed"; e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ImportDescriptorsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match *self {
+            Self::Error(ref e) => Some(e),
+        }
+    }
+}
+
 /// Error when converting a `Send` type into the model type.
 #[derive(Debug)]
 pub enum SendError {
@@ -3,7 +3,8 @@
 use bitcoin::{hex, Txid};
 
 use super::{
-    GetWalletInfo, GetWalletInfoError, GetWalletInfoScanning, PsbtBumpFee, PsbtBumpFeeError, Send,
+    GetWalletInfo, GetWalletInfoError, GetWalletInfoScanning, ImportDescriptors,
+    ImportDescriptorsError, ImportDescriptorsResult, PsbtBumpFee, PsbtBumpFeeError, Send,
     SendError, SendMany, SendManyVerbose, UnloadWallet,
 };
 use crate::model;
@@ -15,6 +16,31 @@ impl UnloadWallet {
     }
 }
 
+impl ImportDescriptors {
+    /// Converts version specific type to a version nonspecific, more strongly typed type.
+    pub fn into_model(self) -> Result<model::ImportDescriptors, ImportDescriptorsError> {
+        let results =
+            self.0.into_iter().map(|result| result.into_model()).collect::<Result<_, _>>()?;
+        Ok(model::ImportDescriptors(results))
+    }
+}
+
+impl ImportDescriptorsResult {
+    /// Converts version specific type to a version nonspecific, more strongly typed type.
+    pub fn into_model(self) -> Result<model::ImportDescriptorsResult, ImportDescriptorsError> {
+        let error = self
+            .error
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(ImportDescriptorsError::Error)?;
+        Ok(model::ImportDescriptorsResult {
+            success: self.success,
+            warnings: self.warnings.unwrap_or_default(),
+            error,
+        })
+    }
+}
+
 impl PsbtBumpFee {
     /// Converts version specific type to a version nonspecific, more strongly typed type.
     pub fn into_model(self) -> Result<model::PsbtBumpFee, PsbtBumpFeeError> {
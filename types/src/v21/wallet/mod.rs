@@ -9,7 +9,7 @@ mod into;
 
 use serde::{Deserialize, Serialize};
 
-pub use self::error::{PsbtBumpFeeError, SendError};
+pub use self::error::{ImportDescriptorsError, PsbtBumpFeeError, SendError};
 pub use super::GetWalletInfoError;
 
 /// Result of the JSON-RPC method `getwalletinfo`.
@@ -1,5 +1,7 @@
 // SPDX-License-Identifier: CC0-1.0
 
+use bitcoin::Amount;
+
 use super::{GetMempoolInfo, GetMempoolInfoError};
 use crate::model;
 
@@ -12,6 +14,7 @@ impl GetMempoolInfo {
         let max_mempool = crate::to_u32(self.max_mempool, "max_mempool")?;
         let mempool_min_fee = crate::btc_per_kb(self.mempool_min_fee)?;
         let min_relay_tx_fee = crate::btc_per_kb(self.min_relay_tx_fee)?;
+        let total_fee = Amount::from_btc(self.total_fee).map_err(GetMempoolInfoError::TotalFee)?;
         let unbroadcast_count = Some(crate::to_u32(self.unbroadcast_count, "unbroadcast_count")?);
 
         Ok(model::GetMempoolInfo {
@@ -19,7 +22,7 @@ impl GetMempoolInfo {
             size,
             bytes,
             usage,
-            total_fee: Some(self.total_fee),
+            total_fee: Some(total_fee),
             max_mempool,
             mempool_min_fee,
             min_relay_tx_fee,
@@ -6,8 +6,8 @@ use bitcoin::key::PublicKey;
 use bitcoin::{address, bip32, Address, ScriptBuf, WitnessProgram, WitnessVersion};
 
 use super::{
-    GetAddressInfo, GetAddressInfoEmbedded, GetAddressInfoEmbeddedError, GetAddressInfoError,
-    WalletDisplayAddress,
+    DescriptorInfo, GetAddressInfo, GetAddressInfoEmbedded, GetAddressInfoEmbeddedError,
+    GetAddressInfoError, ListDescriptors, WalletDisplayAddress,
 };
 use crate::model;
 
@@ -157,3 +157,25 @@ impl WalletDisplayAddress {
         Ok(model::WalletDisplayAddress { address })
     }
 }
+
+impl ListDescriptors {
+    /// Converts version specific type to a version nonspecific, more strongly typed type.
+    pub fn into_model(self) -> model::ListDescriptors {
+        let descriptors = self.descriptors.into_iter().map(|d| d.into_model()).collect();
+        model::ListDescriptors { wallet_name: self.wallet_name, descriptors }
+    }
+}
+
+impl DescriptorInfo {
+    /// Converts version specific type to a version nonspecific, more strongly typed type.
+    pub fn into_model(self) -> model::ListDescriptorsItem {
+        model::ListDescriptorsItem {
+            descriptor: self.descriptor,
+            timestamp: self.timestamp,
+            active: self.active,
+            internal: self.internal,
+            range: self.range,
+            next_index: self.next,
+        }
+    }
+}
@@ -2,7 +2,7 @@
 
 use alloc::collections::BTreeMap;
 
-use bitcoin::{hex, BlockHash, Network, Txid, Work, Wtxid};
+use bitcoin::{hex, BlockHash, Network, Txid, Weight, Work, Wtxid};
 
 use super::{
     Bip9Info, Bip9Statistics, DeploymentInfo, GetBlockchainInfo, GetBlockchainInfoError,
@@ -170,7 +170,7 @@ impl MempoolEntry {
 
         let vsize = Some(crate::to_u32(self.vsize, "vsize")?);
         let size = None;
-        let weight = Some(crate::to_u32(self.weight, "weight")?);
+        let weight = Some(Weight::from_wu(crate::to_u32(self.weight, "weight")?.into()));
         let time = crate::to_u32(self.time, "time")?;
         let height = crate::to_u32(self.height, "height")?;
         let descendant_count = crate::to_u32(self.descendant_count, "descendant_count")?;
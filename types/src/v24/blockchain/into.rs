@@ -2,7 +2,7 @@
 
 use alloc::collections::BTreeMap;
 
-use bitcoin::{hex, OutPoint, Txid, Wtxid};
+use bitcoin::{hex, Amount, OutPoint, Txid, Weight, Wtxid};
 
 use super::{
     GetMempoolAncestors, GetMempoolAncestorsVerbose, GetMempoolDescendants,
@@ -72,7 +72,7 @@ impl MempoolEntry {
 
         let vsize = Some(crate::to_u32(self.vsize, "vsize")?);
         let size = None;
-        let weight = Some(crate::to_u32(self.weight, "weight")?);
+        let weight = Some(Weight::from_wu(crate::to_u32(self.weight, "weight")?.into()));
         let time = crate::to_u32(self.time, "time")?;
         let height = crate::to_u32(self.height, "height")?;
         let descendant_count = crate::to_u32(self.descendant_count, "descendant_count")?;
@@ -138,6 +138,7 @@ impl GetMempoolInfo {
         let max_mempool = crate::to_u32(self.max_mempool, "max_mempool")?;
         let mempool_min_fee = crate::btc_per_kb(self.mempool_min_fee)?;
         let min_relay_tx_fee = crate::btc_per_kb(self.min_relay_tx_fee)?;
+        let total_fee = Amount::from_btc(self.total_fee).map_err(GetMempoolInfoError::TotalFee)?;
         let incremental_relay_fee = crate::btc_per_kb(self.incremental_relay_fee)?;
         let unbroadcast_count = Some(crate::to_u32(self.unbroadcast_count, "unbroadcast_count")?);
 
@@ -146,7 +147,7 @@ impl GetMempoolInfo {
             size,
             bytes,
             usage,
-            total_fee: Some(self.total_fee),
+            total_fee: Some(total_fee),
             max_mempool,
             mempool_min_fee,
             min_relay_tx_fee,
@@ -131,6 +131,7 @@ pub struct ScanBlocksAbort(pub bool);
 /// > Arguments:
 /// > 1. scanobjects                            (json array, required) Array of scan objects
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "serde-deny-unknown-fields", serde(deny_unknown_fields))]
 pub struct ScanBlocksStart {
     /// The height we started the scan from
     pub from_height: i64,
@@ -146,6 +147,7 @@ pub struct ScanBlocksStart {
 /// >
 /// > Returns progress report (in %) of the current scan.
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "serde-deny-unknown-fields", serde(deny_unknown_fields))]
 pub struct ScanBlocksStatus {
     /// Approximate percent complete
     pub progress: f64,
@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: CC0-1.0
 
-use super::{CreateWallet, LoadWallet, UnloadWallet};
+use super::{CreateWallet, DescriptorInfo, ListDescriptors, LoadWallet, UnloadWallet};
 use crate::model;
 
 impl CreateWallet {
@@ -33,3 +33,25 @@ impl UnloadWallet {
         model::UnloadWallet { warnings: self.warnings.unwrap_or_default() }
     }
 }
+
+impl ListDescriptors {
+    /// Converts version specific type to a version nonspecific, more strongly typed type.
+    pub fn into_model(self) -> model::ListDescriptors {
+        let descriptors = self.descriptors.into_iter().map(|d| d.into_model()).collect();
+        model::ListDescriptors { wallet_name: self.wallet_name, descriptors }
+    }
+}
+
+impl DescriptorInfo {
+    /// Converts version specific type to a version nonspecific, more strongly typed type.
+    pub fn into_model(self) -> model::ListDescriptorsItem {
+        model::ListDescriptorsItem {
+            descriptor: self.descriptor,
+            timestamp: self.timestamp,
+            active: self.active,
+            internal: self.internal,
+            range: self.range,
+            next_index: self.next_index.or(self.next),
+        }
+    }
+}
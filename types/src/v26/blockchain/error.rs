@@ -48,8 +48,6 @@ impl From<NumericError> for GetChainStatesError {
 /// Error when converting a `DumpTxOutSet` type into the model type.
 #[derive(Debug)]
 pub enum DumpTxOutSetError {
-    /// Conversion of the `coins_written` field to Amount failed.
-    CoinsWritten(amount::ParseAmountError),
     /// Conversion of the `base_hash` field failed.
     BaseHash(hex::HexToArrayError),
     /// Conversion of the `txoutset_hash` field failed.
@@ -61,8 +59,6 @@ pub enum DumpTxOutSetError {
 impl fmt::Display for DumpTxOutSetError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            Self::CoinsWritten(ref e) =>
-                write_err!(f, "conversion of the `coins_written` field failed"; e),
             Self::BaseHash(ref e) => write_err!(f, "conversion of the `base_hash` field failed"; e),
             Self::TxOutSetHash(ref e) =>
                 write_err!(f, "conversion of the `txoutset_hash` field failed"; e),
@@ -75,7 +71,6 @@ impl fmt::Display for DumpTxOutSetError {
 impl std::error::Error for DumpTxOutSetError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match *self {
-            Self::CoinsWritten(ref e) => Some(e),
             Self::BaseHash(ref e) => Some(e),
             Self::TxOutSetHash(ref e) => Some(e),
             Self::Numeric(ref e) => Some(e),
@@ -172,8 +167,6 @@ impl From<NumericError> for GetTxOutSetInfoError {
 /// Error when converting a `LoadTxOutSet` type into the model type.
 #[derive(Debug)]
 pub enum LoadTxOutSetError {
-    /// Conversion of the `coins_loaded` field to Amount failed.
-    CoinsLoaded(amount::ParseAmountError),
     /// Conversion of the `tip_hash` field failed.
     TipHash(hex::HexToArrayError),
     /// Conversion of numeric type to expected type failed.
@@ -183,8 +176,6 @@ pub enum LoadTxOutSetError {
 impl fmt::Display for LoadTxOutSetError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
-            Self::CoinsLoaded(ref e) =>
-                write_err!(f, "conversion of the `coins_loaded` field failed"; e),
             Self::TipHash(ref e) => write_err!(f, "conversion of the `tip_hash` field failed"; e),
             Self::Numeric(ref e) => write_err!(f, "numeric"; e),
         }
@@ -195,7 +186,6 @@ impl fmt::Display for LoadTxOutSetError {
 impl std::error::Error for LoadTxOutSetError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match *self {
-            Self::CoinsLoaded(ref e) => Some(e),
             Self::TipHash(ref e) => Some(e),
             Self::Numeric(ref e) => Some(e),
         }
@@ -47,7 +47,6 @@ impl DumpTxOutSet {
     pub fn into_model(self) -> Result<model::DumpTxOutSet, DumpTxOutSetError> {
         use DumpTxOutSetError as E;
 
-        let coins_written = Amount::from_btc(self.coins_written).map_err(E::CoinsWritten)?;
         let base_hash = self.base_hash.parse::<BlockHash>().map_err(E::BaseHash)?;
         let base_height = crate::to_u32(self.base_height, "base_height")?;
         let tx_out_set_hash =
@@ -55,7 +54,7 @@ impl DumpTxOutSet {
         let n_chain_tx = crate::to_u32(self.n_chain_tx, "n_chain_tx")?;
 
         Ok(model::DumpTxOutSet {
-            coins_written,
+            coins_written: self.coins_written,
             base_hash,
             base_height,
             path: self.path,
@@ -134,9 +133,13 @@ impl LoadTxOutSet {
 
         let tip_hash = self.tip_hash.parse::<BlockHash>().map_err(E::TipHash)?;
         let base_height = crate::to_u32(self.base_height, "base_height")?;
-        let coins_loaded = Amount::from_btc(self.coins_loaded).map_err(E::CoinsLoaded)?;
 
-        Ok(model::LoadTxOutSet { coins_loaded, tip_hash, base_height, path: self.path })
+        Ok(model::LoadTxOutSet {
+            coins_loaded: self.coins_loaded,
+            tip_hash,
+            base_height,
+            path: self.path,
+        })
     }
 }
 
@@ -25,7 +25,7 @@ pub use self::error::{
 #[cfg_attr(feature = "serde-deny-unknown-fields", serde(deny_unknown_fields))]
 pub struct DumpTxOutSet {
     /// The number of coins written in the snapshot.
-    pub coins_written: f64,
+    pub coins_written: u64,
     /// The hash of the base of the snapshot.
     pub base_hash: String,
     /// The height of the base of the snapshot.
@@ -166,7 +166,7 @@ pub struct GetTxOutSetInfoUnspendables {
 #[cfg_attr(feature = "serde-deny-unknown-fields", serde(deny_unknown_fields))]
 pub struct LoadTxOutSet {
     /// The number of coins loaded from the snapshot.
-    pub coins_loaded: f64,
+    pub coins_loaded: u64,
     /// The hash of the base of the snapshot.
     pub tip_hash: String,
     /// The height of the base of the snapshot.
@@ -4,9 +4,10 @@
 //!
 //! Types for methods found under the `== Mining ==` section of the API docs.
 
-use bitcoin::Weight;
+use bitcoin::{Network, Weight};
 use serde::{Deserialize, Serialize};
 
+use super::GetMiningInfoError;
 use crate::model;
 
 /// Result of the JSON-RPC method `getmininginfo`.
@@ -41,10 +42,13 @@ pub struct GetMiningInfo {
 
 impl GetMiningInfo {
     /// Converts version specific type to a version nonspecific, more strongly typed type.
-    pub fn into_model(self) -> model::GetMiningInfo {
+    pub fn into_model(self) -> Result<model::GetMiningInfo, GetMiningInfoError> {
+        use GetMiningInfoError as E;
+
         let current_block_weight = self.current_block_weight.map(Weight::from_wu);
+        let chain = Network::from_core_arg(&self.chain).map_err(E::Chain)?;
 
-        model::GetMiningInfo {
+        Ok(model::GetMiningInfo {
             blocks: self.blocks,
             current_block_weight,
             current_block_tx: self.current_block_tx,
@@ -54,10 +58,10 @@ impl GetMiningInfo {
             network_hash_ps: self.network_hash_ps,
             pooled_tx: self.pooled_tx,
             block_min_tx_fee: None,
-            chain: self.chain,
+            chain,
             signet_challenge: None,
             next: None,
             warnings: self.warnings,
-        }
+        })
     }
 }
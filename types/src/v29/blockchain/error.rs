@@ -135,6 +135,40 @@ impl From<NumericError> for GetBlockVerboseTwoError {
     fn from(e: NumericError) -> Self { Self::Numeric(e) }
 }
 
+/// Error when decoding a transaction of a `GetBlockVerboseTwoLazy` type into the model type.
+#[derive(Debug)]
+pub enum GetBlockVerboseTwoTransactionError {
+    /// Deserialization of the raw transaction entry failed.
+    Json(serde_json::Error),
+    /// Conversion of the transaction entry failed.
+    Transaction(GetRawTransactionVerboseError),
+    /// Conversion of the transaction `fee` field failed.
+    Fee(amount::ParseAmountError),
+}
+
+impl fmt::Display for GetBlockVerboseTwoTransactionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Self::Json(ref e) =>
+                write_err!(f, "deserialization of the transaction entry failed"; e),
+            Self::Transaction(ref e) =>
+                write_err!(f, "conversion of the transaction entry failed"; e),
+            Self::Fee(ref e) => write_err!(f, "conversion of the `fee` field failed"; e),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GetBlockVerboseTwoTransactionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match *self {
+            Self::Json(ref e) => Some(e),
+            Self::Transaction(ref e) => Some(e),
+            Self::Fee(ref e) => Some(e),
+        }
+    }
+}
+
 /// Error when converting a `GetBlockVerboseThree` type into the model type.
 #[derive(Debug)]
 pub enum GetBlockVerboseThreeError {
@@ -125,6 +125,36 @@ impl GetBlockVerboseTwo {
     }
 }
 
+impl GetBlockVerboseTwoLazy {
+    /// Returns an iterator that decodes the transactions into model types, one at a time.
+    pub fn iter_transactions(
+        &self,
+    ) -> impl Iterator<
+        Item = Result<model::GetBlockVerboseTwoTransaction, GetBlockVerboseTwoTransactionError>,
+    > + '_ {
+        self.tx.iter().map(RawBlockTransaction::to_model)
+    }
+}
+
+impl RawBlockTransaction {
+    /// Deserializes the transaction entry.
+    pub fn decode(&self) -> Result<GetBlockVerboseTwoTransaction, serde_json::Error> {
+        serde_json::from_str(self.0.get())
+    }
+
+    /// Deserializes the transaction entry and converts it to a model type.
+    pub fn to_model(
+        &self,
+    ) -> Result<model::GetBlockVerboseTwoTransaction, GetBlockVerboseTwoTransactionError> {
+        use GetBlockVerboseTwoTransactionError as E;
+
+        let entry = self.decode().map_err(E::Json)?;
+        let transaction = entry.transaction.into_model().map_err(E::Transaction)?;
+        let fee = entry.fee.map(Amount::from_btc).transpose().map_err(E::Fee)?;
+        Ok(model::GetBlockVerboseTwoTransaction { transaction, fee })
+    }
+}
+
 impl GetRawTransactionVerboseWithPrevout {
     fn into_model_with_prevouts(
         self,
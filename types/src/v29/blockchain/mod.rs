@@ -4,14 +4,15 @@
 //!
 //! Types for methods found under the `== Blockchain ==` section of the API docs.
 use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
 
 mod error;
 mod into;
 
 pub use self::error::{
     GetBlockHeaderError, GetBlockHeaderVerboseError, GetBlockVerboseOneError,
-    GetBlockVerboseThreeError, GetBlockVerboseTwoError, GetBlockchainInfoError,
-    GetChainStatesError, GetDescriptorActivityError,
+    GetBlockVerboseThreeError, GetBlockVerboseTwoError, GetBlockVerboseTwoTransactionError,
+    GetBlockchainInfoError, GetChainStatesError, GetDescriptorActivityError,
 };
 use crate::psbt::{RawTransactionInput, RawTransactionOutput};
 use crate::v17::GetRawTransactionVerbose;
@@ -136,6 +137,72 @@ pub struct GetBlockVerboseTwoTransaction {
     pub fee: Option<f64>,
 }
 
+/// Result of JSON-RPC method `getblock` with verbosity set to 2, with lazily decoded transactions.
+///
+/// The transactions are kept as raw JSON and only deserialized when iterated, for consumers that
+/// only need a few transactions (or only the header fields) of a block.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+#[cfg_attr(feature = "serde-deny-unknown-fields", serde(deny_unknown_fields))]
+pub struct GetBlockVerboseTwoLazy {
+    /// The block hash (same as provided).
+    pub hash: String,
+    /// The number of confirmations, or -1 if the block is not on the main chain.
+    pub confirmations: i64,
+    /// The block size.
+    pub size: i64,
+    /// The block size excluding witness data.
+    #[serde(rename = "strippedsize")]
+    pub stripped_size: Option<i64>,
+    /// The block weight as defined in BIP 141.
+    pub weight: u64,
+    /// The block height or index.
+    pub height: i64,
+    /// The block version.
+    pub version: i32,
+    /// The block version formatted in hexadecimal.
+    #[serde(rename = "versionHex")]
+    pub version_hex: String,
+    /// The merkle root.
+    #[serde(rename = "merkleroot")]
+    pub merkle_root: String,
+    /// The transactions, not yet deserialized.
+    pub tx: Vec<RawBlockTransaction>,
+    /// The block time expressed in UNIX epoch time.
+    pub time: i64,
+    /// The median block time expressed in UNIX epoch time.
+    #[serde(rename = "mediantime")]
+    pub median_time: Option<i64>,
+    /// The nonce.
+    pub nonce: i64,
+    /// nBits: compact representation of the block difficulty target.
+    pub bits: String,
+    /// The difficulty target.
+    pub target: String,
+    /// The difficulty.
+    pub difficulty: f64,
+    /// Expected number of hashes required to produce the chain up to this block (in hex).
+    #[serde(rename = "chainwork")]
+    pub chain_work: String,
+    /// The number of transactions in the block.
+    #[serde(rename = "nTx")]
+    pub n_tx: i64,
+    /// The hash of the previous block (if available).
+    #[serde(rename = "previousblockhash")]
+    pub previous_block_hash: Option<String>,
+    /// The hash of the next block (if available).
+    #[serde(rename = "nextblockhash")]
+    pub next_block_hash: Option<String>,
+}
+
+/// A transaction entry for `getblock` verbosity 2 that has not been deserialized yet.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct RawBlockTransaction(pub Box<RawValue>);
+
+impl PartialEq for RawBlockTransaction {
+    fn eq(&self, other: &Self) -> bool { self.0.get() == other.0.get() }
+}
+
 /// Result of JSON-RPC method `getblock` with verbosity set to 3.
 #[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
 #[cfg_attr(feature = "serde-deny-unknown-fields", serde(deny_unknown_fields))]
@@ -3,6 +3,7 @@
 use core::fmt;
 
 use bitcoin::error::UnprefixedHexError;
+use bitcoin::network;
 
 use crate::error::write_err;
 
@@ -13,6 +14,8 @@ pub enum GetMiningInfoError {
     Bits(UnprefixedHexError),
     /// Conversion of the `target` field failed.
     Target(UnprefixedHexError),
+    /// Conversion of the `chain` field failed.
+    Chain(network::ParseNetworkError),
     /// Conversion of one of the items in field `next` failed.
     Next(NextBlockInfoError),
 }
@@ -22,6 +25,7 @@ impl fmt::Display for GetMiningInfoError {
         match *self {
             Self::Bits(ref e) => write_err!(f, "conversion of the `bits` field failed"; e),
             Self::Target(ref e) => write_err!(f, "conversion of the `target` field failed"; e),
+            Self::Chain(ref e) => write_err!(f, "conversion of the `chain` field failed"; e),
             Self::Next(ref e) =>
                 write_err!(f, "conversion of one of the items in field `next` failed"; e),
         }
@@ -34,6 +38,7 @@ impl std::error::Error for GetMiningInfoError {
         match *self {
             Self::Bits(ref e) => Some(e),
             Self::Target(ref e) => Some(e),
+            Self::Chain(ref e) => Some(e),
             Self::Next(ref e) => Some(e),
         }
     }
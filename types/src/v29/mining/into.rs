@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: CC0-1.0
 
-use bitcoin::{CompactTarget, Target, Weight};
+use bitcoin::{CompactTarget, Network, Target, Weight};
 
 use super::{GetMiningInfo, GetMiningInfoError, NextBlockInfo, NextBlockInfoError};
 use crate::model;
@@ -14,6 +14,7 @@ impl GetMiningInfo {
         let bits = CompactTarget::from_unprefixed_hex(&self.bits).map_err(E::Bits)?;
         let target = Target::from_unprefixed_hex(self.target.as_ref()).map_err(E::Target)?;
 
+        let chain = Network::from_core_arg(&self.chain).map_err(E::Chain)?;
         let next = self.next.into_model().map_err(E::Next)?;
 
         Ok(model::GetMiningInfo {
@@ -26,7 +27,7 @@ impl GetMiningInfo {
             network_hash_ps: self.network_hash_ps,
             pooled_tx: self.pooled_tx,
             block_min_tx_fee: None,
-            chain: self.chain,
+            chain,
             signet_challenge: self.signet_challenge,
             next: Some(next),
             warnings: self.warnings,
@@ -158,7 +158,7 @@
 //! | createmultisig                     | version + model |                                        |
 //! | deriveaddresses                    | version + model |                                        |
 //! | estimatesmartfee                   | version + model |                                        |
-//! | getdescriptorinfo                  | version         |                                        |
+//! | getdescriptorinfo                  | version + model |                                        |
 //! | getindexinfo                       | version         |                                        |
 //! | signmessagewithprivkey             | version + model |                                        |
 //! | validateaddress                    | version + model |                                        |
@@ -194,7 +194,7 @@
 //! | getunconfirmedbalance              | version + model |                                        |
 //! | getwalletinfo                      | version + model |                                        |
 //! | importaddress                      | returns nothing |                                        |
-//! | importdescriptors                  | version         |                                        |
+//! | importdescriptors                  | version + model |                                        |
 //! | importmulti                        | version         |                                        |
 //! | importprivkey                      | returns nothing |                                        |
 //! | importprunedfunds                  | returns nothing |                                        |
@@ -202,7 +202,7 @@
 //! | importwallet                       | returns nothing |                                        |
 //! | keypoolrefill                      | returns nothing |                                        |
 //! | listaddressgroupings               | version + model |                                        |
-//! | listdescriptors                    | version         |                                        |
+//! | listdescriptors                    | version + model |                                        |
 //! | listlabels                         | version         |                                        |
 //! | listlockunspent                    | version + model |                                        |
 //! | migratewallet                      | version         |                                        |
@@ -264,10 +264,10 @@ pub use self::{
         GetBlockHeaderVerboseError, GetBlockVerboseOne, GetBlockVerboseOneError,
         GetBlockVerboseThree, GetBlockVerboseThreeError, GetBlockVerboseThreePrevout,
         GetBlockVerboseThreeTransaction, GetBlockVerboseTwo, GetBlockVerboseTwoError,
-        GetBlockVerboseTwoTransaction, GetBlockchainInfo, GetBlockchainInfoError, GetChainStates,
-        GetChainStatesError, GetDescriptorActivity, GetDescriptorActivityError,
-        GetRawTransactionVerboseWithPrevout, RawTransactionInputWithPrevout, ReceiveActivity,
-        SpendActivity,
+        GetBlockVerboseTwoLazy, GetBlockVerboseTwoTransaction, GetBlockVerboseTwoTransactionError,
+        GetBlockchainInfo, GetBlockchainInfoError, GetChainStates, GetChainStatesError,
+        GetDescriptorActivity, GetDescriptorActivityError, GetRawTransactionVerboseWithPrevout,
+        RawBlockTransaction, RawTransactionInputWithPrevout, ReceiveActivity, SpendActivity,
     },
     hidden::{
         GetOrphanTxs, GetOrphanTxsError, GetOrphanTxsVerboseOne, GetOrphanTxsVerboseOneEntry,
@@ -327,8 +327,8 @@ pub use crate::{
     v20::GenerateToDescriptor,
     v21::{
         AddPeerAddress, GetIndexInfo, GetIndexInfoName, GetRawMempoolSequence, ImportDescriptors,
-        ImportDescriptorsResult, PsbtBumpFee, PsbtBumpFeeError, Send, SendError, SendMany,
-        SendManyVerbose, UpgradeWallet,
+        ImportDescriptorsError, ImportDescriptorsResult, PsbtBumpFee, PsbtBumpFeeError, Send,
+        SendError, SendMany, SendManyVerbose, UpgradeWallet,
     },
     v22::{
         AddConnection, Banned, EnumerateSigners, GetNodeAddresses, ListBanned, NodeAddress,
@@ -58,6 +58,7 @@ impl MempoolAcceptance {
             fees,
             reject_reason: self.reject_reason,
             reject_details: self.reject_details,
+            package_error: self.package_error,
         })
     }
 }
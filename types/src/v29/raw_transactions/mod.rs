@@ -37,6 +37,10 @@ pub struct MempoolAcceptance {
     /// The transaction witness hash in hex.
     pub wtxid: String,
     /// If the mempool allows this tx to be inserted.
+    ///
+    /// Not present, and therefore false, if the tx was not fully validated due to a failure in
+    /// another tx of the package.
+    #[serde(default)]
     pub allowed: bool,
     /// Virtual transaction size as defined in BIP 141. This is different from actual serialized size for witness transactions as witness data is discounted (only present when 'allowed' is true).
     pub vsize: Option<i64>,
@@ -48,6 +52,9 @@ pub struct MempoolAcceptance {
     /// Rejection details (only present when 'allowed' is false and rejection details exist)
     #[serde(rename = "reject-details")]
     pub reject_details: Option<String>,
+    /// Package validation error, if any (only possible if rawtxs had more than 1 transaction).
+    #[serde(rename = "package-error")]
+    pub package_error: Option<String>,
 }
 
 /// Wrapper for the fees field. Part of `testmempoolaccept`.
@@ -59,3 +59,17 @@ pub struct GetDescriptorInfo {
     #[serde(rename = "hasprivatekeys")]
     pub has_private_keys: bool,
 }
+
+impl GetDescriptorInfo {
+    /// Converts version specific type to a version nonspecific, more strongly typed type.
+    pub fn into_model(self) -> model::GetDescriptorInfo {
+        model::GetDescriptorInfo {
+            descriptor: self.descriptor,
+            checksum: Some(self.checksum),
+            multipath_expansion: self.multipath_expansion,
+            is_range: self.is_range,
+            is_solvable: self.is_solvable,
+            has_private_keys: self.has_private_keys,
+        }
+    }
+}
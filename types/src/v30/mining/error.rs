@@ -4,6 +4,7 @@ use core::fmt;
 
 use bitcoin::amount::ParseAmountError;
 use bitcoin::error::UnprefixedHexError;
+use bitcoin::network;
 
 use super::NextBlockInfoError;
 use crate::error::write_err;
@@ -17,6 +18,8 @@ pub enum GetMiningInfoError {
     Target(UnprefixedHexError),
     /// Conversion of the `block_min_tx_fee` field failed.
     BlockMinTxFee(ParseAmountError),
+    /// Conversion of the `chain` field failed.
+    Chain(network::ParseNetworkError),
     /// Conversion of one of the items in field `next` failed.
     Next(NextBlockInfoError),
 }
@@ -28,6 +31,7 @@ impl fmt::Display for GetMiningInfoError {
             Self::Target(ref e) => write_err!(f, "conversion of the `target` field failed"; e),
             Self::BlockMinTxFee(ref e) =>
                 write_err!(f, "conversion of the `block_min_tx_fee` field failed"; e),
+            Self::Chain(ref e) => write_err!(f, "conversion of the `chain` field failed"; e),
             Self::Next(ref e) =>
                 write_err!(f, "conversion of one of the items in field `next` failed"; e),
         }
@@ -41,6 +45,7 @@ impl std::error::Error for GetMiningInfoError {
             Self::Bits(ref e) => Some(e),
             Self::Target(ref e) => Some(e),
             Self::BlockMinTxFee(ref e) => Some(e),
+            Self::Chain(ref e) => Some(e),
             Self::Next(ref e) => Some(e),
         }
     }
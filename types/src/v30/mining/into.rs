@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: CC0-1.0
 
-use bitcoin::{CompactTarget, Target, Weight};
+use bitcoin::{CompactTarget, Network, Target, Weight};
 
 use super::{GetMiningInfo, GetMiningInfoError};
 use crate::model;
@@ -16,6 +16,7 @@ impl GetMiningInfo {
         let block_min_tx_fee =
             crate::btc_per_kb(self.block_min_tx_fee).map_err(E::BlockMinTxFee)?;
 
+        let chain = Network::from_core_arg(&self.chain).map_err(E::Chain)?;
         let next = self.next.into_model().map_err(E::Next)?;
 
         Ok(model::GetMiningInfo {
@@ -28,7 +29,7 @@ impl GetMiningInfo {
             network_hash_ps: self.network_hash_ps,
             pooled_tx: self.pooled_tx,
             block_min_tx_fee,
-            chain: self.chain,
+            chain,
             signet_challenge: self.signet_challenge,
             next: Some(next),
             warnings: self.warnings,
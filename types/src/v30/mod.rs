@@ -161,7 +161,7 @@
 //! | createmultisig                     | version + model |                                        |
 //! | deriveaddresses                    | version + model |                                        |
 //! | estimatesmartfee                   | version + model |                                        |
-//! | getdescriptorinfo                  | version         |                                        |
+//! | getdescriptorinfo                  | version + model |                                        |
 //! | getindexinfo                       | version         |                                        |
 //! | signmessagewithprivkey             | version + model |                                        |
 //! | validateaddress                    | version + model |                                        |
@@ -192,11 +192,11 @@
 //! | getreceivedbylabel                 | version + model |                                        |
 //! | gettransaction                     | version + model |                                        |
 //! | getwalletinfo                      | version + model |                                        |
-//! | importdescriptors                  | version         |                                        |
+//! | importdescriptors                  | version + model |                                        |
 //! | importprunedfunds                  | returns nothing |                                        |
 //! | keypoolrefill                      | returns nothing |                                        |
 //! | listaddressgroupings               | version + model |                                        |
-//! | listdescriptors                    | version         |                                        |
+//! | listdescriptors                    | version + model |                                        |
 //! | listlabels                         | version         |                                        |
 //! | listlockunspent                    | version + model |                                        |
 //! | migratewallet                      | version         | Untested in v30, unchanged from v29    |
@@ -316,8 +316,8 @@ pub use crate::{
     v20::GenerateToDescriptor,
     v21::{
         AddPeerAddress, GetIndexInfo, GetIndexInfoName, GetRawMempoolSequence, ImportDescriptors,
-        ImportDescriptorsResult, PsbtBumpFee, PsbtBumpFeeError, Send, SendError, SendMany,
-        SendManyVerbose,
+        ImportDescriptorsError, ImportDescriptorsResult, PsbtBumpFee, PsbtBumpFeeError, Send,
+        SendError, SendMany, SendManyVerbose,
     },
     v22::{
         AddConnection, Banned, EnumerateSigners, GetNodeAddresses, ListBanned, NodeAddress,
@@ -362,11 +362,12 @@ pub use crate::{
         GetBlockHeaderVerbose, GetBlockHeaderVerboseError, GetBlockVerboseOne,
         GetBlockVerboseOneError, GetBlockVerboseThree, GetBlockVerboseThreeError,
         GetBlockVerboseThreePrevout, GetBlockVerboseThreeTransaction, GetBlockVerboseTwo,
-        GetBlockVerboseTwoError, GetBlockVerboseTwoTransaction, GetBlockchainInfo,
-        GetBlockchainInfoError, GetChainStates, GetChainStatesError, GetDescriptorActivity,
-        GetDescriptorActivityError, GetDescriptorInfo, GetOrphanTxsError,
-        GetRawTransactionVerboseWithPrevout, MempoolAcceptance, MempoolAcceptanceFees,
-        NextBlockInfo, NextBlockInfoError, RawTransactionInputWithPrevout, ReceiveActivity,
-        SpendActivity, TestMempoolAccept,
+        GetBlockVerboseTwoError, GetBlockVerboseTwoLazy, GetBlockVerboseTwoTransaction,
+        GetBlockVerboseTwoTransactionError, GetBlockchainInfo, GetBlockchainInfoError,
+        GetChainStates, GetChainStatesError, GetDescriptorActivity, GetDescriptorActivityError,
+        GetDescriptorInfo, GetOrphanTxsError, GetRawTransactionVerboseWithPrevout,
+        MempoolAcceptance, MempoolAcceptanceFees, NextBlockInfo, NextBlockInfoError,
+        RawBlockTransaction, RawTransactionInputWithPrevout, ReceiveActivity, SpendActivity,
+        TestMempoolAccept,
     },
 };
@@ -96,7 +96,7 @@ pub const METHODS: &[Method] = &[
     Method::new_modelled("createmultisig", "CreateMultisig", "create_multisig"),
     Method::new_modelled("deriveaddresses", "DeriveAddresses", "derive_addresses"),
     Method::new_modelled("estimatesmartfee", "EstimateSmartFee", "estimate_smart_fee"),
-    Method::new_no_model("getdescriptorinfo", "GetDescriptorInfo", "get_descriptor_info"),
+    Method::new_modelled("getdescriptorinfo", "GetDescriptorInfo", "get_descriptor_info"),
     Method::new_modelled(
         "signmessagewithprivkey",
         "SignMessageWithPrivKey",
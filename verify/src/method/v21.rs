@@ -98,7 +98,7 @@ pub const METHODS: &[Method] = &[
     Method::new_modelled("createmultisig", "CreateMultisig", "create_multisig"),
     Method::new_modelled("deriveaddresses", "DeriveAddresses", "derive_addresses"),
     Method::new_modelled("estimatesmartfee", "EstimateSmartFee", "estimate_smart_fee"),
-    Method::new_no_model("getdescriptorinfo", "GetDescriptorInfo", "get_descriptor_info"),
+    Method::new_modelled("getdescriptorinfo", "GetDescriptorInfo", "get_descriptor_info"),
     Method::new_no_model("getindexinfo", "GetIndexInfo", "get_index_info"),
     Method::new_modelled(
         "signmessagewithprivkey",
@@ -133,7 +133,7 @@ pub const METHODS: &[Method] = &[
     ),
     Method::new_modelled("getwalletinfo", "GetWalletInfo", "get_wallet_info"),
     Method::new_nothing("importaddress", "import_address"),
-    Method::new_no_model("importdescriptors", "ImportDescriptors", "import_descriptors"),
+    Method::new_modelled("importdescriptors", "ImportDescriptors", "import_descriptors"),
     Method::new_no_model("importmulti", "ImportMulti", "import_multi"),
     Method::new_nothing("importprivkey", "import_priv_key"),
     Method::new_nothing("importprunedfunds", "import_pruned_funds"),
@@ -100,7 +100,7 @@ pub const METHODS: &[Method] = &[
     Method::new_modelled("createmultisig", "CreateMultisig", "create_multisig"),
     Method::new_modelled("deriveaddresses", "DeriveAddresses", "derive_addresses"),
     Method::new_modelled("estimatesmartfee", "EstimateSmartFee", "estimate_smart_fee"),
-    Method::new_no_model("getdescriptorinfo", "GetDescriptorInfo", "get_descriptor_info"),
+    Method::new_modelled("getdescriptorinfo", "GetDescriptorInfo", "get_descriptor_info"),
     Method::new_no_model("getindexinfo", "GetIndexInfo", "get_index_info"),
     Method::new_modelled(
         "signmessagewithprivkey",
@@ -135,7 +135,7 @@ pub const METHODS: &[Method] = &[
     ),
     Method::new_modelled("getwalletinfo", "GetWalletInfo", "get_wallet_info"),
     Method::new_nothing("importaddress", "import_address"),
-    Method::new_no_model("importdescriptors", "ImportDescriptors", "import_descriptors"),
+    Method::new_modelled("importdescriptors", "ImportDescriptors", "import_descriptors"),
     Method::new_no_model("importmulti", "ImportMulti", "import_multi"),
     Method::new_nothing("importprivkey", "import_priv_key"),
     Method::new_nothing("importprunedfunds", "import_pruned_funds"),
@@ -143,7 +143,7 @@ pub const METHODS: &[Method] = &[
     Method::new_nothing("importwallet", "import_walet"),
     Method::new_nothing("keypoolrefill", "keypool_refill"),
     Method::new_modelled("listaddressgroupings", "ListAddressGroupings", "list_address_groupings"),
-    Method::new_no_model("listdescriptors", "ListDescriptors", "list_descriptors"),
+    Method::new_modelled("listdescriptors", "ListDescriptors", "list_descriptors"),
     Method::new_no_model("listlabels", "ListLabels", "list_labels"),
     Method::new_modelled("listlockunspent", "ListLockUnspent", "list_lock_unspent"),
     Method::new_modelled("psbtbumpfee", "PsbtBumpFee", "psbt_bump_fee"),